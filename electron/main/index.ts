@@ -10,7 +10,18 @@ import { ProPresenterClient } from '../../src/propresenter-client';
 import { collectPlaylistLyrics, PlaylistProgressEvent } from '../../src/services/playlist-exporter';
 import { mapPlaylistTree, PlaylistTreeNode } from '../../src/utils/playlist-utils';
 import { findLogoPath } from '../../src/services/logo';
-import { exportToPowerPoint, DEFAULT_PPTX_TEXT_STYLE, PptxTextStyle } from '../../src/pptx-exporter';
+import { PptxTextStyle, PptxTextStyleOverrides, TEXT_STYLE_SETTINGS, explicitTextStyle } from '../../src/pptx-exporter';
+import { describeExportFormats, requireExporter, resolveExportOptions, describeExportProgress } from '../../src/exporters';
+import type { ExportProgressEvent } from '../../src/exporters';
+import { recordExportUsage } from '../../src/services/usage-store';
+import {
+  loadServiceLibraries,
+  matchServiceSongs,
+  matchVerseReferences,
+  buildServicePlaylist,
+  recordServicePlaylistUsage,
+} from '../../src/services/service-pipeline';
+import type { SongItemToMatch, ServicePlaylistItem } from '../../src/services/service-pipeline';
 // PDFParser is lazy-loaded in the pdf:parse handler to avoid DOMMatrix errors at startup
 import { SongMatcher } from '../../src/services/song-matcher';
 import { BibleFetcher } from '../../src/services/bible-fetcher';
@@ -21,13 +32,18 @@ interface AppSettings {
   port: number;
   libraryFilter: string | null;
   includeSongTitles: boolean;
-  textColor: string;
-  fontFace: string;
-  fontSize: number;
-  titleFontSize: number;
-  bold: boolean;
-  italic: boolean;
+  // PPTX text style; unset (null) fields fall back to the theme, then the defaults
+  textColor?: string | null;
+  fontFace?: string | null;
+  fontSize?: number | null;
+  titleFontSize?: number | null;
+  bold?: boolean | null;
+  italic?: boolean | null;
   logoPath?: string | null;
+  /** .pptx whose slide master PPTX exports use instead of the theme */
+  pptxTemplatePath?: string | null;
+  /** PPTX theme id; unset means the classic layout */
+  pptxTheme?: string | null;
   lastPlaylistId?: string;
   // Service Generator
   enableServiceGenerator?: boolean;
@@ -35,6 +51,8 @@ interface AppSettings {
   kidsLibraryId?: string | null;
   serviceContentLibraryId?: string | null;
   templatePlaylistId?: string | null;
+  /** Service order grammar id; unset means the built-in one */
+  serviceGrammar?: string | null;
   // Birthday Bucket
   enableBirthdayBucket?: boolean;
   birthdayChurchName?: string | null;
@@ -56,8 +74,10 @@ interface ExportPayload extends ConnectionConfig {
   playlistName: string;
   libraryFilter?: string | null;
   includeSongTitles?: boolean;
-  styleOverrides?: Partial<PptxTextStyle>;
+  styleOverrides?: PptxTextStyleOverrides;
   logoPath?: string | null;
+  format?: string;
+  formatOptions?: Record<string, unknown>;
 }
 
 const DEFAULT_HOST = process.env.PROPRESENTER_HOST || '127.0.0.1';
@@ -71,12 +91,6 @@ const settings = new Store<AppSettings>({
     port: DEFAULT_PORT,
     libraryFilter: DEFAULT_LIBRARY,
     includeSongTitles: true,
-    textColor: DEFAULT_PPTX_TEXT_STYLE.textColor,
-    fontFace: DEFAULT_PPTX_TEXT_STYLE.fontFace,
-    fontSize: DEFAULT_PPTX_TEXT_STYLE.fontSize,
-    titleFontSize: DEFAULT_PPTX_TEXT_STYLE.titleFontSize,
-    bold: DEFAULT_PPTX_TEXT_STYLE.bold,
-    italic: DEFAULT_PPTX_TEXT_STYLE.italic,
     logoPath: null,
   },
 });
@@ -132,8 +146,8 @@ function slugify(value: string): string {
     .slice(0, 60) || 'playlist';
 }
 
-function defaultOutputPath(playlistName: string): string {
-  const suggested = `${slugify(playlistName)}-${Date.now()}.pptx`;
+function defaultOutputPath(playlistName: string, extension = 'pptx'): string {
+  const suggested = `${slugify(playlistName)}-${Date.now()}.${extension}`;
   return path.join(app.getPath('documents'), suggested);
 }
 
@@ -294,7 +308,7 @@ async function checkSingleFont(fontName: string): Promise<FontStatus | null> {
 }
 
 function resolveStyleOverrides(payload: ExportPayload): Partial<PptxTextStyle> {
-  const stored: Partial<PptxTextStyle> = {
+  const stored: PptxTextStyleOverrides = {
     textColor: settings.get('textColor'),
     fontFace: settings.get('fontFace'),
     fontSize: settings.get('fontSize'),
@@ -303,15 +317,16 @@ function resolveStyleOverrides(payload: ExportPayload): Partial<PptxTextStyle> {
     italic: settings.get('italic'),
   };
 
-  const overrides: Partial<PptxTextStyle> = {
+  const overrides: PptxTextStyleOverrides = {
     ...stored,
     ...payload.styleOverrides,
   };
 
-  return {
+  // Fields the user has not set (null) are dropped so the selected theme's fonts apply
+  return explicitTextStyle({
     ...overrides,
     textColor: sanitizeColor(overrides.textColor),
-  };
+  });
 }
 
 function forwardProgress(playlistId: string, event: PlaylistProgressEvent, window: BrowserWindow['webContents']): void {
@@ -365,6 +380,15 @@ function forwardProgress(playlistId: string, event: PlaylistProgressEvent, windo
   }
 }
 
+function forwardExportProgress(playlistId: string, event: ExportProgressEvent, window: BrowserWindow['webContents']): void {
+  window.send('export:progress', {
+    playlistId,
+    type: event.overflow ? 'warning' : event.action === 'split' ? 'slide:split' : 'slide:fit',
+    itemName: event.song,
+    message: describeExportProgress(event),
+  });
+}
+
 async function runPlaylistExport(
   payload: ExportPayload,
   targetPath: string,
@@ -388,9 +412,12 @@ async function runPlaylistExport(
     throw new Error('No lyric slides found in this playlist.');
   }
 
+  const exporter = requireExporter(payload.format || 'pptx');
+
   window.send('export:progress', {
     playlistId: payload.playlistId,
     type: 'pptx:start',
+    format: exporter.id,
     totalSongs: result.songs.length,
   });
 
@@ -400,17 +427,25 @@ async function runPlaylistExport(
   const logoPath =
     payload.logoPath?.trim() || settings.get('logoPath') || findLogoPath([path.join(app.getAppPath(), 'logo.png')]);
 
-  const lyricsOnly = result.songs.map(entry => entry.lyrics);
-  const finalPath = await exportToPowerPoint(lyricsOnly, {
-    outputPath: targetPath,
-    logoPath,
+  const options = resolveExportOptions(exporter, {
+    ...resolveStyleOverrides(payload),
     includeSongTitles,
-    styleOverrides: resolveStyleOverrides(payload),
+    logoPath,
+    theme: settings.get('pptxTheme') || undefined,
+    templatePath: settings.get('pptxTemplatePath') || undefined,
+    ...payload.formatOptions,
   });
 
+  const lyricsOnly = result.songs.map(entry => entry.lyrics);
+  const finalPath = await exporter.write(lyricsOnly, targetPath, options, {
+    onProgress: (event) => forwardExportProgress(payload.playlistId, event, window),
+  });
+  recordExportUsage(lyricsOnly, { name: payload.playlistName, id: payload.playlistId });
+
   window.send('export:progress', {
     playlistId: payload.playlistId,
     type: 'pptx:complete',
+    format: exporter.id,
     outputPath: finalPath,
   });
 
@@ -462,6 +497,66 @@ ipcMain.handle('planned-services:delete', async (_event, id: string) => {
   return { deleted: deletePlannedService(id) };
 });
 
+ipcMain.handle('service-sessions:list', async () => {
+  const { loadServiceSessions } = await import('../../src/services/service-session-store');
+  return loadServiceSessions();
+});
+
+ipcMain.handle('service-sessions:get', async (_event, id: string) => {
+  const { getServiceSession } = await import('../../src/services/service-session-store');
+  return getServiceSession(id) || null;
+});
+
+ipcMain.handle('service-sessions:save', async (_event, session: any) => {
+  const { saveServiceSession, validateServiceSession } = await import('../../src/services/service-session-store');
+  const errors = validateServiceSession(session);
+  if (errors.length > 0) {
+    throw new Error(errors.join('; '));
+  }
+  return saveServiceSession(session);
+});
+
+ipcMain.handle('service-sessions:delete', async (_event, id: string) => {
+  const { deleteServiceSession } = await import('../../src/services/service-session-store');
+  return { deleted: deleteServiceSession(id) };
+});
+
+// Full-text lyric search for the manual song picker. Libraries that were
+// never indexed are crawled first, so the first search can take a while.
+ipcMain.handle('library:search-lyrics', async (_event, config: ConnectionConfig, libraryIds: string[], query: string) => {
+  try {
+    const { buildLyricIndex, resolveLibraryIds, searchLyrics, unindexedLibraries } = await import('../../src/services/lyric-search');
+    const { ProPresenterClient } = await import('../../src/propresenter-client');
+    const client = new ProPresenterClient(config);
+    const ids = await resolveLibraryIds(client, libraryIds);
+    const missing = unindexedLibraries(ids);
+    if (missing.length > 0) {
+      await buildLyricIndex(client, { libraries: missing });
+    }
+    const results = searchLyrics(query, { libraryIds: ids }).map(result => ({
+      uuid: result.uuid,
+      name: result.title,
+      library: result.library,
+      section: result.section,
+      snippet: result.snippet,
+    }));
+    return { success: true, results };
+  } catch (error: any) {
+    return { success: false, error: error.message, results: [] };
+  }
+});
+
+ipcMain.handle('library:index-lyrics', async (_event, config: ConnectionConfig, libraryIds: string[]) => {
+  try {
+    const { buildLyricIndex } = await import('../../src/services/lyric-search');
+    const { ProPresenterClient } = await import('../../src/propresenter-client');
+    const indexed = await buildLyricIndex(new ProPresenterClient(config), { libraries: libraryIds });
+    return { success: true, songs: indexed.reduce((total, library) => total + library.songs, 0) };
+  } catch (error: any) {
+    return { success: false, error: error.message };
+  }
+});
+
 // Search presentations across libraries (for manual song override)
 ipcMain.handle('library:search-presentations', async (_event, config: ConnectionConfig, libraryIds: string[], query: string) => {
   try {
@@ -519,6 +614,22 @@ ipcMain.handle('logo:choose', async () => {
   return { canceled: false, filePath: result.filePaths[0] };
 });
 
+ipcMain.handle('pptx-template:choose', async () => {
+  const result = await dialog.showOpenDialog({
+    title: 'Select PowerPoint Master Template',
+    properties: ['openFile'],
+    filters: [
+      { name: 'PowerPoint', extensions: ['pptx'] },
+    ],
+  });
+
+  if (result.canceled || !result.filePaths[0]) {
+    return { canceled: true };
+  }
+
+  return { canceled: false, filePath: result.filePaths[0] };
+});
+
 /**
  * Check if ProPresenter is running
  */
@@ -711,12 +822,36 @@ ipcMain.handle('libraries:list', async (_event, config: ConnectionConfig) => {
   }
 });
 
+ipcMain.handle('export:formats', () => describeExportFormats());
+
+ipcMain.handle('themes:list', async () => {
+  const { listThemes } = await import('../../src/services/theme-store');
+  return listThemes();
+});
+
+// Lyric classification rules
+ipcMain.handle('rules:load', async () => {
+  const { loadClassificationRules } = await import('../../src/services/classification-rules');
+  return loadClassificationRules();
+});
+
+ipcMain.handle('rules:save', async (_event, ruleSet: any) => {
+  const { saveClassificationRules } = await import('../../src/services/classification-rules');
+  return saveClassificationRules(ruleSet);
+});
+
+ipcMain.handle('rules:reset', async () => {
+  const { resetClassificationRules } = await import('../../src/services/classification-rules');
+  return resetClassificationRules();
+});
+
 ipcMain.handle('export:start', async (event, payload: ExportPayload) => {
+  const exporter = requireExporter(payload.format || 'pptx');
   const target = await dialog.showSaveDialog({
-    title: 'Save PPTX Export',
-    defaultPath: defaultOutputPath(payload.playlistName),
+    title: `Save ${exporter.label} Export`,
+    defaultPath: defaultOutputPath(payload.playlistName, exporter.extension),
     buttonLabel: 'Export',
-    filters: [{ name: 'PowerPoint', extensions: ['pptx'] }],
+    filters: [{ name: exporter.label, extensions: [exporter.extension] }],
   });
 
   if (target.canceled || !target.filePath) {
@@ -753,6 +888,12 @@ ipcMain.handle('export:start', async (event, payload: ExportPayload) => {
       if (typeof payload.styleOverrides.italic === 'boolean') {
         valuesToPersist.italic = payload.styleOverrides.italic;
       }
+      // Fields cleared in the app (e.g. by picking a theme) are cleared here too
+      for (const key of TEXT_STYLE_SETTINGS) {
+        if (payload.styleOverrides[key] === null) {
+          valuesToPersist[key] = null;
+        }
+      }
     }
     if (payload.logoPath) {
       valuesToPersist.logoPath = payload.logoPath;
@@ -803,10 +944,13 @@ ipcMain.handle('playlist:create-from-template', async (_event, config: Connectio
 // Service Generator IPC handlers
 ipcMain.handle('pdf:choose', async () => {
   const result = await dialog.showOpenDialog({
-    title: 'Select Service Order PDF',
+    title: 'Select Service Order',
     properties: ['openFile'],
     filters: [
+      { name: 'Service Orders', extensions: ['pdf', 'docx', 'txt', 'md'] },
       { name: 'PDF Files', extensions: ['pdf'] },
+      { name: 'Word Documents', extensions: ['docx'] },
+      { name: 'Text and Markdown', extensions: ['txt', 'md'] },
     ],
   });
 
@@ -817,11 +961,12 @@ ipcMain.handle('pdf:choose', async () => {
   return { canceled: false, filePath: result.filePaths[0] };
 });
 
-ipcMain.handle('pdf:parse', async (_event, filePath: string) => {
+ipcMain.handle('pdf:parse', async (_event, filePath: string, grammarId?: string) => {
   try {
     const { PDFParser } = await import('../../src/services/pdf-parser');
-    const parser = new PDFParser();
-    const result = await parser.parsePDF(filePath);
+    const { requireServiceGrammar } = await import('../../src/services/service-grammar');
+    const parser = new PDFParser(requireServiceGrammar(grammarId || settings.get('serviceGrammar')));
+    const result = await parser.parseFile(filePath);
     // Convert parsed service to simple items array for UI
     // Distinguish between regular songs and kids videos
     // Include praise slot for song ordering context
@@ -839,197 +984,28 @@ ipcMain.handle('pdf:parse', async (_event, filePath: string) => {
     // Include special service type for warnings and service-specific handling
     const specialServiceType = result.specialServiceType;
     
-    return { success: true, items, specialServiceType };
+    return { success: true, items, specialServiceType, service: result };
   } catch (error: any) {
-    return { success: false, error: error.message || 'Failed to parse PDF' };
+    return { success: false, error: error.message || 'Failed to parse service order' };
   }
 });
 
-// Song item type for matching - includes whether it's a kids video and special service awareness
-type SongItemToMatch = {
-  text: string;
-  isKidsVideo?: boolean;
-  praiseSlot?: string;
-  specialServiceType?: string | null;  // Type of service (remembrance, christmas, etc.)
-};
+ipcMain.handle('grammars:list', async () => {
+  const { listServiceGrammars } = await import('../../src/services/service-grammar');
+  return listServiceGrammars();
+});
 
 ipcMain.handle('songs:match', async (_event, songItems: SongItemToMatch[], config: ConnectionConfig, libraryIds: string[], kidsLibraryId?: string, serviceContentLibraryId?: string) => {
   try {
-    const { ProPresenterClient } = await import('../../src/propresenter-client');
-    const { SongMatcher } = await import('../../src/services/song-matcher');
-    const { loadAliases, aliasesToCustomMappings } = await import('../../src/services/alias-store');
-
     // ProPresenter API is REST/HTTP - no persistent connection needed
-    const client = new ProPresenterClient(config);
-
-    // Load persistent song aliases and pass to matcher
-    const aliases = loadAliases();
-    const customMappings = aliasesToCustomMappings(aliases);
-    const matcher = new SongMatcher(0.7, customMappings); // 70% confidence threshold
-
-    // Get library names for display
-    const libraries = await client.getLibraries();
-
-    // Fetch presentations from worship libraries (non-kids, non-service-content)
-    const worshipPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    for (const libraryId of libraryIds) {
-      if (!libraryId || libraryId === kidsLibraryId || libraryId === serviceContentLibraryId) continue;
-      try {
-        const presentations = await client.getLibraryPresentations(libraryId);
-        const library = libraries.find(l => l.uuid === libraryId);
-        const libraryName = library?.name || 'Unknown';
-
-        for (const pres of presentations) {
-          worshipPresentations.push({
-            uuid: pres.uuid,
-            name: pres.name,
-            library: libraryName,
-            libraryId: libraryId
-          });
-        }
-      } catch (err) {
-        console.error(`Failed to fetch library ${libraryId}:`, err);
-      }
-    }
-
-    // Fetch presentations from service content library (for videos)
-    const serviceContentPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    if (serviceContentLibraryId) {
-      try {
-        const presentations = await client.getLibraryPresentations(serviceContentLibraryId);
-        const library = libraries.find(l => l.uuid === serviceContentLibraryId);
-        const libraryName = library?.name || 'Service Content';
-
-        for (const pres of presentations) {
-          serviceContentPresentations.push({
-            uuid: pres.uuid,
-            name: pres.name,
-            library: libraryName,
-            libraryId: serviceContentLibraryId
-          });
-        }
-      } catch (err) {
-        console.error(`Failed to fetch service content library ${serviceContentLibraryId}:`, err);
-      }
-    }
-
-    // Fetch presentations from kids library separately
-    const kidsPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    if (kidsLibraryId) {
-      try {
-        const presentations = await client.getLibraryPresentations(kidsLibraryId);
-        const library = libraries.find(l => l.uuid === kidsLibraryId);
-        const libraryName = library?.name || 'Kids';
-
-        for (const pres of presentations) {
-          kidsPresentations.push({
-            uuid: pres.uuid,
-            name: pres.name,
-            library: libraryName,
-            libraryId: kidsLibraryId
-          });
-        }
-      } catch (err) {
-        console.error(`Failed to fetch kids library ${kidsLibraryId}:`, err);
-      }
-    }
-
-    console.log(`[songs:match] Loaded ${worshipPresentations.length} worship songs, ${serviceContentPresentations.length} service content items, ${kidsPresentations.length} kids songs`);
-
-    // Debug: Log received songItems
-    console.log(`[songs:match] Received ${songItems.length} song items:`);
-    for (const item of songItems) {
-      console.log(`  - text="${item.text}" (type=${typeof item.text}) isKids=${item.isKidsVideo} slot=${item.praiseSlot} service=${item.specialServiceType}`);
-    }
-
-    // Match each song against the appropriate library
-    const results = [];
-    for (let i = 0; i < songItems.length; i++) {
-      const item = songItems[i];
-      
-      // Determine library to search based on content type:
-      // - Kids videos (explicitly marked): Kids library
-      // - Non-kids videos (videos in special services, transition videos, closing videos): Service Content library
-      // - Songs: Worship library
-      
-      const isKids = item.isKidsVideo || item.praiseSlot === 'kids';
-      const isVideo = item.praiseSlot === 'kids_video' || (item.text && item.text.includes('(Video)'));
-      const isNonKidsVideo = isVideo && !isKids;
-
-      // Ensure text is a string
-      const songText = typeof item.text === 'string' ? item.text : String(item.text || '');
-      if (!songText) {
-        console.warn(`[songs:match] Skipping item ${i} - no text`);
-        continue;
-      }
-
-      // Choose which library to match against
-      let presentationsToMatch: Array<{ uuid: string; name: string; library: string; libraryId: string }>;
-      let matchContext: string;
-
-      if (isKids) {
-        // Kids content goes to kids library
-        presentationsToMatch = kidsPresentations;
-        matchContext = `kids`;
-      } else if (isNonKidsVideo) {
-        // Non-kids videos (closing videos, transition videos, special service videos) go to Service Content
-        presentationsToMatch = serviceContentPresentations;
-        matchContext = `service video (${item.specialServiceType || 'regular'})`;
-      } else {
-        // Regular songs go to worship library
-        presentationsToMatch = worshipPresentations;
-        matchContext = 'worship';
-      }
-
-      console.log(`[songs:match] Matching "${songText}" (${matchContext}) against ${presentationsToMatch.length} presentations`);
-
-      // Create service section for matcher
-      const songSection = {
-        type: 'song' as const,
-        title: songText,
-        position: i
-      };
-
-      // Match this single song
-      let matches = await matcher.matchSongs([songSection], presentationsToMatch);
-      let match = matches[0];
-
-      // Cross-library fallback: if kids/video content has no good match, try all libraries
-      if (isKids && (!match.bestMatch || match.bestMatch.confidence < 0.7)) {
-        const allPresentations = [...worshipPresentations, ...serviceContentPresentations, ...kidsPresentations];
-        const fallbackMatches = await matcher.matchSongs([songSection], allPresentations);
-        const fallbackMatch = fallbackMatches[0];
-
-        if (fallbackMatch.bestMatch && fallbackMatch.bestMatch.confidence > (match.bestMatch?.confidence || 0)) {
-          console.log(`[songs:match] Kids fallback found better match: "${fallbackMatch.bestMatch.presentation.name}" (${Math.round(fallbackMatch.bestMatch.confidence * 100)}%) from all libraries`);
-          match = fallbackMatch;
-        }
-      }
-
-      results.push({
-        songName: match.pdfTitle,
-        praiseSlot: item.praiseSlot,
-        isKidsVideo: isKids,
-        matches: match.matches.map(m => ({
-          uuid: m.presentation.uuid,
-          name: m.presentation.name,
-          library: m.presentation.library,
-          confidence: Math.round(m.confidence * 100)
-        })),
-        bestMatch: match.bestMatch ? {
-          uuid: match.bestMatch.presentation.uuid,
-          name: match.bestMatch.presentation.name,
-          library: match.bestMatch.presentation.library,
-          confidence: Math.round(match.bestMatch.confidence * 100)
-        } : undefined,
-        requiresReview: match.requiresReview,
-        selectedMatch: match.bestMatch && !match.requiresReview ? {
-          uuid: match.bestMatch.presentation.uuid,
-          name: match.bestMatch.presentation.name
-        } : undefined
-      });
-    }
+    const libraries = await loadServiceLibraries(createClient(config), {
+      libraryIds,
+      kidsLibraryId,
+      serviceContentLibraryId,
+    });
+    console.log(`[songs:match] Loaded ${libraries.worship.length} worship songs, ${libraries.serviceContent.length} service content items, ${libraries.kids.length} kids songs`);
 
+    const results = await matchServiceSongs(songItems, libraries);
     return { success: true, results };
   } catch (error: any) {
     console.error('Song matching error:', error);
@@ -1068,85 +1044,11 @@ ipcMain.handle('verses:match', async (_event, verseReferences: string[], config:
       };
     }
 
-    const { ProPresenterClient } = await import('../../src/propresenter-client');
-
     // ProPresenter API is REST/HTTP - no persistent connection needed
-    const client = new ProPresenterClient(config);
-
-    // Fetch presentations from service content library
-    const presentations = await client.getLibraryPresentations(serviceContentLibraryId);
+    const presentations = await createClient(config).getLibraryPresentations(serviceContentLibraryId);
     console.log(`[verses:match] Loaded ${presentations.length} presentations from service content library`);
 
-    // Pre-filter to Bible verse presentations (those with NIV, ESV, NLT, KJV etc. in name)
-    // This prevents worship songs and other content from appearing in verse matches
-    const bibleTranslationPattern = /\b(niv|esv|nlt|kjv|nkjv|nasb|csb|msg)\b/i;
-    const biblePresentations = presentations.filter(pres => bibleTranslationPattern.test(pres.name));
-    console.log(`[verses:match] Filtered to ${biblePresentations.length} Bible presentations (from ${presentations.length} total)`);
-
-    // Fall back to all presentations if no Bible-specific ones found
-    const searchPresentations = biblePresentations.length > 0 ? biblePresentations : presentations;
-
-    // Match each verse reference against presentations
-    const results = verseReferences.map(reference => {
-      const normalizedRef = reference.toLowerCase().trim();
-      // Normalize: remove punctuation (colons, underscores, hyphens, parentheses) for better matching
-      const normalizedRefStripped = normalizedRef.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-
-      // Extract book name and chapter from reference for targeted matching
-      // e.g., "Luke 12:35-59" → book="luke", chapter="12"
-      const refParts = normalizedRef.match(/^(\d?\s*[a-z]+)\s+(\d+)/);
-      const refBook = refParts ? refParts[1].replace(/\s+/g, ' ').trim() : '';
-      const refChapter = refParts ? refParts[2] : '';
-
-      // Find presentations that contain the reference in their name
-      const matches = searchPresentations
-        .filter(pres => {
-          const presName = pres.name.toLowerCase();
-          const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-
-          // Check if presentation name contains the reference
-          // e.g., "Luke 12:35-59" should match "Luke 12_35-59 (NIV)-1"
-          return presName.includes(normalizedRef) ||
-                 presNameStripped.includes(normalizedRefStripped) ||
-                 normalizedRef.includes(presName) ||
-                 normalizedRefStripped.includes(presNameStripped) ||
-                 // Match by book + chapter (more targeted than generic word matching)
-                 (refBook && refChapter && presName.includes(refBook) && presName.includes(refChapter));
-        })
-        .map(pres => {
-          // Calculate a simple confidence based on string similarity
-          const presName = pres.name.toLowerCase();
-          const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-          let confidence = 0;
-          if (presName === normalizedRef || presNameStripped === normalizedRefStripped) {
-            confidence = 100;
-          } else if (presName.includes(normalizedRef) || normalizedRef.includes(presName) ||
-                     presNameStripped.includes(normalizedRefStripped) || normalizedRefStripped.includes(presNameStripped)) {
-            confidence = 85;
-          } else {
-            confidence = 60;
-          }
-          return {
-            uuid: pres.uuid,
-            name: pres.name,
-            confidence
-          };
-        })
-        .sort((a, b) => b.confidence - a.confidence)
-        .slice(0, 5); // Top 5 matches
-
-      const bestMatch = matches[0];
-      const requiresReview = !bestMatch || bestMatch.confidence < 85;
-
-      return {
-        reference,
-        matches,
-        bestMatch,
-        requiresReview,
-        selectedMatch: bestMatch && !requiresReview ? { uuid: bestMatch.uuid, name: bestMatch.name } : undefined
-      };
-    });
-
+    const results = matchVerseReferences(verseReferences, presentations);
     return { success: true, results };
   } catch (error: any) {
     console.error('Bible verse matching error:', error);
@@ -1202,205 +1104,16 @@ ipcMain.handle('playlist:focus-item', async (_event, config: ConnectionConfig, p
   }
 });
 
-ipcMain.handle('playlist:build-service', async (_event, config: ConnectionConfig, playlistId: string, items: any[]) => {
+ipcMain.handle('playlist:build-service', async (_event, config: ConnectionConfig, playlistId: string, items: ServicePlaylistItem[]) => {
   try {
-    // Items should be an array of { type, uuid, name, praiseSlot } representing songs/videos
-    // praiseSlot can be: 'praise1', 'praise2', 'praise3', 'kids'
-
+    // Items are { type, uuid, name, praiseSlot }; praiseSlot is praise1, praise2, praise3, kids or reading
     console.log(`[playlist:build-service] Config: host=${config.host}, port=${config.port}, playlistId=${playlistId}`);
+    const playlist = await buildServicePlaylist(config.host, config.port, playlistId, items);
+    console.log(`[playlist:build-service] Built ${playlist.itemCount} items`);
 
-    // Step 1: Fetch current playlist items to preserve structure
-    const getUrl = `http://${config.host}:${config.port}/v1/playlist/${playlistId}`;
-    console.log(`[playlist:build-service] GET ${getUrl}`);
-    const getResponse = await fetch(getUrl);
-    if (!getResponse.ok) {
-      throw new Error(`Failed to fetch playlist: ${getResponse.status}`);
-    }
-    const playlistData = await getResponse.json() as any;
-    const currentItems = playlistData.items || [];
-    console.log(`[playlist:build-service] Found ${currentItems.length} existing items`);
-
-    // Step 2: Group incoming items by praise slot
-    const itemsBySlot: Record<string, any[]> = {
-      praise1: [],
-      praise2: [],
-      praise3: [],
-      kids: [],
-      reading: []
-    };
-    for (const item of items) {
-      const slot = item.praiseSlot || 'praise1';
-      if (itemsBySlot[slot]) {
-        itemsBySlot[slot].push(item);
-      }
-    }
-    console.log('[playlist:build-service] Items by slot:',
-      Object.entries(itemsBySlot).map(([k, v]) => `${k}: ${v.length}`).join(', '));
-
-    // Step 3: Map header names to slots
-    // These are the headers we look for in the template
-    const headerToSlot: Record<string, string> = {
-      'praise 1': 'praise1',
-      'praise1': 'praise1',
-      'praise 2': 'praise2',
-      'praise2': 'praise2',
-      'praise 3': 'praise3',
-      'praise3': 'praise3',
-      'kids talk': 'kids',
-      'kids': 'kids',
-      'kids song': 'kids',
-      'kids video': 'kids',
-      'reading': 'reading',
-      'bible': 'reading'
-    };
-
-    // Step 4: Build new playlist by replacing items in each section
-    const newItems: any[] = [];
-    let currentSlot: string | null = null;
-    let skipUntilNextHeader = false;
-
-    for (let i = 0; i < currentItems.length; i++) {
-      const item = currentItems[i];
-      const isHeader = item.type === 'header';
-      const itemName = (item.id?.name || item.name || '').toLowerCase().trim();
-
-      if (isHeader) {
-        // Check if this header marks a praise section
-        const matchedSlot = headerToSlot[itemName];
-        console.log(`[playlist:build-service] Header "${itemName}" -> slot: ${matchedSlot || 'NONE'}`);
-
-        if (matchedSlot) {
-          const slotItemCount = (itemsBySlot[matchedSlot] || []).length;
-          console.log(`[playlist:build-service]   Will insert ${slotItemCount} items for slot "${matchedSlot}"`);
-
-          // This is a praise section header - keep the header
-          newItems.push(item);
-
-          // Insert our matched songs for this slot
-          const slotItems = itemsBySlot[matchedSlot] || [];
-          for (const songItem of slotItems) {
-            newItems.push({
-              id: {
-                name: songItem.name,
-                uuid: songItem.uuid,
-                index: newItems.length
-              },
-              type: 'presentation',
-              is_hidden: false,
-              is_pco: false,
-              target_uuid: songItem.uuid,
-              presentation_info: {
-                presentation_uuid: songItem.uuid,
-                arrangement_name: '',
-                arrangement_uuid: ''
-              },
-              destination: 'presentation'
-            });
-          }
-
-          // Only skip original items if we're replacing with new ones
-          // If no new items, keep the originals
-          if (slotItems.length > 0) {
-            currentSlot = matchedSlot;
-            skipUntilNextHeader = true;
-          } else {
-            // No replacements - keep existing items in this slot
-            skipUntilNextHeader = false;
-            currentSlot = null;
-          }
-        } else {
-          // This is a different header (Birthday Blessings, Announcements, etc.)
-          // Stop skipping and keep this header
-          skipUntilNextHeader = false;
-          currentSlot = null;
-          newItems.push(item);
-        }
-      } else {
-        // Non-header item
-        if (skipUntilNextHeader) {
-          // Skip this item - it's a placeholder in a praise section we're replacing
-          continue;
-        } else {
-          // Keep this item - it's not in a praise section
-          newItems.push(item);
-        }
-      }
-    }
-
-    // Step 5: Clean all items before sending - ProPresenter PUT expects specific fields only
-    // Raw GET response items contain extra fields that cause PUT to return 404
-    // CRITICAL: presentation items must have a valid id.uuid (use presentation_uuid as fallback)
-    const cleanedItems = newItems.map((item: any, index: number) => {
-      // For presentations, ensure id.uuid is set - ProPresenter rejects items with empty uuid
-      let itemUuid = item.id?.uuid || '';
-      if (item.type === 'presentation' && !itemUuid && item.presentation_info?.presentation_uuid) {
-        itemUuid = item.presentation_info.presentation_uuid;
-      }
-
-      const cleaned: any = {
-        id: {
-          name: item.id?.name || item.name || 'Untitled',
-          index: index,
-          uuid: itemUuid,
-        },
-        type: item.type,
-        is_hidden: item.is_hidden || false,
-        is_pco: item.is_pco || false,
-      };
-
-      // Include target_uuid (required by ProPresenter for deserialization)
-      cleaned.target_uuid = item.target_uuid || '';
-
-      // For headers, include color
-      if (item.type === 'header') {
-        if (item.header_color) {
-          cleaned.header_color = item.header_color;
-        }
-      }
-
-      // For presentations, include presentation info and duration
-      if (item.type === 'presentation') {
-        if (item.presentation_info) {
-          cleaned.presentation_info = {
-            presentation_uuid: item.presentation_info.presentation_uuid,
-            arrangement_name: item.presentation_info.arrangement_name || '',
-            arrangement_uuid: item.presentation_info.arrangement_uuid || '',
-          };
-        }
-        if (item.duration) {
-          cleaned.duration = item.duration;
-        }
-      }
-
-      // Include destination if present
-      if (item.destination) {
-        cleaned.destination = item.destination;
-      }
-
-      return cleaned;
-    });
-
-    console.log(`[playlist:build-service] Built ${cleanedItems.length} items (was ${currentItems.length})`);
-
-    // Step 6: Send PUT request with cleaned items
-    const putBody = JSON.stringify(cleanedItems);
-    console.log(`[playlist:build-service] PUT http://${config.host}:${config.port}/v1/playlist/${playlistId} (${putBody.length} bytes, ${cleanedItems.length} items)`);
-
-    const putResponse = await fetch(`http://${config.host}:${config.port}/v1/playlist/${playlistId}`, {
-      method: 'PUT',
-      headers: { 'Content-Type': 'application/json' },
-      body: putBody,
-    });
-
-    console.log(`[playlist:build-service] PUT response: ${putResponse.status}`);
-
-    if (!putResponse.ok) {
-      const errorText = await putResponse.text();
-      console.error(`[playlist:build-service] PUT failed: ${putResponse.status} ${errorText}`);
-      throw new Error(`Failed to update playlist: ${putResponse.status} ${errorText}`);
-    }
+    await recordServicePlaylistUsage(createClient(config), { name: playlist.name, id: playlistId }, items);
 
-    return { success: true, itemCount: cleanedItems.length };
+    return { success: true, itemCount: playlist.itemCount };
   } catch (error: any) {
     console.error('Playlist build error:', error);
     return { success: false, error: error.message || 'Failed to build playlist' };
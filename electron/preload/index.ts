@@ -27,6 +27,7 @@ type SettingsPayload = {
   bold?: boolean;
   italic?: boolean;
   logoPath?: string | null;
+  pptxTheme?: string | null;
   lastPlaylistId?: string;
   // Service Generator
   enableServiceGenerator?: boolean;
@@ -34,6 +35,7 @@ type SettingsPayload = {
   kidsLibraryId?: string | null;
   serviceContentLibraryId?: string | null;
   templatePlaylistId?: string | null;
+  serviceGrammar?: string | null;
   // Birthday Bucket
   enableBirthdayBucket?: boolean;
   birthdayChurchName?: string | null;
@@ -57,6 +59,8 @@ interface ExportPayload extends ConnectionConfig {
     italic?: boolean;
   };
   logoPath?: string | null;
+  format?: string;
+  formatOptions?: Record<string, string | number | boolean>;
 }
 
 type ProgressEventPayload = {
@@ -66,6 +70,69 @@ type ProgressEventPayload = {
   itemName?: string;
   totalSongs?: number;
   outputPath?: string;
+  format?: string;
+};
+
+type ClassificationRuleSet = {
+  defaultAction: 'include' | 'exclude';
+  rules: Array<{
+    id: string;
+    description?: string;
+    action: 'include' | 'exclude';
+    priority: number;
+    disabled?: boolean;
+    match: Record<string, string | number | boolean>;
+  }>;
+};
+
+type ServiceGrammarSummary = {
+  id: string;
+  name: string;
+  description?: string;
+  builtIn: boolean;
+};
+
+type PptxThemeSummary = {
+  id: string;
+  name: string;
+  description?: string;
+  builtIn: boolean;
+  fonts: {
+    fontFace?: string;
+    fontSize?: number;
+    titleFontSize?: number;
+    textColor?: string;
+    bold?: boolean;
+    italic?: boolean;
+  };
+};
+
+type ExportFormatDescriptor = {
+  id: string;
+  label: string;
+  description: string;
+  extension: string;
+  mimeType: string;
+  textual: boolean;
+  options: Array<{
+    key: string;
+    label: string;
+    type: 'boolean' | 'string' | 'number' | 'color' | 'select';
+    description?: string;
+    default?: string | number | boolean;
+    choices?: Array<{ value: string; label: string }>;
+    min?: number;
+    max?: number;
+    fromSettings?: boolean;
+  }>;
+};
+
+type LyricSearchResult = {
+  uuid: string;
+  name: string;
+  library: string;
+  section: string;
+  snippet: string;
 };
 
 type FontStatus = {
@@ -82,7 +149,14 @@ const api = {
   fetchPlaylists: (config: ConnectionConfig) => ipcRenderer.invoke('playlists:list', config),
   fetchLibraries: (config: ConnectionConfig) => ipcRenderer.invoke('libraries:list', config),
   startExport: (payload: ExportPayload) => ipcRenderer.invoke('export:start', payload),
+  listExportFormats: (): Promise<ExportFormatDescriptor[]> => ipcRenderer.invoke('export:formats'),
+  listThemes: (): Promise<PptxThemeSummary[]> => ipcRenderer.invoke('themes:list'),
+  loadClassificationRules: (): Promise<ClassificationRuleSet> => ipcRenderer.invoke('rules:load'),
+  saveClassificationRules: (ruleSet: ClassificationRuleSet): Promise<ClassificationRuleSet> =>
+    ipcRenderer.invoke('rules:save', ruleSet),
+  resetClassificationRules: (): Promise<ClassificationRuleSet> => ipcRenderer.invoke('rules:reset'),
   chooseLogo: () => ipcRenderer.invoke('logo:choose'),
+  choosePptxTemplate: () => ipcRenderer.invoke('pptx-template:choose'),
   createPlaylistFromTemplate: (config: ConnectionConfig, templateId: string, playlistName: string) =>
     ipcRenderer.invoke('playlist:create-from-template', config, templateId, playlistName),
   // Shell utilities
@@ -110,9 +184,21 @@ const api = {
     query: string
   ): Promise<{ success: boolean; results: Array<{ uuid: string; name: string; library: string }>; error?: string }> =>
     ipcRenderer.invoke('library:search-presentations', config, libraryIds, query),
+  searchLyrics: (
+    config: ConnectionConfig,
+    libraryIds: string[],
+    query: string
+  ): Promise<{ success: boolean; results: LyricSearchResult[]; error?: string }> =>
+    ipcRenderer.invoke('library:search-lyrics', config, libraryIds, query),
+  indexLyrics: (
+    config: ConnectionConfig,
+    libraryIds: string[]
+  ): Promise<{ success: boolean; songs?: number; error?: string }> =>
+    ipcRenderer.invoke('library:index-lyrics', config, libraryIds),
   // Service Generator
   choosePDF: () => ipcRenderer.invoke('pdf:choose'),
-  parsePDF: (filePath: string) => ipcRenderer.invoke('pdf:parse', filePath),
+  parsePDF: (filePath: string, grammarId?: string) => ipcRenderer.invoke('pdf:parse', filePath, grammarId),
+  listServiceGrammars: (): Promise<ServiceGrammarSummary[]> => ipcRenderer.invoke('grammars:list'),
   matchSongs: (
     songItems: Array<{ text: string; isKidsVideo?: boolean; praiseSlot?: string }>,
     config: ConnectionConfig,
@@ -148,6 +234,14 @@ const api = {
     ipcRenderer.invoke('planned-services:save', service),
   deletePlannedService: (id: string): Promise<{ deleted: boolean }> =>
     ipcRenderer.invoke('planned-services:delete', id),
+  listServiceSessions: (): Promise<any[]> =>
+    ipcRenderer.invoke('service-sessions:list'),
+  getServiceSession: (id: string): Promise<any> =>
+    ipcRenderer.invoke('service-sessions:get', id),
+  saveServiceSession: (session: any): Promise<any> =>
+    ipcRenderer.invoke('service-sessions:save', session),
+  deleteServiceSession: (id: string): Promise<{ deleted: boolean }> =>
+    ipcRenderer.invoke('service-sessions:delete', id),
   // Birthday Bucket
   churchSuiteSync: (): Promise<{ success: boolean; contacts: number; children: number; syncedAt: string; error?: string }> =>
     ipcRenderer.invoke('churchsuite:sync'),
@@ -19,6 +19,8 @@ type SettingsState = {
   bold: boolean;
   italic: boolean;
   logoPath: string;
+  pptxTemplatePath: string;
+  pptxTheme: string;
   lastPlaylistId?: string;
   // Service Generator
   enableServiceGenerator: boolean;
@@ -26,6 +28,7 @@ type SettingsState = {
   kidsLibraryId: string;
   serviceContentLibraryId: string;
   templatePlaylistId: string;
+  serviceGrammar: string;
   // Birthday Bucket
   enableBirthdayBucket: boolean;
   churchSuiteClientId: string;
@@ -75,8 +78,11 @@ type RendererProgressEvent = {
   itemName?: string;
   totalSongs?: number;
   outputPath?: string;
+  format?: string;
 };
 
+type FormatOptionValues = Record<string, string | number | boolean>;
+
 const DEFAULT_HOST = '127.0.0.1';
 const DEFAULT_PORT = 1025;
 const DEFAULT_LIBRARY = 'Worship';
@@ -86,6 +92,11 @@ const DEFAULT_TITLE_SIZE = 54;
 const DEFAULT_COLOR = '#2d6a7a';
 const MAX_LOG_ITEMS = 80;
 
+// Font settings only override the theme once the user sets them
+type FontField = 'textColor' | 'fontFace' | 'fontSize' | 'titleFontSize' | 'bold' | 'italic';
+const FONT_FIELDS: FontField[] = ['textColor', 'fontFace', 'fontSize', 'titleFontSize', 'bold', 'italic'];
+const isFontField = (name: string): name is FontField => (FONT_FIELDS as string[]).includes(name);
+
 const colorWithHash = (value?: string | null): string => {
   if (!value) return DEFAULT_COLOR;
   return value.startsWith('#') ? value : `#${value}`;
@@ -164,11 +175,14 @@ function App(): JSX.Element {
     bold: true,
     italic: true,
     logoPath: '',
+    pptxTemplatePath: '',
+    pptxTheme: '',
     enableServiceGenerator: false,
     worshipLibraryId: '',
     kidsLibraryId: '',
     serviceContentLibraryId: '',
     templatePlaylistId: '',
+    serviceGrammar: '',
     enableBirthdayBucket: false,
     churchSuiteClientId: '',
     churchSuiteClientSecret: '',
@@ -192,6 +206,13 @@ function App(): JSX.Element {
   const [fontsLoading, setFontsLoading] = useState(false);
   const [selectedFontStatus, setSelectedFontStatus] = useState<FontStatus | null>(null);
   const [launching, setLaunching] = useState(false);
+  const [exportFormats, setExportFormats] = useState<ExportFormatDescriptor[]>([]);
+  const [exportFormat, setExportFormat] = useState('pptx');
+  const [formatOptions, setFormatOptions] = useState<FormatOptionValues>({});
+  const [themes, setThemes] = useState<PptxThemeSummary[]>([]);
+  const [fontFieldsSet, setFontFieldsSet] = useState<FontField[]>([]);
+  const [rulesText, setRulesText] = useState('');
+  const [rulesMessage, setRulesMessage] = useState<{ tone: 'success' | 'error'; text: string } | null>(null);
 
   useEffect(() => {
     (async () => {
@@ -209,24 +230,37 @@ function App(): JSX.Element {
         bold: typeof saved.bold === 'boolean' ? saved.bold : true,
         italic: typeof saved.italic === 'boolean' ? saved.italic : true,
         logoPath: saved.logoPath ?? '',
+        pptxTemplatePath: saved.pptxTemplatePath ?? '',
+        pptxTheme: saved.pptxTheme ?? '',
         lastPlaylistId: saved.lastPlaylistId,
         enableServiceGenerator: saved.enableServiceGenerator ?? false,
         worshipLibraryId: saved.worshipLibraryId ?? '',
         kidsLibraryId: saved.kidsLibraryId ?? '',
         serviceContentLibraryId: saved.serviceContentLibraryId ?? '',
         templatePlaylistId: saved.templatePlaylistId ?? '',
+        serviceGrammar: saved.serviceGrammar ?? '',
         enableBirthdayBucket: saved.enableBirthdayBucket ?? false,
         churchSuiteClientId: saved.churchSuiteClientId ?? '',
         churchSuiteClientSecret: saved.churchSuiteClientSecret ?? '',
         birthdayChurchName: saved.birthdayChurchName ?? '',
         birthdayBackgroundImagePath: saved.birthdayBackgroundImagePath ?? '',
       }));
+      setFontFieldsSet(FONT_FIELDS.filter(field => saved[field] !== undefined && saved[field] !== null));
       if (saved.lastPlaylistId) {
         setSelectedId(saved.lastPlaylistId);
       }
     })();
   }, []);
 
+  useEffect(() => {
+    window.api.listExportFormats()
+      .then(formats => setExportFormats(formats))
+      .catch(error => console.error('Failed to load export formats:', error));
+    window.api.listThemes()
+      .then(list => setThemes(list))
+      .catch(error => console.error('Failed to load themes:', error));
+  }, []);
+
   // Load font list when settings modal opens
   useEffect(() => {
     if (showSettings && fontList.length === 0) {
@@ -234,6 +268,15 @@ function App(): JSX.Element {
     }
   }, [showSettings]);
 
+  // Load classification rules when settings modal opens
+  useEffect(() => {
+    if (!showSettings) return;
+    setRulesMessage(null);
+    window.api.loadClassificationRules()
+      .then(ruleSet => setRulesText(JSON.stringify(ruleSet, null, 2)))
+      .catch(error => console.error('Failed to load classification rules:', error));
+  }, [showSettings]);
+
   // Update selected font status when font changes
   useEffect(() => {
     if (settings.fontFace && fontList.length > 0) {
@@ -346,7 +389,7 @@ function App(): JSX.Element {
 
   function mapTone(type: string): ProgressEntry['tone'] {
     if (type === 'song:success' || type === 'pptx:complete') return 'success';
-    if (type === 'warning' || type === 'song:skip') return 'warning';
+    if (type === 'warning' || type === 'song:skip' || type === 'slide:split') return 'warning';
     if (type === 'song:error' || type === 'error') return 'error';
     return 'info';
   }
@@ -366,7 +409,9 @@ function App(): JSX.Element {
       case 'collection:complete':
         return `Playlist ready (${event.totalSongs} songs)`;
       case 'pptx:start':
-        return 'Building PowerPoint deck';
+        return event.format && event.format !== 'pptx'
+          ? `Building ${event.format.toUpperCase()} export`
+          : 'Building PowerPoint deck';
       case 'pptx:complete':
         return `Export saved to ${event.outputPath}`;
       case 'error':
@@ -376,19 +421,78 @@ function App(): JSX.Element {
     }
   }
 
+  function markFontFieldSet(name: string): void {
+    if (isFontField(name)) {
+      setFontFieldsSet(prev => prev.includes(name) ? prev : [...prev, name]);
+    }
+  }
+
   function handleInputChange(event: React.ChangeEvent<HTMLInputElement>): void {
     const { name, value } = event.target;
     setSettings(prev => ({ ...prev, [name]: value }));
+    markFontFieldSet(name);
   }
 
   function handleCheckboxChange(event: React.ChangeEvent<HTMLInputElement>): void {
     const { name, checked } = event.target;
     setSettings(prev => ({ ...prev, [name]: checked }));
+    markFontFieldSet(name);
+  }
+
+  function handleFormatChange(event: React.ChangeEvent<HTMLSelectElement>): void {
+    setExportFormat(event.target.value);
+    setFormatOptions({});
+  }
+
+  function handleFormatOptionChange(field: ExportOptionField, value: string | boolean): void {
+    setFormatOptions(prev => {
+      const next = { ...prev };
+      if (field.type === 'number') {
+        const parsed = parseFloat(String(value));
+        if (Number.isNaN(parsed)) {
+          delete next[field.key];
+        } else {
+          next[field.key] = parsed;
+        }
+      } else {
+        next[field.key] = value;
+      }
+      return next;
+    });
+  }
+
+  // Picking a theme loads its fonts into the form and clears the font settings,
+  // so the theme's fonts apply at export until one is changed again
+  function handleThemeSelect(event: React.ChangeEvent<HTMLSelectElement>): void {
+    const theme = themes.find(t => t.id === event.target.value);
+    setFontFieldsSet([]);
+    setSettings(prev => {
+      const next = {
+        ...prev,
+        pptxTheme: event.target.value,
+        textColor: DEFAULT_COLOR,
+        fontFace: DEFAULT_FONT,
+        fontSize: String(DEFAULT_FONT_SIZE),
+        titleFontSize: String(DEFAULT_TITLE_SIZE),
+        bold: true,
+        italic: true,
+      };
+      if (!theme) return next;
+      const { fonts } = theme;
+      if (fonts.fontFace) next.fontFace = fonts.fontFace;
+      if (fonts.textColor) next.textColor = colorWithHash(fonts.textColor);
+      if (typeof fonts.fontSize === 'number') next.fontSize = String(fonts.fontSize);
+      if (typeof fonts.titleFontSize === 'number') next.titleFontSize = String(fonts.titleFontSize);
+      if (typeof fonts.bold === 'boolean') next.bold = fonts.bold;
+      if (typeof fonts.italic === 'boolean') next.italic = fonts.italic;
+      return next;
+    });
   }
 
   function handleFontSelect(event: React.ChangeEvent<HTMLSelectElement>): void {
     const fontName = event.target.value;
     setSettings(prev => ({ ...prev, fontFace: fontName }));
+    markFontFieldSet('fontFace');
   }
 
   // Group fonts by category for the dropdown
@@ -503,20 +607,19 @@ function App(): JSX.Element {
     }
   }
 
-  function buildStyleOverrides() {
-    const overrides: Record<string, unknown> = {};
-    if (settings.textColor.trim()) overrides.textColor = stripHash(settings.textColor.trim());
-    if (settings.fontFace.trim()) overrides.fontFace = settings.fontFace.trim();
-
+  // Font fields the user has not set are sent as null so the theme's fonts apply
+  function buildStyleOverrides(): StyleOverrides {
+    const isSet = (field: FontField) => fontFieldsSet.includes(field);
     const lyricSize = parseInt(settings.fontSize, 10);
-    if (!Number.isNaN(lyricSize)) overrides.fontSize = lyricSize;
-
     const titleSize = parseInt(settings.titleFontSize, 10);
-    if (!Number.isNaN(titleSize)) overrides.titleFontSize = titleSize;
-
-    overrides.bold = settings.bold;
-    overrides.italic = settings.italic;
-    return overrides;
+    return {
+      textColor: isSet('textColor') && settings.textColor.trim() ? stripHash(settings.textColor.trim()) : null,
+      fontFace: isSet('fontFace') && settings.fontFace.trim() ? settings.fontFace.trim() : null,
+      fontSize: isSet('fontSize') && !Number.isNaN(lyricSize) ? lyricSize : null,
+      titleFontSize: isSet('titleFontSize') && !Number.isNaN(titleSize) ? titleSize : null,
+      bold: isSet('bold') ? settings.bold : null,
+      italic: isSet('italic') ? settings.italic : null,
+    };
   }
 
   async function handleExport(): Promise<void> {
@@ -543,6 +646,8 @@ function App(): JSX.Element {
         includeSongTitles: settings.includeSongTitles,
         styleOverrides: buildStyleOverrides(),
         logoPath: settings.logoPath || null,
+        format: exportFormat,
+        formatOptions,
       });
 
       if (response?.canceled) {
@@ -567,18 +672,16 @@ function App(): JSX.Element {
       port,
       libraryFilter: libraryFilter || null,
       includeSongTitles: settings.includeSongTitles,
-      textColor: stripHash(settings.textColor.trim()),
-      fontFace: settings.fontFace.trim() || DEFAULT_FONT,
-      fontSize: parseInt(settings.fontSize, 10) || DEFAULT_FONT_SIZE,
-      titleFontSize: parseInt(settings.titleFontSize, 10) || DEFAULT_TITLE_SIZE,
-      bold: settings.bold,
-      italic: settings.italic,
+      ...buildStyleOverrides(),
       logoPath: settings.logoPath || null,
+      pptxTemplatePath: settings.pptxTemplatePath || null,
+      pptxTheme: settings.pptxTheme || null,
       enableServiceGenerator: settings.enableServiceGenerator,
       worshipLibraryId: settings.worshipLibraryId || null,
       kidsLibraryId: settings.kidsLibraryId || null,
       serviceContentLibraryId: settings.serviceContentLibraryId || null,
       templatePlaylistId: settings.templatePlaylistId || null,
+      serviceGrammar: settings.serviceGrammar || null,
       enableBirthdayBucket: settings.enableBirthdayBucket,
       churchSuiteClientId: settings.churchSuiteClientId || null,
       churchSuiteClientSecret: settings.churchSuiteClientSecret || null,
@@ -590,6 +693,29 @@ function App(): JSX.Element {
     setShowSettings(false);
   }
 
+  async function handleSaveRules(): Promise<void> {
+    let parsed: ClassificationRuleSet;
+    try {
+      parsed = JSON.parse(rulesText);
+    } catch (error: any) {
+      setRulesMessage({ tone: 'error', text: `Not valid JSON: ${error?.message || error}` });
+      return;
+    }
+    try {
+      const saved = await window.api.saveClassificationRules(parsed);
+      setRulesText(JSON.stringify(saved, null, 2));
+      setRulesMessage({ tone: 'success', text: 'Rules saved' });
+    } catch (error: any) {
+      setRulesMessage({ tone: 'error', text: error?.message || 'Failed to save rules' });
+    }
+  }
+
+  async function handleResetRules(): Promise<void> {
+    const defaults = await window.api.resetClassificationRules();
+    setRulesText(JSON.stringify(defaults, null, 2));
+    setRulesMessage({ tone: 'success', text: 'Built-in rules restored' });
+  }
+
   async function handleChooseLogo(): Promise<void> {
     const result = await window.api.chooseLogo();
     if (result?.canceled || !result.filePath) return;
@@ -597,6 +723,17 @@ function App(): JSX.Element {
     setSettings(prev => ({ ...prev, logoPath: filePath }));
   }
 
+  async function handleChoosePptxTemplate(): Promise<void> {
+    try {
+      const result = await window.api.choosePptxTemplate();
+      if (result?.canceled || !result.filePath) return;
+      const filePath = result.filePath;
+      setSettings(prev => ({ ...prev, pptxTemplatePath: filePath }));
+    } catch (error: any) {
+      setErrorMessage(error?.message || 'Could not use that template');
+    }
+  }
+
   async function handleCreatePlaylistFromTemplate(playlistName: string): Promise<{ success: boolean; error?: string; playlistId?: string }> {
     if (!settings.templatePlaylistId) {
       const errorMsg = 'No template playlist selected';
@@ -633,6 +770,53 @@ function App(): JSX.Element {
     }
   }
 
+  const selectedFormat = exportFormats.find(format => format.id === exportFormat);
+  // Settings-backed options are edited in the Formatting dialog instead
+  const exportOptionFields = (selectedFormat?.options ?? []).filter(field => !field.fromSettings);
+
+  const renderFormatOption = (field: ExportOptionField): JSX.Element => {
+    const current = formatOptions[field.key] ?? field.default;
+    if (field.type === 'boolean') {
+      return (
+        <label key={field.key} className="checkbox" title={field.description}>
+          <input
+            type="checkbox"
+            checked={Boolean(current)}
+            onChange={(event) => handleFormatOptionChange(field, event.target.checked)}
+          />
+          {field.label}
+        </label>
+      );
+    }
+    if (field.type === 'select') {
+      return (
+        <label key={field.key} title={field.description}>
+          {field.label}
+          <select
+            value={String(current ?? '')}
+            onChange={(event) => handleFormatOptionChange(field, event.target.value)}
+          >
+            {(field.choices ?? []).map(choice => (
+              <option key={choice.value} value={choice.value}>{choice.label}</option>
+            ))}
+          </select>
+        </label>
+      );
+    }
+    return (
+      <label key={field.key} title={field.description}>
+        {field.label}
+        <input
+          type={field.type === 'number' ? 'number' : field.type === 'color' ? 'color' : 'text'}
+          min={field.min}
+          max={field.max}
+          value={field.type === 'color' ? colorWithHash(String(current ?? '')) : String(current ?? '')}
+          onChange={(event) => handleFormatOptionChange(field, event.target.value)}
+        />
+      </label>
+    );
+  };
+
   const isExportDisabled =
     connectionState !== 'connected' || !selectedPlaylist || exportState === 'running';
 
@@ -688,6 +872,7 @@ function App(): JSX.Element {
           kidsLibraryId: settings.kidsLibraryId,
           serviceContentLibraryId: settings.serviceContentLibraryId,
           templatePlaylistId: settings.templatePlaylistId,
+          serviceGrammar: settings.serviceGrammar,
         }}
         connectionConfig={{ host, port }}
         libraryOptions={libraryOptions}
@@ -851,7 +1036,7 @@ function App(): JSX.Element {
         <div className="panel">
           <div className="panel-heading">
             <h2>Export</h2>
-            <span className="hint">Single-playlist PPTX with styling + logo</span>
+            <span className="hint">{selectedFormat?.description ?? 'Single-playlist PPTX with styling + logo'}</span>
           </div>
           <div className="summary-card">
             <p className="label">Selected playlist</p>
@@ -865,8 +1050,21 @@ function App(): JSX.Element {
               {settings.libraryFilter.trim() ? settings.libraryFilter : 'All items in playlist'}
             </p>
           </div>
+          {exportFormats.length > 1 && (
+            <div className="form-grid">
+              <label>
+                Format
+                <select value={exportFormat} onChange={handleFormatChange}>
+                  {exportFormats.map(format => (
+                    <option key={format.id} value={format.id}>{format.label}</option>
+                  ))}
+                </select>
+              </label>
+              {exportOptionFields.map(renderFormatOption)}
+            </div>
+          )}
           <button className="primary" disabled={isExportDisabled} onClick={handleExport}>
-            {exportState === 'running' ? 'Exporting…' : 'Export to PowerPoint'}
+            {exportState === 'running' ? 'Exporting…' : `Export to ${selectedFormat?.label ?? 'PowerPoint'}`}
           </button>
           {latestOutput && <p className="output-note">Saved to: {latestOutput}</p>}
           <div className="log-view">
@@ -891,6 +1089,21 @@ function App(): JSX.Element {
                   ×
                 </button>
               </div>
+              <label>
+                Theme
+                <select name="pptxTheme" value={settings.pptxTheme} onChange={handleThemeSelect}>
+                  <option value="">Classic (default)</option>
+                  {themes.filter(theme => theme.id !== 'classic' || !theme.builtIn).map(theme => (
+                    <option key={theme.id} value={theme.id}>
+                      {theme.name}{theme.builtIn ? '' : ' (custom)'}
+                    </option>
+                  ))}
+                </select>
+                <span className="hint">
+                  {themes.find(theme => theme.id === settings.pptxTheme)?.description
+                    ?? 'Background, layout and logo position for PPTX slides'}
+                </span>
+              </label>
               <label className="checkbox">
                 <input
                   type="checkbox"
@@ -1014,6 +1227,51 @@ function App(): JSX.Element {
                   </button>
                 </div>
               </div>
+              <div className="logo-row">
+                <div>
+                  <p className="label">Master template (.pptx)</p>
+                  <p className="value">
+                    {settings.pptxTemplatePath ? settings.pptxTemplatePath : 'None (using the theme)'}
+                  </p>
+                </div>
+                <div className="logo-actions">
+                  <button className="ghost" onClick={handleChoosePptxTemplate} type="button">
+                    Choose
+                  </button>
+                  <button
+                    className="ghost"
+                    type="button"
+                    onClick={() => setSettings(prev => ({ ...prev, pptxTemplatePath: '' }))}
+                  >
+                    Clear
+                  </button>
+                </div>
+              </div>
+
+              <div className="settings-section">
+                <h3>Lyric Detection</h3>
+                <span className="hint">
+                  Rules deciding which slides count as lyrics. Highest priority first; the first match wins.
+                  Match on group, text, label (regexes), slideEnabled, minLength or maxLength.
+                  Disabled slides and labels in skipLabels are left out before any rule runs.
+                </span>
+                <textarea
+                  className="rules-editor"
+                  value={rulesText}
+                  onChange={event => setRulesText(event.target.value)}
+                  rows={12}
+                  spellCheck={false}
+                />
+                {rulesMessage && <p className={`hint tone-${rulesMessage.tone}`}>{rulesMessage.text}</p>}
+                <div className="logo-actions">
+                  <button className="ghost" type="button" onClick={handleSaveRules}>
+                    Save rules
+                  </button>
+                  <button className="ghost" type="button" onClick={handleResetRules}>
+                    Reset to built-in
+                  </button>
+                </div>
+              </div>
 
               <div className="settings-section">
                 <h3>Advanced Features</h3>
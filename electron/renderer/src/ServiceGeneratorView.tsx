@@ -1,5 +1,5 @@
 /// <reference path="./env.d.ts" />
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 
 type ServiceGeneratorViewProps = {
   settings: {
@@ -7,6 +7,7 @@ type ServiceGeneratorViewProps = {
     kidsLibraryId: string;
     serviceContentLibraryId: string;
     templatePlaylistId: string;
+    serviceGrammar: string;
   };
   connectionConfig: { host: string; port: number };
   libraryOptions: Array<{ uuid: string; name: string }>;
@@ -72,6 +73,7 @@ type MatchResult = {
   bestMatch?: { uuid: string; name: string; library: string; confidence: number };
   requiresReview: boolean;
   selectedMatch?: { uuid: string; name: string };
+  manualSelection?: string;  // UUID if the user picked the song by hand
 };
 
 type VerseResult = {
@@ -111,6 +113,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
   const [planNotes, setPlanNotes] = useState('');
   const [editingPlanId, setEditingPlanId] = useState<string | null>(null);
   const [savedPlans, setSavedPlans] = useState<any[]>([]);
+  const [grammars, setGrammars] = useState<ServiceGrammarSummary[]>([]);
   const [planSearchSlot, setPlanSearchSlot] = useState<PraiseSlotType | null>(null);
   const [planSearchQuery, setPlanSearchQuery] = useState('');
   const [planSearchResults, setPlanSearchResults] = useState<Array<{ uuid: string; name: string; library: string }>>([]);
@@ -126,11 +129,19 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
   const [isProcessing, setIsProcessing] = useState(false);
   const [specialServiceType, setSpecialServiceType] = useState<string | null>(null);  // Track special service type
   const [versesSkipped, setVersesSkipped] = useState(false);  // Track if user chose to skip verse step
+  const [parsedService, setParsedService] = useState<ParsedServiceOrder | null>(null);
+  const [buildState, setBuildState] = useState<ServiceSession['build']>({ status: 'pending' });
+
+  // Saved sessions, so a PDF workflow can be resumed after the app or tab closes
+  const [savedSessions, setSavedSessions] = useState<ServiceSession[]>([]);
+  const sessionIdRef = useRef<string | null>(null);
+  const sessionSaveRef = useRef<Promise<void>>(Promise.resolve());
+  const sessionTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
 
   // Library search state for manual song override
   const [librarySearchIndex, setLibrarySearchIndex] = useState<number | null>(null);
   const [librarySearchQuery, setLibrarySearchQuery] = useState('');
-  const [librarySearchResults, setLibrarySearchResults] = useState<Array<{ uuid: string; name: string; library: string }>>([]);
+  const [librarySearchResults, setLibrarySearchResults] = useState<Array<{ uuid: string; name: string; library: string; section?: string; snippet?: string }>>([]);
   const [librarySearchLoading, setLibrarySearchLoading] = useState(false);
 
   // Bible verse library search state
@@ -145,11 +156,148 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
   // Get the active steps list based on workflow mode
   const STEPS = workflowMode === 'plan' ? PLAN_STEPS : PDF_STEPS;
 
+  // Scope manual song search to the relevant library
+  const songLibraryIds = (isKidsVideo?: boolean): string[] => (isKidsVideo
+    ? [props.settings.kidsLibraryId]
+    : [props.settings.worshipLibraryId]
+  ).filter(Boolean) as string[];
+
+  // Manual song search: title matches first, then songs whose lyrics contain the words
+  const searchSongLibrary = async (isKidsVideo: boolean | undefined, query: string) => {
+    const searchLibraryIds = songLibraryIds(isKidsVideo);
+    setLibrarySearchLoading(true);
+    try {
+      const [byName, byLyrics] = await Promise.all([
+        window.api.searchPresentations(props.connectionConfig, searchLibraryIds, query),
+        window.api.searchLyrics(props.connectionConfig, searchLibraryIds, query),
+      ]);
+      const results: typeof librarySearchResults = byName.success ? [...byName.results] : [];
+      if (byLyrics.success) {
+        for (const hit of byLyrics.results) {
+          const existing = results.find(r => r.uuid === hit.uuid);
+          if (existing) {
+            Object.assign(existing, { section: hit.section, snippet: hit.snippet });
+          } else {
+            results.push(hit);
+          }
+        }
+      }
+      setLibrarySearchResults(results);
+    } catch (err: any) {
+      setNotification({ message: `Search failed: ${err.message}`, type: 'error' });
+    }
+    setLibrarySearchLoading(false);
+  };
+
   // Load saved plans on mount
   useEffect(() => {
     window.api.listPlannedServices().then(plans => setSavedPlans(plans)).catch(() => {});
+    window.api.listServiceGrammars().then(list => setGrammars(list)).catch(() => {});
+    window.api.listServiceSessions()
+      .then(sessions => setSavedSessions(sessions.filter(s => s.build.status !== 'built')))
+      .catch(() => {});
   }, []);
 
+  // Autosave the PDF workflow after each change; saves run one at a time so the
+  // first one's session id is used by the rest. A discarded session's id is
+  // forgotten, so the next change starts a new session; built services are done
+  // and not saved.
+  useEffect(() => {
+    if (workflowMode !== 'pdf' || currentStep === 'setup' || currentStep === 'plan') return;
+    if (!selectedPlaylistId || parsedItems.length === 0 || buildState.status === 'built') return;
+
+    const session = {
+      name: selectedPlaylistName || pdfName,
+      playlistId: selectedPlaylistId,
+      sourceFile: pdfPath,
+      grammarId: props.settings.serviceGrammar || undefined,
+      step: currentStep,
+      parsed: parsedService || undefined,
+      items: parsedItems,
+      songMatches: matchResults,
+      verseMatches: bibleMatches,
+      verses: verseResults,
+      versesSkipped,
+      build: buildState,
+    };
+    const timer = setTimeout(() => {
+      sessionTimerRef.current = null;
+      sessionSaveRef.current = sessionSaveRef.current
+        .then(async () => {
+          const saved = await window.api.saveServiceSession({ ...session, id: sessionIdRef.current || undefined });
+          sessionIdRef.current = saved.id;
+          setSavedSessions(prev => [saved, ...prev.filter(s => s.id !== saved.id)]);
+        })
+        .catch((error: any) => {
+          setNotification({ message: `Could not save progress: ${error?.message || 'unknown error'}`, type: 'error' });
+        });
+    }, 500);
+    sessionTimerRef.current = timer;
+    return () => clearTimeout(timer);
+  }, [workflowMode, currentStep, selectedPlaylistId, selectedPlaylistName, pdfPath, pdfName, parsedService, parsedItems, matchResults, bibleMatches, verseResults, versesSkipped, buildState]);
+
+  const cancelPendingSave = () => {
+    if (sessionTimerRef.current) {
+      clearTimeout(sessionTimerRef.current);
+      sessionTimerRef.current = null;
+    }
+  };
+
+  // A built service is finished, so drop its session once any save in flight is done
+  const finishSession = () => {
+    cancelPendingSave();
+    sessionSaveRef.current = sessionSaveRef.current
+      .then(async () => {
+        const id = sessionIdRef.current;
+        if (!id) return;
+        sessionIdRef.current = null;
+        await window.api.deleteServiceSession(id);
+        setSavedSessions(prev => prev.filter(s => s.id !== id));
+      })
+      .catch(() => {});
+  };
+
+  // Restore a saved session and continue from the step it was left on
+  const resumeSession = (session: ServiceSession) => {
+    sessionIdRef.current = session.id;
+    setSelectedPlaylistId(session.playlistId);
+    setSelectedPlaylistName(session.name);
+    setPdfPath(session.sourceFile || '');
+    setPdfName(session.sourceFile?.split('/').pop() || '');
+    setParsedService(session.parsed || null);
+    setParsedItems(session.items as ParsedItem[]);
+    setSpecialServiceType(session.parsed?.specialServiceType || null);
+    setMatchResults(session.songMatches as MatchResult[]);
+    setBibleMatches(session.verseMatches);
+    setVerseResults(session.verses);
+    setVersesSkipped(Boolean(session.versesSkipped));
+    setBuildState(session.build);
+    setWorkflowMode('pdf');
+    setCurrentStep(session.step);
+    setNotification({ message: `Resumed "${session.name}"`, type: 'success' });
+  };
+
+  // Discarding the session being worked on forgets its id once any save in flight is done
+  const discardSession = (session: ServiceSession) => {
+    if (sessionIdRef.current === session.id) {
+      cancelPendingSave();
+    }
+    sessionSaveRef.current = sessionSaveRef.current
+      .then(async () => {
+        if (sessionIdRef.current === session.id) {
+          sessionIdRef.current = null;
+        }
+        const result = await window.api.deleteServiceSession(session.id);
+        if (result.deleted) {
+          setSavedSessions(prev => prev.filter(s => s.id !== session.id));
+          setNotification({ message: `Discarded "${session.name}"`, type: 'success' });
+        }
+      })
+      .catch((error: any) => {
+        setNotification({ message: `Could not discard "${session.name}": ${error?.message || 'unknown error'}`, type: 'error' });
+      });
+  };
+
   // Step validation - check if step is complete
   const isStepComplete = (step: Step): boolean => {
     switch (step) {
@@ -328,6 +476,22 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                   <span className="hint">No templates found. Create a folder named "TEMPLATE" and add playlists inside it.</span>
                 )}
               </label>
+
+              <label>
+                Service Order Format
+                <select
+                  name="serviceGrammar"
+                  value={props.settings.serviceGrammar}
+                  onChange={(e) => props.onSettingsChange({ serviceGrammar: e.target.value })}
+                >
+                  {grammars.map(grammar => (
+                    <option key={grammar.id} value={grammar.builtIn ? '' : grammar.id}>
+                      {grammar.name}{grammar.builtIn ? ' (built-in)' : ''}
+                    </option>
+                  ))}
+                </select>
+                <span className="hint">How the PDF is read. Add formats as JSON files; see the Service Generator guide.</span>
+              </label>
             </div>
 
             {/* Selected Working Playlist */}
@@ -375,6 +539,58 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
               </div>
             )}
 
+            {/* Unfinished Services */}
+            {savedSessions.length > 0 && (
+              <div style={{ marginTop: '24px' }}>
+                <h3 style={{ fontSize: '16px', marginBottom: '12px' }}>Unfinished Services</h3>
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '8px' }}>
+                  {savedSessions.map(session => {
+                    const stepLabel = PDF_STEPS.find(s => s.id === session.step)?.label || session.step;
+                    const songCount = session.songMatches.filter(m => m.selectedMatch).length;
+                    return (
+                      <div
+                        key={session.id}
+                        style={{
+                          padding: '12px 16px',
+                          background: 'rgba(255,255,255,0.03)',
+                          borderRadius: '10px',
+                          border: '1px solid var(--panel-border)',
+                          display: 'flex',
+                          alignItems: 'center',
+                          gap: '12px',
+                        }}
+                      >
+                        <div style={{ flex: 1 }}>
+                          <div style={{ fontWeight: 600, marginBottom: '4px' }}>{session.name}</div>
+                          <div style={{ fontSize: '13px', color: 'var(--muted)' }}>
+                            {session.sourceFile?.split('/').pop() || 'No service order'}
+                            {' '}&middot; {stepLabel}
+                            {songCount > 0 && <> &middot; {songCount} song{songCount !== 1 ? 's' : ''} matched</>}
+                            {' '}&middot; {new Date(session.updatedAt).toLocaleString('en-GB', { day: 'numeric', month: 'short', hour: '2-digit', minute: '2-digit' })}
+                          </div>
+                        </div>
+                        <button
+                          className="primary small"
+                          onClick={() => resumeSession(session)}
+                          type="button"
+                        >
+                          Resume
+                        </button>
+                        <button
+                          className="ghost small"
+                          onClick={() => discardSession(session)}
+                          type="button"
+                          style={{ color: '#f44336' }}
+                        >
+                          Discard
+                        </button>
+                      </div>
+                    );
+                  })}
+                </div>
+              </div>
+            )}
+
             {/* Saved Plans */}
             {savedPlans.length > 0 && (
               <div style={{ marginTop: '24px' }}>
@@ -532,7 +748,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
         return (
           <div className="service-step-content">
             <h2>Upload PDF</h2>
-            <p className="hint">Upload your service order PDF for parsing</p>
+            <p className="hint">Upload your service order for parsing: a PDF, Word document (.docx), text or Markdown file</p>
             {!selectedPlaylistName ? (
               <div style={{ padding: '40px 20px', textAlign: 'center' }}>
                 <div style={{ fontSize: '48px', marginBottom: '16px', opacity: 0.5 }}>📋</div>
@@ -551,9 +767,9 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                 {!pdfPath ? (
                   <div style={{ padding: '40px 20px', textAlign: 'center' }}>
                     <div style={{ fontSize: '48px', marginBottom: '16px' }}>📄</div>
-                    <h3 style={{ margin: '0 0 12px' }}>Select Service Order PDF</h3>
+                    <h3 style={{ margin: '0 0 12px' }}>Select Service Order</h3>
                     <p style={{ margin: '0 0 24px', color: 'var(--muted)', fontSize: '14px' }}>
-                      Choose your PDF file to extract songs and verses
+                      Choose the PDF, Word document or pasted text to extract songs and verses
                     </p>
                     <button
                       className="primary"
@@ -564,15 +780,19 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                           if (!result.canceled && result.filePath) {
                             setPdfPath(result.filePath);
                             setPdfName(result.filePath.split('/').pop() || 'service-order.pdf');
-                            setNotification({ message: 'PDF selected, parsing...', type: 'info' });
+                            setNotification({ message: 'Service order selected, parsing...', type: 'info' });
 
-                            // Auto-parse the PDF
-                            const parseResult = await window.api.parsePDF(result.filePath);
+                            // Auto-parse the service order
+                            const parseResult = await window.api.parsePDF(result.filePath, props.settings.serviceGrammar || undefined);
                             if (parseResult.success && parseResult.items) {
+                              // A new service order starts a new session
+                              sessionIdRef.current = null;
                               setParsedItems(parseResult.items);
+                              setParsedService(parseResult.service || null);
                               setSpecialServiceType(parseResult.specialServiceType || null);
+                              setBuildState({ status: 'pending' });
 
-                              let notificationMessage = `Found ${parseResult.items.length} items in PDF`;
+                              let notificationMessage = `Found ${parseResult.items.length} items in ${result.filePath.split('/').pop()}`;
                               if (parseResult.specialServiceType) {
                                 notificationMessage += ` (${parseResult.specialServiceType} service)`;
                               }
@@ -583,14 +803,14 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                               setCurrentStep('parse');
                             } else {
                               setNotification({
-                                message: parseResult.error || 'Failed to parse PDF',
+                                message: parseResult.error || 'Failed to parse service order',
                                 type: 'error'
                               });
                             }
                           }
                         } catch (error: any) {
                           setNotification({
-                            message: error?.message || 'Error selecting PDF',
+                            message: error?.message || 'Error selecting service order',
                             type: 'error'
                           });
                         } finally {
@@ -600,7 +820,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                       disabled={isProcessing}
                       type="button"
                     >
-                      {isProcessing ? 'Processing...' : 'Select PDF File'}
+                      {isProcessing ? 'Processing...' : 'Select File'}
                     </button>
                   </div>
                 ) : (
@@ -614,9 +834,11 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                       <button
                         className="ghost small"
                         onClick={() => {
+                          sessionIdRef.current = null;
                           setPdfPath('');
                           setPdfName('');
                           setParsedItems([]);
+                          setParsedService(null);
                           setMatchResults([]);
                           setVerseResults([]);
                           setSpecialServiceType(null);
@@ -654,7 +876,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
 
             {parsedItems.length === 0 ? (
               <div className="empty-state" style={{ padding: '60px 20px' }}>
-                No items parsed yet. Go back to Upload to select a PDF.
+                No items parsed yet. Go back to Upload to select a service order.
               </div>
             ) : (
               <div>
@@ -1018,7 +1240,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                             const match = result.matches.find(m => m.uuid === uuid);
                             setMatchResults(prev => prev.map((r, i) =>
                               i === index
-                                ? { ...r, selectedMatch: match ? { uuid: match.uuid, name: match.name } : undefined }
+                                ? { ...r, selectedMatch: match ? { uuid: match.uuid, name: match.name } : undefined, manualSelection: match?.uuid }
                                 : r
                             ));
                           }}
@@ -1090,34 +1312,17 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                         {librarySearchIndex === index && (
                           <div style={{ marginTop: '10px', padding: '12px', background: 'rgba(255,255,255,0.03)', borderRadius: '8px', border: '1px solid var(--panel-border)' }}>
                             <div style={{ fontSize: '12px', color: 'var(--muted)', marginBottom: '8px' }}>
-                              Search {result.isKidsVideo ? 'Kids' : 'Worship'} library:
+                              Search {result.isKidsVideo ? 'Kids' : 'Worship'} library by title or a line of the lyrics:
                             </div>
                             <div style={{ display: 'flex', gap: '8px' }}>
                               <input
                                 type="text"
-                                placeholder="Type to search..."
+                                placeholder="Song title or lyric line..."
                                 value={librarySearchQuery}
                                 onChange={(e) => setLibrarySearchQuery(e.target.value)}
                                 onKeyDown={async (e) => {
                                   if (e.key === 'Enter' && librarySearchQuery.trim()) {
-                                    setLibrarySearchLoading(true);
-                                    try {
-                                      // Scope search to the relevant library
-                                      const searchLibraryIds = result.isKidsVideo
-                                        ? [props.settings.kidsLibraryId].filter(Boolean) as string[]
-                                        : [props.settings.worshipLibraryId].filter(Boolean) as string[];
-                                      const searchResult = await window.api.searchPresentations(
-                                        props.connectionConfig,
-                                        searchLibraryIds,
-                                        librarySearchQuery.trim()
-                                      );
-                                      if (searchResult.success) {
-                                        setLibrarySearchResults(searchResult.results);
-                                      }
-                                    } catch (err: any) {
-                                      setNotification({ message: `Search failed: ${err.message}`, type: 'error' });
-                                    }
-                                    setLibrarySearchLoading(false);
+                                    await searchSongLibrary(result.isKidsVideo, librarySearchQuery.trim());
                                   }
                                 }}
                                 style={{
@@ -1136,28 +1341,28 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                                 disabled={!librarySearchQuery.trim() || librarySearchLoading}
                                 onClick={async () => {
                                   if (!librarySearchQuery.trim()) return;
+                                  await searchSongLibrary(result.isKidsVideo, librarySearchQuery.trim());
+                                }}
+                                style={{ fontSize: '12px', padding: '8px 14px' }}
+                              >
+                                {librarySearchLoading ? '...' : 'Search'}
+                              </button>
+                              <button
+                                className="ghost small"
+                                type="button"
+                                title="Re-read the library's lyrics so new and edited songs can be found"
+                                disabled={librarySearchLoading}
+                                onClick={async () => {
                                   setLibrarySearchLoading(true);
-                                  try {
-                                    // Scope search to the relevant library
-                                    const searchLibraryIds = result.isKidsVideo
-                                      ? [props.settings.kidsLibraryId].filter(Boolean) as string[]
-                                      : [props.settings.worshipLibraryId].filter(Boolean) as string[];
-                                    const searchResult = await window.api.searchPresentations(
-                                      props.connectionConfig,
-                                      searchLibraryIds,
-                                      librarySearchQuery.trim()
-                                    );
-                                    if (searchResult.success) {
-                                      setLibrarySearchResults(searchResult.results);
-                                    }
-                                  } catch (err: any) {
-                                    setNotification({ message: `Search failed: ${err.message}`, type: 'error' });
-                                  }
+                                  const indexed = await window.api.indexLyrics(props.connectionConfig, songLibraryIds(result.isKidsVideo));
                                   setLibrarySearchLoading(false);
+                                  setNotification(indexed.success
+                                    ? { message: `Lyrics of ${indexed.songs} songs indexed`, type: 'success' }
+                                    : { message: `Indexing failed: ${indexed.error}`, type: 'error' });
                                 }}
                                 style={{ fontSize: '12px', padding: '8px 14px' }}
                               >
-                                {librarySearchLoading ? '...' : 'Search'}
+                                ↻ Lyrics
                               </button>
                             </div>
                             {/* Search results */}
@@ -1173,6 +1378,7 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                                           ? {
                                               ...r,
                                               selectedMatch: { uuid: pres.uuid, name: pres.name },
+                                              manualSelection: pres.uuid,
                                               // Add to matches list so it shows in dropdown too
                                               matches: r.matches.some(m => m.uuid === pres.uuid)
                                                 ? r.matches
@@ -1199,7 +1405,14 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                                     onMouseEnter={(e) => (e.currentTarget.style.background = 'rgba(255,255,255,0.06)')}
                                     onMouseLeave={(e) => (e.currentTarget.style.background = 'transparent')}
                                   >
-                                    <span>{pres.name}</span>
+                                    <span>
+                                      {pres.name}
+                                      {pres.snippet && (
+                                        <span style={{ display: 'block', fontSize: '11px', color: 'var(--muted)' }}>
+                                          {pres.section}: “{pres.snippet}”
+                                        </span>
+                                      )}
+                                    </span>
                                     <span style={{ fontSize: '11px', color: 'var(--muted)' }}>{pres.library}</span>
                                   </div>
                                 ))}
@@ -2649,17 +2862,21 @@ export function ServiceGeneratorView(props: ServiceGeneratorViewProps) {
                         );
 
                         if (result.success) {
+                          setBuildState({ status: 'built', itemCount: items.length, builtAt: new Date().toISOString() });
+                          finishSession();
                           setNotification({
                             message: `Added ${items.length} items to playlist!`,
                             type: 'success'
                           });
                         } else {
+                          setBuildState({ status: 'failed', error: result.error });
                           setNotification({
                             message: result.error || 'Failed to build playlist',
                             type: 'error'
                           });
                         }
                       } catch (error: any) {
+                        setBuildState({ status: 'failed', error: error?.message });
                         setNotification({
                           message: error?.message || 'Error building playlist',
                           type: 'error'
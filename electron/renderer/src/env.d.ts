@@ -20,13 +20,16 @@ type SettingsPayload = {
   port?: number;
   libraryFilter?: string | null;
   includeSongTitles?: boolean;
-  textColor?: string;
-  fontFace?: string;
-  fontSize?: number;
-  titleFontSize?: number;
-  bold?: boolean;
-  italic?: boolean;
+  // null clears a font setting so the theme's value applies
+  textColor?: string | null;
+  fontFace?: string | null;
+  fontSize?: number | null;
+  titleFontSize?: number | null;
+  bold?: boolean | null;
+  italic?: boolean | null;
   logoPath?: string | null;
+  pptxTemplatePath?: string | null;
+  pptxTheme?: string | null;
   lastPlaylistId?: string;
   // Service Generator
   enableServiceGenerator?: boolean;
@@ -34,6 +37,7 @@ type SettingsPayload = {
   kidsLibraryId?: string | null;
   serviceContentLibraryId?: string | null;
   templatePlaylistId?: string | null;
+  serviceGrammar?: string | null;
   // Birthday Bucket
   enableBirthdayBucket?: boolean;
   churchSuiteClientId?: string | null;
@@ -42,20 +46,25 @@ type SettingsPayload = {
   birthdayBackgroundImagePath?: string | null;
 };
 
+/** Font settings for an export; null means not set, so the theme's value applies */
+type StyleOverrides = {
+  textColor?: string | null;
+  fontFace?: string | null;
+  fontSize?: number | null;
+  titleFontSize?: number | null;
+  bold?: boolean | null;
+  italic?: boolean | null;
+};
+
 interface ExportPayload extends ConnectionConfig {
   playlistId: string;
   playlistName: string;
   libraryFilter?: string | null;
   includeSongTitles?: boolean;
-  styleOverrides?: {
-    textColor?: string;
-    fontFace?: string;
-    fontSize?: number;
-    titleFontSize?: number;
-    bold?: boolean;
-    italic?: boolean;
-  };
+  styleOverrides?: StyleOverrides;
   logoPath?: string | null;
+  format?: string;
+  formatOptions?: Record<string, string | number | boolean>;
 }
 
 type ProgressEventPayload = {
@@ -65,6 +74,71 @@ type ProgressEventPayload = {
   itemName?: string;
   totalSongs?: number;
   outputPath?: string;
+  format?: string;
+};
+
+type ExportOptionField = {
+  key: string;
+  label: string;
+  type: 'boolean' | 'string' | 'number' | 'color' | 'select';
+  description?: string;
+  default?: string | number | boolean;
+  choices?: Array<{ value: string; label: string }>;
+  min?: number;
+  max?: number;
+  fromSettings?: boolean;
+};
+
+type ClassificationRuleSet = {
+  defaultAction: 'include' | 'exclude';
+  rules: Array<{
+    id: string;
+    description?: string;
+    action: 'include' | 'exclude';
+    priority: number;
+    disabled?: boolean;
+    match: Record<string, string | number | boolean>;
+  }>;
+};
+
+type ServiceGrammarSummary = {
+  id: string;
+  name: string;
+  description?: string;
+  builtIn: boolean;
+};
+
+type PptxThemeSummary = {
+  id: string;
+  name: string;
+  description?: string;
+  builtIn: boolean;
+  fonts: {
+    fontFace?: string;
+    fontSize?: number;
+    titleFontSize?: number;
+    textColor?: string;
+    bold?: boolean;
+    italic?: boolean;
+  };
+};
+
+type ExportFormatDescriptor = {
+  id: string;
+  label: string;
+  description: string;
+  extension: string;
+  mimeType: string;
+  textual: boolean;
+  options: ExportOptionField[];
+};
+
+type LyricSearchResult = {
+  uuid: string;
+  name: string;
+  library: string;
+  section: string;
+  snippet: string;
 };
 
 type FontStatus = {
@@ -78,9 +152,18 @@ type ParsePDFResult = {
   success: boolean;
   items?: ParsedServiceItem[];
   specialServiceType?: string | null;
+  service?: ParsedServiceOrder;
   error?: string;
 };
 
+type ParsedServiceOrder = {
+  date: string;
+  rawDate?: string;
+  sections: Array<{ type: string; title: string; position: number; praiseSlot?: string; isKidsVideo?: boolean }>;
+  rawText: string;
+  specialServiceType?: string | null;
+};
+
 type ParsedServiceItem = {
   type: 'song' | 'kids_video' | 'verse' | 'heading';
   text: string;
@@ -96,6 +179,31 @@ type SongMatchResult = {
   bestMatch?: { uuid: string; name: string; library: string; confidence: number };
   requiresReview: boolean;
   selectedMatch?: { uuid: string; name: string };
+  manualSelection?: string;
+};
+
+type ServiceSession = {
+  id: string;
+  name: string;
+  playlistId: string;
+  sourceFile?: string;
+  grammarId?: string;
+  step: 'upload' | 'parse' | 'match' | 'verse' | 'build';
+  parsed?: ParsedServiceOrder;
+  items: ParsedServiceItem[];
+  songMatches: SongMatchResult[];
+  verseMatches: Array<{
+    reference: string;
+    matches: Array<{ uuid: string; name: string; confidence: number }>;
+    bestMatch?: { uuid: string; name: string; confidence: number };
+    requiresReview: boolean;
+    selectedMatch?: { uuid: string; name: string };
+  }>;
+  verses: Array<{ reference: string; text: string; error?: string }>;
+  versesSkipped?: boolean;
+  build: { status: 'pending' | 'built' | 'failed'; itemCount?: number; builtAt?: string; error?: string };
+  createdAt: string;
+  updatedAt: string;
 };
 
 interface ElectronAPI {
@@ -105,7 +213,13 @@ interface ElectronAPI {
   fetchPlaylists: (config: ConnectionConfig) => Promise<any[]>;
   fetchLibraries: (config: ConnectionConfig) => Promise<any[]>;
   startExport: (payload: ExportPayload) => Promise<{ success: boolean; outputPath?: string; error?: string; canceled?: boolean }>;
+  listExportFormats: () => Promise<ExportFormatDescriptor[]>;
+  listThemes: () => Promise<PptxThemeSummary[]>;
+  loadClassificationRules: () => Promise<ClassificationRuleSet>;
+  saveClassificationRules: (ruleSet: ClassificationRuleSet) => Promise<ClassificationRuleSet>;
+  resetClassificationRules: () => Promise<ClassificationRuleSet>;
   chooseLogo: () => Promise<{ canceled: boolean; filePath: string | undefined }>;
+  choosePptxTemplate: () => Promise<{ canceled: boolean; filePath: string | undefined }>;
   createPlaylistFromTemplate: (config: ConnectionConfig, templateId: string, playlistName: string) => Promise<{ success: boolean; playlistId?: string; error?: string }>;
   // Shell utilities
   openExternal: (url: string) => Promise<{ success: boolean }>;
@@ -124,9 +238,19 @@ interface ElectronAPI {
     libraryIds: string[],
     query: string
   ) => Promise<{ success: boolean; results: Array<{ uuid: string; name: string; library: string }>; error?: string }>;
+  searchLyrics: (
+    config: ConnectionConfig,
+    libraryIds: string[],
+    query: string
+  ) => Promise<{ success: boolean; results: LyricSearchResult[]; error?: string }>;
+  indexLyrics: (
+    config: ConnectionConfig,
+    libraryIds: string[]
+  ) => Promise<{ success: boolean; songs?: number; error?: string }>;
   // Service Generator
   choosePDF: () => Promise<{ canceled: boolean; filePath?: string }>;
-  parsePDF: (filePath: string) => Promise<ParsePDFResult>;
+  parsePDF: (filePath: string, grammarId?: string) => Promise<ParsePDFResult>;
+  listServiceGrammars: () => Promise<ServiceGrammarSummary[]>;
   matchSongs: (
     songItems: Array<{ text: string; isKidsVideo?: boolean; praiseSlot?: string; specialServiceType?: string | null }>,
     config: ConnectionConfig,
@@ -152,6 +276,10 @@ interface ElectronAPI {
   }>;
   buildServicePlaylist: (config: ConnectionConfig, playlistId: string, items: any[]) => Promise<{ success: boolean; error?: string }>;
   focusPlaylistItem: (config: ConnectionConfig, playlistId: string, headerName: string) => Promise<{ success: boolean; error?: string; index?: number }>;
+  listServiceSessions: () => Promise<ServiceSession[]>;
+  getServiceSession: (id: string) => Promise<ServiceSession | null>;
+  saveServiceSession: (session: Partial<ServiceSession> & { name: string; playlistId: string }) => Promise<ServiceSession>;
+  deleteServiceSession: (id: string) => Promise<{ deleted: boolean }>;
   // Birthday Bucket
   churchSuiteSync: () => Promise<{ success: boolean; contacts: number; children: number; syncedAt: string; error?: string }>;
   churchSuiteGetBirthdays: (weekOffset: number) => Promise<{ success: boolean; entries: any[]; range: { start: string; end: string } }>;
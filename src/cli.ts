@@ -6,12 +6,63 @@
  */
 
 import { ProPresenterClient, PresentationInfo, PlaylistItem } from './propresenter-client';
-import { extractLyrics, formatLyricsAsText, formatLyricsAsJSON, getLyricsSummary } from './lyrics-extractor';
-import { exportToPowerPoint } from './pptx-exporter';
+import { extractLyrics, formatLyricsAsText, formatLyricsAsJSON, getLyricsSummary, ExtractedLyrics } from './lyrics-extractor';
+import { requireExporter, resolveExportOptions, describeExportFormats, describeExportProgress, LyricsExporter } from './exporters';
 import { collectPlaylistLyrics, PlaylistProgressEvent } from './services/playlist-exporter';
+import { loadPresentationsFromPath, isOfflineSource } from './services/pro-file-reader';
 import { findLogoPath } from './services/logo';
 import { flattenPlaylists, formatPlaylistName } from './utils/playlist-utils';
 import { loadAliases, setAlias, removeAlias, getAliasFilePath } from './services/alias-store';
+import {
+  recordExportUsage,
+  buildUsageReport,
+  formatUsageReportCsv,
+  getUsageFilePath,
+} from './services/usage-store';
+import { listThemes, getTheme, copyTheme, getThemesDir } from './services/theme-store';
+import { listTemplateLayouts } from './services/pptx-template';
+import {
+  listServiceGrammars,
+  getServiceGrammar,
+  requireServiceGrammar,
+  copyServiceGrammar,
+  getServiceGrammarsDir,
+  DEFAULT_SERVICE_GRAMMAR,
+} from './services/service-grammar';
+import { PDFParser } from './services/pdf-parser';
+import { readServiceOrderLines } from './services/service-order-reader';
+import {
+  loadServiceLibraries,
+  matchServiceSongs,
+  matchVerseReferences,
+  serviceOrderItems,
+  selectedServiceItems,
+  recordServicePlaylistUsage,
+  insertServiceItems,
+  fetchPlaylistItems,
+  buildServicePlaylist,
+  formatServiceReview,
+  DEFAULT_SONG_THRESHOLD,
+} from './services/service-pipeline';
+import { diffLyrics, formatLyricDiff, loadLyricsFile, isLyricsFile } from './services/lyric-diff';
+import {
+  takeLibrarySnapshot,
+  listSnapshotSongs,
+  findSnapshotSong,
+  loadSnapshotVersion,
+  getSnapshotDir,
+  SnapshotProgressEvent,
+} from './services/snapshot-store';
+import { buildLyricIndex, searchLyrics, listIndexedLibraries, getLyricIndexPath } from './services/lyric-search';
+import { findDuplicates, scanLibraryDuplicates, DuplicateReport } from './services/duplicate-finder';
+import { lintSongs, parseLintOptions, formatLintReport, addLintWords, getLintWordsPath } from './services/lyric-linter';
+import { crawlLibraries, resolveLibraries } from './services/library-crawler';
+import {
+  classifySlide,
+  loadClassificationRules,
+  saveClassificationRules,
+  getClassificationRulesPath,
+} from './services/classification-rules';
 import {
   getAllUsers,
   getAllowedEmails,
@@ -23,7 +74,10 @@ import {
   ensureUsersFile,
   getUsersFilePath,
 } from './server/services/user-store';
+import { loadSettings } from './server/services/settings-store';
 import { checkTunnelReachable, validateTunnelConfig } from './server/middleware/cloudflare';
+import { loadFixtures } from './simulator/fixtures';
+import { startSimulator } from './simulator/server';
 import * as readline from 'readline';
 import * as fs from 'fs';
 import * as path from 'path';
@@ -36,13 +90,45 @@ const DEFAULT_PORT = parseInt(process.env.PROPRESENTER_PORT || '1025', 10);
 // Default library filter for songs
 const DEFAULT_LIBRARY = process.env.PROPRESENTER_LIBRARY || 'Worship';
 
+// Commands that are shorthand for `export --format <id>`
+const FORMAT_COMMANDS = new Map<string, string>([
+  ['pptx', 'pptx'],
+  ['chordpro', 'chordpro'],
+  ['opensong', 'opensong'],
+]);
+
+/**
+ * Settings for service build, given as --template, --threshold, --report,
+ * --name, --worship, --kids and --content
+ */
+interface ServiceBuildOptions {
+  /** Template playlist UUID or name */
+  template?: string;
+  /** Percentage (80) or fraction (0.8) */
+  threshold?: string;
+  report?: string;
+  /** Name of the playlist to create */
+  name?: string;
+  /** Library names or UUIDs, comma-separated */
+  worship?: string;
+  kids?: string;
+  content?: string;
+}
+
+const SERVICE_BUILD_FLAGS: Array<keyof ServiceBuildOptions> = ['template', 'threshold', 'report', 'name', 'worship', 'kids', 'content'];
+
 interface CLIOptions {
   host: string;
   port: number;
   command: string;
   args: string[];
-  format: 'text' | 'json';
+  format: string;
+  /** Export format options given as --option key=value */
+  exportOptions: Record<string, string>;
+  build: ServiceBuildOptions;
   debug: boolean;
+  /** Show what would change without changing anything in ProPresenter */
+  dryRun: boolean;
 }
 
 function parseArgs(argv: string[]): CLIOptions {
@@ -52,7 +138,10 @@ function parseArgs(argv: string[]): CLIOptions {
     command: 'help',
     args: [],
     format: 'text',
+    exportOptions: {},
+    build: {},
     debug: false,
+    dryRun: false,
   };
 
   const args = argv.slice(2);
@@ -67,6 +156,19 @@ function parseArgs(argv: string[]): CLIOptions {
       options.port = parseInt(args[++i], 10);
     } else if (arg === '--json' || arg === '-j') {
       options.format = 'json';
+    } else if (arg === '--format' || arg === '-f') {
+      options.format = (args[++i] || 'text').toLowerCase();
+    } else if (arg === '--option' || arg === '-o') {
+      const [key, ...rest] = (args[++i] || '').split('=');
+      if (key) {
+        options.exportOptions[key] = rest.join('=');
+      }
+    } else if (arg === '--theme' || arg === '-t') {
+      options.exportOptions.theme = args[++i] || '';
+    } else if (arg.startsWith('--') && SERVICE_BUILD_FLAGS.includes(arg.slice(2) as keyof ServiceBuildOptions)) {
+      options.build[arg.slice(2) as keyof ServiceBuildOptions] = args[++i] || '';
+    } else if (arg === '--dry-run') {
+      options.dryRun = true;
     } else if (arg === '--debug' || arg === '-d') {
       options.debug = true;
     } else if (arg === '--help') {
@@ -98,12 +200,53 @@ COMMANDS:
   playlists           List all available playlists
   export              Export lyrics from a playlist (interactive)
   export <uuid>       Export lyrics from specific playlist UUID
+  export <path>       Export lyrics from .pro/.proplaylist files (offline)
+  export <src> <out>  Write the export to a file instead of the terminal
   pptx                Export playlist to PowerPoint (interactive)
   pptx <uuid> [out]   Export specific playlist to PowerPoint
+  pptx <path> [out]   Export .pro/.proplaylist files to PowerPoint (offline)
+  chordpro <src> [out] Export playlist or files as ChordPro (.cho)
+  opensong <src> [out] Export playlist or files as OpenSong XML (zipped)
+  formats             List export formats and their options
+  themes              List PPTX themes (built-in and custom)
+  themes show <id>    Print a theme's JSON
+  themes init <id> [from] Copy a theme into the themes folder to edit
+  themes layouts <file.pptx> List the layouts of a master template
+  service parse <file> [grammar] List the songs and readings in a service order
+                      (.pdf, .docx, .txt or .md; -o layout=positional for
+                      two-column PDFs, -o lines=true to see the lines read)
+  service build <file> [grammar] Parse, match and fill a new playlist from a
+                      template (--template <playlist>, --threshold 80,
+                      --report <file>, --name <playlist name>, --worship,
+                      --kids and --content <library>, --dry-run)
+  grammars            List service order grammars (how PDFs are read)
+  grammars show <id>  Print a grammar's JSON
+  grammars init <id> [from] Copy a grammar into the grammars folder to edit
+  grammars test <order.pdf> [id] Show what a grammar finds in a service order
+  report [from] [to]  CCLI song usage report (dates as YYYY-MM-DD)
+  report ... <file>   Save the report as .csv or .json
   libraries           List all available libraries
   current             Show currently active presentation
   focused             Show focused presentation
   inspect <uuid>      Get full details of a presentation
+  classify <uuid|path> Show why each slide is or isn't treated as lyrics
+  classify rules      Show the lyric classification rules
+  classify rules init Write the built-in rules to a file for editing
+  diff <before> <after> Compare two versions of a song (UUID, .pro, .json or
+                      snapshot:<song>[@version])
+  search "<lyric>"    Find songs by a line of their lyrics
+  search index [library] Build the lyric search index (all libraries by default)
+  duplicates [library] Find copies of the same song (all libraries by default)
+  duplicates <path>   Find duplicates among .pro files in a folder (offline)
+  lint [uuid]         Check a playlist's lyrics for typos and layout problems
+  lint library <name> Check every song in a library
+  lint <path>         Check .pro/.proplaylist files (offline)
+  lint allow <word>   Add words the spelling check should accept
+  snapshot [library]  Store the lyrics of every song (all libraries by default)
+  snapshot list       List songs in the snapshot store
+  snapshot history <song> Show a song's stored versions
+  snapshot show <song> [version] Print a stored version
+  snapshot restore <song> [version] [file] Save a stored version as text
   watch               Watch for real-time slide changes
   alias               Manage song alias mappings (list/add/remove)
   alias list          Show all saved song aliases
@@ -117,11 +260,25 @@ COMMANDS:
   tunnel              Manage Cloudflare Tunnel integration
   tunnel status       Check tunnel configuration and reachability
   tunnel config       Generate cloudflared config file
+  simulate [fixtures] Run a local ProPresenter API simulator on --host/--port
+                      (fixtures: .json, .pro, .proplaylist or a folder)
 
 OPTIONS:
   --host, -h <addr>   ProPresenter host (default: 127.0.0.1)
   --port, -p <port>   ProPresenter port (default: 1025)
   --json, -j          Output results as JSON
+  --format, -f <id>   Export format (text, json, pptx, ... see "formats")
+  --option, -o k=v    Set an export format option (repeatable)
+  --theme, -t <id>    PPTX theme (see "themes"; default: classic)
+  --template <id>     Template playlist for service build (UUID or name)
+  --threshold <n>     Match confidence for service build (default: 70)
+  --report <file>     Review report for service build (.md or .json)
+  --name <name>       Name of the playlist service build creates
+  --worship <libs>    Worship libraries for service build (names or UUIDs,
+                      comma-separated; default: app setting, then Worship)
+  --kids <lib>        Kids library for service build
+  --content <lib>     Service content library for service build (videos, readings)
+  --dry-run           Show what service build would do without changing ProPresenter
   --debug, -d         Show detailed error information
   --help              Display this help message
 
@@ -148,6 +305,63 @@ EXAMPLES:
   # Export specific playlist to PowerPoint
   npm start -- pptx abc123-def456 my-service
 
+  # Export to PowerPoint with the dark stage theme
+  npm start -- pptx abc123-def456 my-service --theme dark-stage
+
+  # Export onto the church's PowerPoint master
+  npm start -- pptx abc123-def456 my-service -o templatePath=~/Templates/church.pptx
+
+  # Export a playlist as ChordPro for the band
+  npm start -- chordpro abc123-def456 band-charts
+
+  # List the songs in a service order sent as a Word document
+  npm start -- service parse ~/Downloads/order.docx
+
+  # Check how a service order PDF is read with a custom grammar
+  npm start -- grammars test ~/Downloads/order.pdf evening
+
+  # Preview the playlist Sunday's order would make, without changing anything
+  npm start -- service build ~/Downloads/order.pdf --template "Sunday Template" --dry-run
+
+  # Build it, leaving matches under 85% for review in review.md
+  npm start -- service build ~/Downloads/order.pdf --template "Sunday Template" --threshold 85 --report review.md
+
+  # Read a two-column bulletin by text position, showing the lines it sees
+  npm start -- service parse ~/Downloads/bulletin.pdf -o layout=positional -o lines=true
+
+  # CCLI usage report for the first quarter as CSV
+  npm start -- report 2026-01-01 2026-03-31 usage.csv
+
+  # Export lyrics as JSON to a file
+  npm start -- export abc123-def456 lyrics --format json
+
+  # Export a playlist bundle without ProPresenter running
+  npm start -- pptx ~/Desktop/Sunday.proplaylist my-service
+
+  # Develop without ProPresenter: run the simulator, then use it as normal
+  npm start -- simulate --port 1026
+  npm start -- playlists --port 1026
+
+  # See why slides of a song were left out of the export
+  npm start -- classify abc123-def456
+
+  # What changed in a song since last month's export?
+  npm start -- diff last-month.json abc123-def456
+
+  # Which song has this line?
+  npm start -- search index Worship
+  npm start -- search "grace that taught my heart to fear"
+
+  # Find songs imported twice under different titles
+  npm start -- duplicates Worship
+
+  # Check Sunday's songs for typos and long lines
+  npm start -- lint abc123-def456 -o max-line=36
+
+  # Record this week's lyrics, then see what changed in a song
+  npm start -- snapshot Worship
+  npm start -- snapshot history "Amazing Grace"
+
   # Connect to different host
   npm start -- status --host 192.168.1.100 --port 1025
 
@@ -334,6 +548,128 @@ async function inspectPresentation(
   await showPresentation(client, presentation, 'Inspected', format, debug);
 }
 
+/**
+ * Explain, slide by slide, which classification rule included or excluded it
+ */
+function printClassification(presentations: PresentationInfo[], format: string): void {
+  const rules = loadClassificationRules();
+  const results = presentations.map(presentation => ({
+    title: presentation.name,
+    uuid: presentation.uuid,
+    slides: presentation.groups.flatMap(group => group.slides.map(slide => ({
+      group: group.name,
+      index: slide.index,
+      label: slide.label || undefined,
+      enabled: slide.enabled,
+      text: slide.text,
+      ...classifySlide(slide, group.name, rules),
+    }))),
+  }));
+
+  if (format === 'json') {
+    console.log(JSON.stringify(results, null, 2));
+    return;
+  }
+
+  for (const result of results) {
+    const included = result.slides.filter(slide => slide.isLyric).length;
+    console.log(`\n${result.title}  (${included}/${result.slides.length} slides are lyrics)`);
+    console.log('-'.repeat(60));
+    for (const slide of result.slides) {
+      const mark = slide.isLyric ? '✓' : '✗';
+      const preview = slide.text.replace(/\s+/g, ' ').trim().slice(0, 40) || '(empty)';
+      const flags = [slide.label && `label "${slide.label}"`, slide.enabled === false && 'disabled'].filter(Boolean).join(', ');
+      console.log(`  ${mark} [${slide.group}] #${slide.index + 1} ${preview}${flags ? ` (${flags})` : ''}`);
+      console.log(`      ${slide.ruleId ? `rule ${slide.ruleId}: ` : ''}${slide.reason}`);
+    }
+  }
+  console.log('');
+}
+
+/**
+ * "snapshot:<song>[@version]" names a stored snapshot in diff arguments
+ */
+function isSnapshotRef(arg: string): boolean {
+  return arg.startsWith('snapshot:');
+}
+
+function loadSnapshotRef(arg: string): { lyrics: ExtractedLyrics; label: string } {
+  const ref = arg.slice('snapshot:'.length);
+  const at = ref.lastIndexOf('@');
+  const [query, version] = at > 0 ? [ref.slice(0, at), ref.slice(at + 1)] : [ref, undefined];
+  const snapshot = loadSnapshotVersion(query, version);
+  return { lyrics: snapshot.lyrics, label: `snapshot v${snapshot.number}, ${snapshot.version.takenAt.slice(0, 10)}` };
+}
+
+/**
+ * Compare two versions of a song. Each side is a .pro file, a JSON lyrics
+ * export, a stored snapshot or a presentation UUID (which needs the client).
+ */
+async function printLyricDiff(args: string[], client: ProPresenterClient | null, format: string): Promise<void> {
+  if (args.length < 2) {
+    throw new Error('diff needs two versions to compare: <before> <after> (UUID, .pro, .json or snapshot:<song>)');
+  }
+
+  const loaded: Array<{ lyrics: ExtractedLyrics; label: string } | null> = [null, null];
+  // Files and presentations first, so a JSON export can pick the matching song
+  for (const index of [0, 1]) {
+    const arg = args[index];
+    if (path.extname(arg).toLowerCase() === '.json') continue;
+    if (isSnapshotRef(arg)) {
+      loaded[index] = loadSnapshotRef(arg);
+      continue;
+    }
+    if (isLyricsFile(arg)) {
+      loaded[index] = { lyrics: loadLyricsFile(arg), label: path.basename(arg) };
+      continue;
+    }
+    if (!client) {
+      throw new Error(`"${arg}" is not a .pro or .json file or a snapshot`);
+    }
+    const presentation = await client.getPresentationByUuid(arg);
+    if (!presentation) {
+      throw new Error(`Presentation not found: ${arg}`);
+    }
+    loaded[index] = { lyrics: extractLyrics(presentation), label: 'ProPresenter' };
+  }
+  for (const index of [0, 1]) {
+    if (loaded[index]) continue;
+    const other = loaded[1 - index]?.lyrics;
+    loaded[index] = {
+      lyrics: loadLyricsFile(args[index], { uuid: other?.uuid, title: other?.title }),
+      label: path.basename(args[index]),
+    };
+  }
+
+  const [before, after] = loaded as Array<{ lyrics: ExtractedLyrics; label: string }>;
+  const diff = diffLyrics(before.lyrics, after.lyrics, { before: before.label, after: after.label });
+
+  if (format === 'json') {
+    console.log(JSON.stringify(diff, null, 2));
+    return;
+  }
+  console.log(`\n${formatLyricDiff(diff)}\n`);
+}
+
+function printClassificationRules(format: string): void {
+  const rules = loadClassificationRules();
+
+  if (format === 'json') {
+    console.log(JSON.stringify(rules, null, 2));
+    return;
+  }
+
+  const rulesPath = getClassificationRulesPath();
+  console.log(`\nLyric classification rules (${fs.existsSync(rulesPath) ? rulesPath : 'built-in'}):\n`);
+  const ordered = [...rules.rules].sort((a, b) => b.priority - a.priority);
+  for (const rule of ordered) {
+    const state = rule.disabled ? ' (off)' : '';
+    console.log(`  ${String(rule.priority).padStart(4)}  ${rule.action.padEnd(7)}  ${rule.id}${state}`);
+    console.log(`        ${rule.description ? `${rule.description} — ` : ''}${JSON.stringify(rule.match)}`);
+  }
+  console.log(`\n  Otherwise: ${rules.defaultAction}\n`);
+}
+
 function logPlaylistProgress(
   event: PlaylistProgressEvent,
   { debug = false, verbose = true }: { debug?: boolean; verbose?: boolean } = {}
@@ -378,81 +714,756 @@ function logPlaylistProgress(
   }
 }
 
-async function exportPlaylistLyrics(
+/**
+ * Best-effort playlist name lookup for usage records
+ */
+async function resolvePlaylistName(client: ProPresenterClient, playlistId: string): Promise<string> {
+  try {
+    const match = flattenPlaylists(await client.getPlaylists()).find(p => p.uuid === playlistId);
+    return match ? formatPlaylistName(match) : playlistId;
+  } catch {
+    return playlistId;
+  }
+}
+
+async function collectSongs(
   client: ProPresenterClient,
   playlistId: string,
-  format: string,
+  verbose: boolean,
   debug: boolean
-): Promise<void> {
+): Promise<ExtractedLyrics[]> {
   await client.connect();
 
-  console.log(`\nFetching playlist: ${playlistId}`);
+  if (verbose) {
+    console.log(`\nFetching playlist: ${playlistId}`);
+  }
 
   const result = await collectPlaylistLyrics(client, playlistId, {
     libraryFilter: DEFAULT_LIBRARY,
-    onProgress: (event) => logPlaylistProgress(event, { debug, verbose: format !== 'json' }),
+    onProgress: (event) => logPlaylistProgress(event, { debug, verbose }),
   });
 
-  if (result.songs.length === 0) {
-    console.log('No songs found in playlist.');
+  return result.songs.map(entry => entry.lyrics);
+}
+
+function loadOfflineSongs(sourcePath: string, verbose: boolean): ExtractedLyrics[] {
+  const source = loadPresentationsFromPath(sourcePath);
+  if (verbose) {
+    console.log(`\nReading offline source: ${source.name} (${source.presentations.length} presentations)`);
+  }
+
+  const songs: ExtractedLyrics[] = [];
+  for (const presentation of source.presentations) {
+    const lyrics = extractLyrics(presentation);
+    if (lyrics.lyricSlideCount === 0) {
+      if (verbose) console.log(`  ⊘ ${presentation.name}: no lyric slides, skipped`);
+      continue;
+    }
+    if (verbose) console.log(`  ✓ ${presentation.name}: ${lyrics.lyricSlideCount} lyric slides`);
+    songs.push(lyrics);
+  }
+  return songs;
+}
+
+/**
+ * Progress output would corrupt machine-readable formats printed to stdout,
+ * so only plain text keeps the running commentary in that case.
+ */
+function isVerboseExport(exporter: LyricsExporter, outputPath?: string): boolean {
+  const toStdout = typeof exporter.render === 'function' && !outputPath;
+  return !toStdout || exporter.id === 'text';
+}
+
+/**
+ * Print or save an export. Usage is recorded for the playlist, when given,
+ * once the file has been written; previews on stdout and offline files are
+ * not counted as CCLI usage.
+ */
+async function writeExport(
+  songs: ExtractedLyrics[],
+  exporter: LyricsExporter,
+  outputPath: string | undefined,
+  rawOptions: Record<string, string>,
+  playlist?: { name: string; id?: string }
+): Promise<void> {
+  if (songs.length === 0) {
+    console.log('No songs to export.');
+    return;
+  }
+
+  const options = resolveExportOptions(exporter, { logoPath: findLogoPath(), ...rawOptions });
+
+  // Text formats print to stdout unless an output file was given
+  if (exporter.render && !outputPath) {
+    if (exporter.id === 'text') {
+      console.log('\n' + '='.repeat(60) + '\n');
+    }
+    console.log(exporter.render(songs, options));
+    if (exporter.id === 'text') {
+      console.log(`\nExported ${songs.length} songs.`);
+    }
     return;
   }
 
-  const lyricsList = result.songs.map(entry => entry.lyrics);
+  console.log(`\nGenerating ${exporter.label}...`);
+  if (options.logoPath) {
+    console.log(`  Using logo: ${options.logoPath}`);
+  }
+
+  const finalPath = await exporter.write(songs, outputPath || `service-lyrics-${Date.now()}`, options, {
+    // Only report slides that needed adjusting
+    onProgress: event => {
+      if (event.action !== 'fit' || event.overflow) {
+        console.log(`  ${event.overflow ? '⚠️ ' : ''}${describeExportProgress(event)}`);
+      }
+    },
+  });
+
+  if (playlist) {
+    recordExportUsage(songs, playlist);
+  }
+
+  console.log(`\n✓ ${exporter.label} saved to: ${finalPath}`);
+  console.log(`  ${songs.length} songs`);
+}
+
+/**
+ * CCLI usage report. Positional args: optional from/to dates (YYYY-MM-DD)
+ * and an optional output file (.csv or .json).
+ */
+function printUsageReport(args: string[], format: string): void {
+  const dates = args.filter(arg => /^\d{4}-\d{2}-\d{2}$/.test(arg));
+  const outputPath = args.find(arg => !/^\d{4}-\d{2}-\d{2}$/.test(arg));
+  const [from, to] = dates;
+
+  const report = buildUsageReport(from, to);
+  const asJson = format === 'json' || outputPath?.toLowerCase().endsWith('.json');
+
+  if (outputPath) {
+    const content = asJson ? JSON.stringify(report, null, 2) : formatUsageReportCsv(report);
+    fs.writeFileSync(outputPath, content, 'utf-8');
+    console.log(`\n✓ Usage report saved to: ${outputPath}`);
+    console.log(`  ${report.songs.length} songs from ${report.totalPlaylists} playlists`);
+    return;
+  }
+
+  if (asJson) {
+    console.log(JSON.stringify(report, null, 2));
+    return;
+  }
+  if (format === 'csv') {
+    process.stdout.write(formatUsageReportCsv(report));
+    return;
+  }
+
+  const range = from || to ? `${from || 'start'} to ${to || 'today'}` : 'all time';
+  console.log(`\nSong Usage (${range})`);
+  console.log('='.repeat(60));
+
+  if (report.songs.length === 0) {
+    console.log('\n  No usage recorded for this period.');
+    console.log(`  Usage is recorded on every export (${getUsageFilePath()})`);
+    return;
+  }
+
+  for (const row of report.songs) {
+    const ccli = row.ccliNumber ? `CCLI ${row.ccliNumber}` : 'CCLI ?';
+    console.log(`  ${String(row.timesUsed).padStart(3)}×  ${row.title}  (${ccli})`);
+  }
+  console.log(`\n  ${report.songs.length} songs from ${report.totalPlaylists} playlists`);
+}
+
+function logSnapshotProgress(event: SnapshotProgressEvent): void {
+  switch (event.type) {
+    case 'library':
+      console.log(`\n${event.name} (${event.presentations} presentations)`);
+      break;
+    case 'song':
+      if (event.outcome !== 'unchanged') {
+        console.log(`  ${event.outcome === 'added' ? '+' : '~'} ${event.title}`);
+      }
+      break;
+    case 'error':
+      console.log(`  ✗ ${event.title}: ${event.message}`);
+      break;
+  }
+}
+
+/**
+ * Store the lyrics of every song in the given libraries (all by default)
+ */
+async function snapshotLibraries(client: ProPresenterClient, libraries: string[], format: string): Promise<void> {
+  const run = await takeLibrarySnapshot(client, {
+    libraries,
+    onProgress: format === 'json' ? undefined : logSnapshotProgress,
+  });
 
   if (format === 'json') {
-    console.log(JSON.stringify(lyricsList, null, 2));
+    console.log(JSON.stringify(run, null, 2));
+    return;
+  }
+  console.log(`\n✓ Snapshot of ${run.songs} songs: ${run.added} new, ${run.changed} changed`
+    + `${run.failed ? `, ${run.failed} failed` : ''}`);
+  console.log(`  Stored in ${getSnapshotDir()}`);
+}
+
+/**
+ * Browse the snapshot store: list, history, show and restore
+ */
+function runSnapshotCommand(args: string[], format: string): void {
+  const [subcommand, query, version, outputPath] = args;
+
+  if (subcommand === 'list') {
+    const songs = listSnapshotSongs();
+    if (format === 'json') {
+      console.log(JSON.stringify(songs, null, 2));
+      return;
+    }
+    if (songs.length === 0) {
+      console.log('\nNo snapshots yet. Run "snapshot" while ProPresenter is running.');
+      return;
+    }
+    console.log(`\nSnapshots (${songs.length} songs)\n`);
+    for (const song of songs) {
+      const latest = song.versions[song.versions.length - 1];
+      const versions = `${song.versions.length} version${song.versions.length === 1 ? '' : 's'}`;
+      console.log(`  ${song.title}  [${song.library}]  ${versions}, latest ${latest.takenAt.slice(0, 10)}`);
+      console.log(`    UUID: ${song.uuid}`);
+    }
     return;
   }
 
-  console.log('\n' + '='.repeat(60) + '\n');
+  if (!query) {
+    throw new Error(`snapshot ${subcommand} needs a song title or UUID`);
+  }
+
+  if (subcommand === 'history') {
+    const song = findSnapshotSong(query);
+    if (!song) {
+      throw new Error(`No snapshots of "${query}"`);
+    }
+    const history = song.versions.map((entry, index) => {
+      const previous = index > 0 ? loadSnapshotVersion(song.uuid, `v${index}`).lyrics : null;
+      const current = loadSnapshotVersion(song.uuid, `v${index + 1}`).lyrics;
+      return { number: index + 1, ...entry, changes: previous ? diffLyrics(previous, current).summary : null };
+    });
+    if (format === 'json') {
+      console.log(JSON.stringify({ ...song, versions: history }, null, 2));
+      return;
+    }
+    console.log(`\n${song.title}  (${song.uuid})\n`);
+    for (const entry of history) {
+      const changes = entry.changes
+        ? `${entry.changes.slidesChanged} changed, ${entry.changes.slidesAdded} added, ${entry.changes.slidesRemoved} removed`
+        : 'first snapshot';
+      const when = entry.takenAt.replace('T', ' ').slice(0, 16);
+      console.log(`  v${entry.number}  ${when}  ${entry.hash.slice(0, 8)}  ${entry.lyricSlideCount} slides  (${changes})`);
+    }
+    console.log(`\n  Compare: diff "snapshot:${song.title}@v1" ${song.uuid}`);
+    return;
+  }
 
-  for (const song of lyricsList) {
-    console.log(formatLyricsAsText(song));
-    console.log('\n' + '-'.repeat(40) + '\n');
+  if (subcommand === 'show' || subcommand === 'restore') {
+    const snapshot = loadSnapshotVersion(query, version);
+    const content = format === 'json' ? JSON.stringify(snapshot.lyrics, null, 2) : formatLyricsAsText(snapshot.lyrics);
+    if (subcommand === 'show') {
+      console.log(`\n${content}`);
+      return;
+    }
+    const fileName = outputPath
+      || `${snapshot.lyrics.title.replace(/[^\w\s-]/g, '').trim() || 'song'} v${snapshot.number}.${format === 'json' ? 'json' : 'txt'}`;
+    fs.writeFileSync(fileName, content, 'utf-8');
+    console.log(`\n✓ Restored "${snapshot.lyrics.title}" v${snapshot.number} (${snapshot.version.takenAt.slice(0, 10)}) to ${fileName}`);
+    console.log('  Paste the text back into ProPresenter to undo the edit.');
+    return;
   }
 
-  console.log(`\nExported ${lyricsList.length} songs from playlist.`);
+  throw new Error(`Unknown snapshot subcommand: "${subcommand}"`);
 }
 
-async function exportPlaylistToPptx(
-  client: ProPresenterClient,
-  playlistId: string,
-  outputPath: string,
-  debug: boolean
+/**
+ * Crawl libraries into the lyric search index
+ */
+async function indexLyrics(client: ProPresenterClient, libraries: string[], format: string): Promise<void> {
+  const indexed = await buildLyricIndex(client, {
+    libraries,
+    onProgress: format === 'json' ? undefined : event => {
+      if (event.type === 'library') console.log(`\nIndexing ${event.name} (${event.presentations} presentations)...`);
+      if (event.type === 'error') console.log(`  ✗ ${event.title}: ${event.message}`);
+    },
+  });
+
+  if (format === 'json') {
+    console.log(JSON.stringify(indexed, null, 2));
+    return;
+  }
+  for (const library of indexed) {
+    console.log(`  ✓ ${library.name}: ${library.songs} songs`);
+  }
+  console.log(`\n  Index saved to ${getLyricIndexPath()}`);
+}
+
+/**
+ * Find songs by a line of their lyrics, from the local index
+ */
+function printLyricSearch(query: string, format: string): void {
+  if (listIndexedLibraries().length === 0) {
+    throw new Error('The lyric index is empty. Run "search index" while ProPresenter is running.');
+  }
+
+  const results = searchLyrics(query);
+  if (format === 'json') {
+    console.log(JSON.stringify(results, null, 2));
+    return;
+  }
+
+  console.log(`\nSongs containing "${query}"\n`);
+  if (results.length === 0) {
+    console.log('  No matches. Try fewer words, or run "search index" to pick up new songs.');
+    return;
+  }
+  for (const result of results) {
+    console.log(`  ${result.title}  [${result.library}]`);
+    if (result.snippet) {
+      console.log(`    ${result.section}: "${result.snippet}"`);
+    }
+    console.log(`    UUID: ${result.uuid}`);
+  }
+}
+
+function printDuplicateReport(report: DuplicateReport, scope: string, format: string): void {
+  if (format === 'json') {
+    console.log(JSON.stringify(report, null, 2));
+    return;
+  }
+
+  console.log(`\nDuplicate songs in ${scope} (${report.clusters.length} groups in ${report.songsScanned} songs)`);
+  console.log('='.repeat(60));
+  if (report.clusters.length === 0) {
+    console.log('\n  No duplicates found.');
+    return;
+  }
+
+  report.clusters.forEach((cluster, index) => {
+    console.log(`\n  ${index + 1}. ${cluster.songs[0].title}`);
+    cluster.songs.forEach((song, position) => {
+      const marker = position === 0 ? '★' : ' ';
+      console.log(`     ${marker} ${song.title}  [${song.library}]  ${song.slideCount} slides (${song.lyricSlideCount} lyric)`);
+      console.log(`       UUID: ${song.uuid}`);
+      const comparison = cluster.comparisons.find(entry => entry.uuid === song.uuid);
+      if (!comparison) return;
+      const reasons = comparison.reasons.map(reason => reason.replace('-', ' ')).join(', ') || 'via another copy';
+      const { changes } = comparison;
+      console.log(`       ${reasons} · title ${Math.round(comparison.titleSimilarity * 100)}%`
+        + ` · lyrics ${Math.round(comparison.lyricSimilarity * 100)}%`);
+      console.log(`       vs ★: ${changes.slidesChanged} changed, ${changes.slidesAdded} added, ${changes.slidesRemoved} removed slides;`
+        + ` ${comparison.linesOnlyInReference} lines only in ★, ${comparison.linesOnlyInThis} only here`);
+    });
+  });
+  console.log('\n  ★ = copy with the most lyric slides. Compare any two with "diff <uuid> <uuid>".');
+}
+
+function printLint(songs: ExtractedLyrics[], scope: string, exportOptions: Record<string, string>, format: string): void {
+  const report = lintSongs(songs, parseLintOptions(exportOptions));
+  if (format === 'json') {
+    console.log(JSON.stringify(report, null, 2));
+    return;
+  }
+  console.log('\n' + formatLintReport(report, scope));
+}
+
+async function lintLibrary(client: ProPresenterClient, args: string[], options: CLIOptions): Promise<void> {
+  if (args.length === 0) {
+    throw new Error('lint library needs a library name or UUID');
+  }
+  const songs: ExtractedLyrics[] = [];
+  const libraries = await crawlLibraries(client, {
+    libraries: args,
+    onLibrary: (library, presentations) => {
+      if (options.format !== 'json') console.log(`Reading ${library.name} (${presentations} presentations)...`);
+    },
+    onError: (title, error) => {
+      if (options.format !== 'json') console.log(`  ✗ ${title}: ${error.message}`);
+    },
+  }, ({ presentation }) => {
+    songs.push(extractLyrics(presentation));
+  });
+  printLint(songs, libraries.map(library => library.name).join(', '), options.exportOptions, options.format);
+}
+
+function listExportFormats(format: string): void {
+  const formats = describeExportFormats();
+
+  if (format === 'json') {
+    console.log(JSON.stringify(formats, null, 2));
+    return;
+  }
+
+  console.log('\nExport formats:\n');
+  for (const descriptor of formats) {
+    console.log(`  ${descriptor.id.padEnd(10)} ${descriptor.label} (.${descriptor.extension}) - ${descriptor.description}`);
+    for (const field of descriptor.options) {
+      const defaultValue = field.default !== undefined ? ` [default: ${field.default}]` : '';
+      const choices = field.choices ? ` {${field.choices.map(c => c.value).join('|')}}` : '';
+      console.log(`      -o ${field.key}=<${field.type}>${choices}  ${field.label}${defaultValue}`);
+    }
+  }
+  console.log('');
+}
+
+function listThemesCommand(format: string): void {
+  const themes = listThemes();
+
+  if (format === 'json') {
+    console.log(JSON.stringify(themes, null, 2));
+    return;
+  }
+
+  console.log('\nPPTX themes:\n');
+  for (const theme of themes) {
+    const source = theme.builtIn ? 'built-in' : 'custom';
+    console.log(`  ${theme.id.padEnd(14)} ${theme.name} (${source})${theme.description ? ` - ${theme.description}` : ''}`);
+  }
+  console.log(`\nCustom themes live in ${getThemesDir()}`);
+  console.log('Use with: npm start -- pptx <uuid> --theme <id>\n');
+}
+
+function showThemeCommand(id: string): void {
+  const theme = getTheme(id);
+  if (!theme) {
+    console.error(`Theme not found: "${id}"`);
+    console.log(`Available: ${listThemes().map(t => t.id).join(', ')}`);
+    process.exit(1);
+  }
+  const { builtIn, filePath, ...definition } = theme;
+  console.log(JSON.stringify(definition, null, 2));
+}
+
+function initThemeCommand(id: string, fromId: string): void {
+  const existing = getTheme(id);
+  if (existing && !existing.builtIn) {
+    console.error(`Theme "${id}" already exists: ${existing.filePath}`);
+    process.exit(1);
+  }
+  const theme = copyTheme(fromId, id);
+  console.log(`✓ Created theme "${theme.id}" from "${fromId}"`);
+  console.log(`  Edit: ${theme.filePath}`);
+}
+
+function listTemplateLayoutsCommand(templatePath: string, format: string): void {
+  if (!fs.existsSync(templatePath)) {
+    throw new Error(`Template not found: ${templatePath}`);
+  }
+  const layouts = listTemplateLayouts(templatePath);
+
+  if (format === 'json') {
+    console.log(JSON.stringify(layouts, null, 2));
+    return;
+  }
+
+  console.log(`\nLayouts in ${path.basename(templatePath)}:\n`);
+  for (const layout of layouts) {
+    const slots = [layout.titlePlaceholder && 'title', layout.bodyPlaceholder && 'body'].filter(Boolean).join(' + ');
+    console.log(`  ${layout.name.padEnd(28)} ${layout.type.padEnd(8)} ${slots || '(no text placeholders)'}`);
+  }
+  console.log('\nChoose with -o lyricLayout="<name>" and -o titleLayout="<name>"\n');
+}
+
+function listGrammarsCommand(format: string): void {
+  const grammars = listServiceGrammars();
+
+  if (format === 'json') {
+    console.log(JSON.stringify(grammars, null, 2));
+    return;
+  }
+
+  console.log('\nService order grammars:\n');
+  for (const grammar of grammars) {
+    const source = grammar.builtIn ? 'built-in' : 'custom';
+    console.log(`  ${grammar.id.padEnd(14)} ${grammar.name} (${source})${grammar.description ? ` - ${grammar.description}` : ''}`);
+  }
+  console.log(`\nCustom grammars live in ${getServiceGrammarsDir()}`);
+  console.log('Try one with: npm start -- grammars test <order.pdf> <id>\n');
+}
+
+function showGrammarCommand(id: string): void {
+  const grammar = getServiceGrammar(id);
+  if (!grammar) {
+    console.error(`Grammar not found: "${id}"`);
+    console.log(`Available: ${listServiceGrammars().map(g => g.id).join(', ')}`);
+    process.exit(1);
+  }
+  const { builtIn, filePath, ...definition } = grammar;
+  console.log(JSON.stringify(definition, null, 2));
+}
+
+function initGrammarCommand(id: string, fromId: string): void {
+  const existing = getServiceGrammar(id);
+  if (existing && !existing.builtIn) {
+    console.error(`Grammar "${id}" already exists: ${existing.filePath}`);
+    process.exit(1);
+  }
+  const grammar = copyServiceGrammar(fromId, id);
+  console.log(`✓ Created grammar "${grammar.id}" from "${fromId}"`);
+  console.log(`  Edit: ${grammar.filePath}`);
+}
+
+/**
+ * A parser for a grammar, with -o layout=text|positional overriding how it reads PDFs
+ */
+function serviceOrderParser(grammarId: string | null | undefined, exportOptions: Record<string, string>): PDFParser {
+  const grammar = requireServiceGrammar(grammarId);
+  const { layout } = exportOptions;
+  if (!layout) {
+    return new PDFParser(grammar);
+  }
+  if (layout !== 'text' && layout !== 'positional') {
+    throw new Error(`Unknown layout "${layout}". Use text or positional`);
+  }
+  return new PDFParser({ ...grammar, pdfLayout: layout });
+}
+
+/**
+ * Parse a service order (.pdf, .docx, .txt or .md) with a grammar and show what was found.
+ * -o layout=text|positional overrides how the grammar reads PDFs; -o lines=true
+ * prints the lines in reading order with their bold/heading cues first.
+ */
+async function printServiceOrder(
+  filePath: string,
+  grammarId: string | undefined,
+  format: string,
+  exportOptions: Record<string, string> = {}
 ): Promise<void> {
-  await client.connect();
+  if (!fs.existsSync(filePath)) {
+    throw new Error(`File not found: ${filePath}`);
+  }
+  const parser = serviceOrderParser(grammarId, exportOptions);
+  const { grammar } = parser;
 
-  console.log(`\nFetching playlist: ${playlistId}`);
+  const lines = ['true', '1', 'yes'].includes(exportOptions.lines)
+    ? await readServiceOrderLines(filePath, { pdfLayout: grammar.pdfLayout })
+    : undefined;
+  const parsed = lines ? parser.parseLines(lines) : await parser.parseFile(filePath);
 
-  const result = await collectPlaylistLyrics(client, playlistId, {
-    libraryFilter: DEFAULT_LIBRARY,
-    onProgress: (event) => logPlaylistProgress(event, { debug, verbose: true }),
+  if (format === 'json') {
+    const { rawText, ...result } = parsed;
+    console.log(JSON.stringify(lines ? { ...result, lines } : result, null, 2));
+    return;
+  }
+
+  if (lines) {
+    console.log(`\nLines (${grammar.pdfLayout} layout, H = heading, B = bold):`);
+    for (const line of lines) {
+      if (!line.text.trim()) continue;
+      const cue = line.heading ? 'H' : line.bold ? 'B' : ' ';
+      console.log(`  ${cue} ${line.text.replace(/\t/g, ' | ')}`);
+    }
+  }
+
+  console.log(`\n${path.basename(filePath)} (grammar: ${parser.grammar.id}, ${parser.grammar.pdfLayout} layout)`);
+  console.log(`  Date: ${parsed.date}`);
+  if (parsed.specialServiceType) {
+    console.log(`  Special service: ${parsed.specialServiceType}`);
+  }
+  console.log('');
+  if (parsed.sections.length === 0) {
+    console.log('  No songs, videos or readings found\n');
+    return;
+  }
+  for (const section of parsed.sections) {
+    const slot = section.praiseSlot ? `[${section.praiseSlot}]` : '';
+    const kind = section.isKidsVideo ? 'kids video' : section.type;
+    const leader = section.leader ? ` (${section.leader})` : '';
+    console.log(`  ${kind.padEnd(10)} ${slot.padEnd(10)} ${section.title}${leader}`);
+    if (section.notes) {
+      console.log(`  ${''.padEnd(21)} ${section.notes}`);
+    }
+  }
+  console.log('');
+}
+
+/**
+ * Song match threshold from --threshold: a percentage (80) or a fraction (0.8)
+ */
+function parseMatchThreshold(value: string | undefined): number {
+  if (!value) {
+    return DEFAULT_SONG_THRESHOLD;
+  }
+  const number = Number(value.replace(/%$/, ''));
+  const threshold = number > 1 ? number / 100 : number;
+  if (!Number.isFinite(number) || threshold <= 0 || threshold > 1) {
+    throw new Error(`Invalid threshold "${value}". Use a percentage like 80 or a fraction like 0.8`);
+  }
+  return threshold;
+}
+
+/**
+ * UUIDs of the libraries named in a comma-separated list of names or UUIDs
+ */
+async function resolveLibraryIds(client: ProPresenterClient, value: string | null | undefined): Promise<string[]> {
+  if (!value) {
+    return [];
+  }
+  return (await resolveLibraries(client, value.split(','))).map(library => library.uuid);
+}
+
+async function resolveTemplatePlaylist(
+  client: ProPresenterClient,
+  wanted: string | null | undefined
+): Promise<{ uuid: string; name: string }> {
+  if (!wanted) {
+    throw new Error('service build needs a template playlist: --template <uuid or name>');
+  }
+  const key = wanted.trim().toLowerCase();
+  const match = flattenPlaylists(await client.getPlaylists()).find(playlist =>
+    playlist.uuid.toLowerCase() === key
+    || playlist.name.trim().toLowerCase() === key
+    || formatPlaylistName(playlist).toLowerCase() === key);
+  if (!match) {
+    throw new Error(`No playlist matching "${wanted}". List them with: npm start -- playlists`);
+  }
+  return { uuid: match.uuid, name: formatPlaylistName(match) };
+}
+
+/**
+ * Run the Service Generator without the app: parse a service order, match its
+ * songs and readings, and fill a new playlist made from a template. Matches
+ * below the threshold are left out and written to a review report. With
+ * --dry-run the planned playlist is printed and ProPresenter is left alone.
+ */
+async function buildServiceCommand(client: ProPresenterClient, options: CLIOptions): Promise<void> {
+  const [, filePath, grammarId] = options.args;
+  if (!filePath) {
+    throw new Error('service build needs a service order file (.pdf, .docx, .txt or .md)');
+  }
+  if (!fs.existsSync(filePath)) {
+    throw new Error(`File not found: ${filePath}`);
+  }
+  const { build } = options;
+  const json = options.format === 'json';
+  const log = (message: string) => {
+    if (!json) console.log(message);
+  };
+  // Library and template defaults are shared with the web app's settings
+  const settings = loadSettings();
+  const threshold = parseMatchThreshold(build.threshold);
+
+  const parsed = await serviceOrderParser(grammarId || settings.serviceGrammar, options.exportOptions).parseFile(filePath);
+  const { songs: songItems, readings } = serviceOrderItems(parsed);
+  log(`\n${path.basename(filePath)}: ${parsed.date}, ${songItems.length} songs and videos, ${readings.length} readings`);
+
+  const template = await resolveTemplatePlaylist(client, build.template || settings.templatePlaylistId);
+  const [kidsLibraryId] = await resolveLibraryIds(client, build.kids || settings.kidsLibraryId);
+  const [serviceContentLibraryId] = await resolveLibraryIds(client, build.content || settings.serviceContentLibraryId);
+  const libraries = await loadServiceLibraries(client, {
+    libraryIds: await resolveLibraryIds(client, build.worship || settings.worshipLibraryId || DEFAULT_LIBRARY),
+    kidsLibraryId,
+    serviceContentLibraryId,
   });
 
-  if (result.songs.length === 0) {
-    console.log('No songs to export.');
+  const songs = await matchServiceSongs(songItems, libraries, threshold);
+  const verses = matchVerseReferences(readings, libraries.serviceContent, threshold * 100);
+  const items = selectedServiceItems(songs, verses);
+  const playlistName = build.name || `Service ${parsed.date}`;
+
+  log(`\nMatches (threshold ${Math.round(threshold * 100)}%):`);
+  for (const song of songs) {
+    const slot = `[${song.isKidsVideo ? 'kids' : song.praiseSlot || 'praise1'}]`.padEnd(10);
+    const best = song.bestMatch ? `${song.bestMatch.name} (${song.bestMatch.confidence}%)` : 'no match';
+    log(`  ${song.selectedMatch ? '✓' : '?'} ${slot} ${song.songName} → ${best}`);
+  }
+  for (const verse of verses) {
+    const best = verse.bestMatch ? `${verse.bestMatch.name} (${verse.bestMatch.confidence}%)` : 'no match';
+    log(`  ${verse.selectedMatch ? '✓' : '?'} ${'[reading]'.padEnd(10)} ${verse.reference} → ${best}`);
+  }
+
+  const toReview = songs.filter(song => !song.selectedMatch).length + verses.filter(verse => !verse.selectedMatch).length;
+  let reportPath: string | undefined;
+  if (toReview > 0 || build.report) {
+    reportPath = build.report
+      || path.join(path.dirname(filePath), `${path.basename(filePath, path.extname(filePath))}-review.md`);
+    const report = reportPath.toLowerCase().endsWith('.json')
+      ? JSON.stringify({
+        source: filePath,
+        date: parsed.date,
+        threshold,
+        songs: songs.filter(song => !song.selectedMatch),
+        verses: verses.filter(verse => !verse.selectedMatch),
+      }, null, 2)
+      : formatServiceReview(songs, verses, { source: path.basename(filePath), date: parsed.date, playlist: playlistName, threshold });
+    fs.writeFileSync(reportPath, report, 'utf-8');
+    log(`\n${toReview} item${toReview === 1 ? '' : 's'} to review: ${reportPath}`);
+  }
+
+  if (options.dryRun) {
+    const planned = insertServiceItems((await fetchPlaylistItems(options.host, options.port, template.uuid)).items, items);
+    if (json) {
+      console.log(JSON.stringify({ dryRun: true, template, playlistName, songs, verses, items, planned, reportPath }, null, 2));
+      return;
+    }
+    const added = new Set(items.map(item => item.uuid));
+    console.log(`\nDry run: "${playlistName}" from template "${template.name}" would hold:`);
+    for (const item of planned) {
+      if (item.type === 'header') {
+        console.log(`\n  == ${item.id.name}`);
+      } else {
+        console.log(`  ${added.has(item.id.uuid) ? '+' : ' '}  ${item.id.name}`);
+      }
+    }
+    console.log('\nNothing was changed in ProPresenter (+ = from the service order)');
     return;
   }
 
-  const songs = result.songs.map(entry => entry.lyrics);
-  const logoPath = findLogoPath();
+  const playlistId = await client.createPlaylistFromTemplate(template.uuid, playlistName);
+  const built = await buildServicePlaylist(options.host, options.port, playlistId, items);
+  await recordServicePlaylistUsage(client, { name: playlistName, id: playlistId }, items);
 
-  console.log(`\nGenerating PowerPoint...`);
-  if (logoPath) {
-    console.log(`  Using logo: ${logoPath}`);
-  } else {
-    console.log(`  No logo found (place logo.png in project root to include it)`);
+  if (json) {
+    console.log(JSON.stringify({ dryRun: false, template, playlistName, playlistId, songs, verses, items, reportPath }, null, 2));
+    return;
   }
+  console.log(`\n✓ Created "${playlistName}" from "${template.name}": ${items.length} added, ${built.itemCount} items in the playlist`);
+}
 
-  const finalPath = await exportToPowerPoint(songs, {
-    outputPath,
-    logoPath,
-    includeSongTitles: true,
+/**
+ * Serve the ProPresenter API from fixtures until interrupted
+ */
+async function runSimulator(options: CLIOptions): Promise<void> {
+  const source = options.args[0];
+  const fixtures = loadFixtures(source);
+  const advanceSeconds = parseFloat(process.env.SIMULATOR_ADVANCE_SECONDS || '0');
+
+  const simulator = await startSimulator({
+    host: options.host,
+    port: options.port,
+    fixtures,
+    advanceIntervalMs: advanceSeconds > 0 ? advanceSeconds * 1000 : 0,
+    log: options.debug ? line => console.log(`  ${line}`) : undefined,
   });
 
-  console.log(`\n✓ PowerPoint saved to: ${finalPath}`);
-  console.log(`  ${songs.length} songs, slides formatted for print/display`);
+  console.log('');
+  console.log('  ProPresenter API Simulator');
+  console.log('  ==========================');
+  console.log(`  API:       ${simulator.url}/v1/version`);
+  console.log(`  Fixtures:  ${source || '(built-in)'}`);
+  console.log(`  Content:   ${fixtures.libraries.length} libraries, ${fixtures.presentations.length} presentations`);
+  if (advanceSeconds > 0) {
+    console.log(`  Advancing: every ${advanceSeconds}s`);
+  }
+  console.log('');
+  console.log('  Try:');
+  console.log(`    npm start -- playlists --host ${options.host} --port ${options.port}`);
+  console.log(`    PROPRESENTER_PORT=${options.port} npm run web:dev`);
+  console.log('');
+  console.log('  (Press Ctrl+C to stop)');
+
+  process.on('SIGINT', async () => {
+    await simulator.close();
+    process.exit(0);
+  });
+
+  // Keep the process running
+  await new Promise(() => {});
 }
 
 async function watchSlides(client: ProPresenterClient): Promise<void> {
@@ -1041,6 +2052,240 @@ async function main(): Promise<void> {
     process.exit(1);
   }
 
+  // Local API simulator — serves the API instead of connecting to it
+  if (options.command === 'simulate') {
+    try {
+      await runSimulator(options);
+    } catch (error: any) {
+      console.error(`\n❌ Error: ${error.message}`);
+      if (error.code === 'EADDRINUSE') {
+        console.error(`Port ${options.port} is already in use. Try --port 1026.`);
+      }
+      if (options.debug && error.stack) {
+        console.error(error.stack);
+      }
+      process.exit(1);
+    }
+  }
+
+  if (options.command === 'formats') {
+    listExportFormats(options.format);
+    process.exit(0);
+  }
+
+  // Themes are local files — no ProPresenter connection needed
+  if (options.command === 'themes') {
+    const subcommand = options.args[0] || 'list';
+
+    try {
+      if (subcommand === 'list') {
+        listThemesCommand(options.format);
+        process.exit(0);
+      }
+
+      if (subcommand === 'show' && options.args[1]) {
+        showThemeCommand(options.args[1]);
+        process.exit(0);
+      }
+
+      if (subcommand === 'init' && options.args[1]) {
+        initThemeCommand(options.args[1], options.args[2] || 'classic');
+        process.exit(0);
+      }
+
+      if (subcommand === 'layouts' && options.args[1]) {
+        listTemplateLayoutsCommand(options.args[1], options.format);
+        process.exit(0);
+      }
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+
+    console.error(`Unknown themes subcommand: "${subcommand}"`);
+    console.log('Usage: npm start -- themes [list|show <id>|init <id> [from]|layouts <file.pptx>]');
+    process.exit(1);
+  }
+
+  // Reading a service order needs no connection
+  if (options.command === 'service' && options.args[0] === 'parse') {
+    try {
+      if (!options.args[1]) {
+        throw new Error('service parse needs a service order file (.pdf, .docx, .txt or .md)');
+      }
+      await printServiceOrder(options.args[1], options.args[2], options.format, options.exportOptions);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Service order grammars are local files too
+  if (options.command === 'grammars') {
+    const subcommand = options.args[0] || 'list';
+
+    try {
+      if (subcommand === 'list') {
+        listGrammarsCommand(options.format);
+        process.exit(0);
+      }
+
+      if (subcommand === 'show' && options.args[1]) {
+        showGrammarCommand(options.args[1]);
+        process.exit(0);
+      }
+
+      if (subcommand === 'init' && options.args[1]) {
+        initGrammarCommand(options.args[1], options.args[2] || DEFAULT_SERVICE_GRAMMAR.id);
+        process.exit(0);
+      }
+
+      if (subcommand === 'test' && options.args[1]) {
+        await printServiceOrder(options.args[1], options.args[2], options.format, options.exportOptions);
+        process.exit(0);
+      }
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+
+    console.error(`Unknown grammars subcommand: "${subcommand}"`);
+    console.log('Usage: npm start -- grammars [list|show <id>|init <id> [from]|test <order.pdf> [id]]');
+    process.exit(1);
+  }
+
+  // Classification rules and offline sources need no connection
+  if (options.command === 'classify') {
+    try {
+      if (options.args[0] === 'rules') {
+        if (options.args[1] === 'init') {
+          const rulesPath = getClassificationRulesPath();
+          if (fs.existsSync(rulesPath)) {
+            console.error(`Rules file already exists: ${rulesPath}`);
+            process.exit(1);
+          }
+          saveClassificationRules(loadClassificationRules());
+          console.log(`✓ Wrote classification rules to ${rulesPath}`);
+        } else {
+          printClassificationRules(options.format);
+        }
+        process.exit(0);
+      }
+
+      if (options.args.length > 0 && isOfflineSource(options.args[0])) {
+        printClassification(loadPresentationsFromPath(options.args[0]).presentations, options.format);
+        process.exit(0);
+      }
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+  }
+
+  // Diffs between files need no connection
+  if (options.command === 'diff' && options.args.length >= 2
+    && options.args.slice(0, 2).every(arg => isLyricsFile(arg) || isSnapshotRef(arg))) {
+    try {
+      await printLyricDiff(options.args, null, options.format);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Searching the lyric index needs no connection; building it does
+  if (options.command === 'search' && options.args[0] !== 'index') {
+    try {
+      if (options.args.length === 0) {
+        throw new Error('search needs some words of the lyric to look for');
+      }
+      printLyricSearch(options.args.join(' '), options.format);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Duplicate checks over a folder of .pro files need no connection
+  if (options.command === 'duplicates' && options.args.length > 0 && isOfflineSource(options.args[0])) {
+    try {
+      const source = loadPresentationsFromPath(options.args[0]);
+      const report = findDuplicates(source.presentations.map(presentation => ({
+        library: source.name,
+        lyrics: extractLyrics(presentation),
+      })));
+      printDuplicateReport(report, source.name, options.format);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Word list edits and linting files need no connection
+  if (options.command === 'lint' && options.args[0] === 'allow') {
+    if (options.args.length < 2) {
+      console.error('Error: lint allow needs one or more words');
+      process.exit(1);
+    }
+    const added = addLintWords(options.args.slice(1));
+    console.log(added.length > 0
+      ? `✓ Added ${added.join(', ')} to ${getLintWordsPath()}`
+      : 'Those words are already allowed.');
+    process.exit(0);
+  }
+
+  if (options.command === 'lint' && options.args.length > 0 && isOfflineSource(options.args[0])) {
+    try {
+      const songs = loadOfflineSongs(options.args[0], options.format !== 'json');
+      printLint(songs, path.basename(options.args[0]), options.exportOptions, options.format);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Browsing stored snapshots needs no connection
+  if (options.command === 'snapshot' && ['list', 'history', 'show', 'restore'].includes(options.args[0])) {
+    try {
+      runSnapshotCommand(options.args, options.format);
+    } catch (error: any) {
+      console.error(`Error: ${error.message}`);
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
+  // Usage reports read the local log — no ProPresenter connection needed
+  if (options.command === 'report') {
+    printUsageReport(options.args, options.format);
+    process.exit(0);
+  }
+
+  // Offline export from .pro/.proplaylist files — no ProPresenter connection needed
+  const isExportCommand = options.command === 'export' || FORMAT_COMMANDS.has(options.command);
+  const exportFormat = FORMAT_COMMANDS.get(options.command) ?? options.format;
+
+  if (isExportCommand && options.args.length > 0 && isOfflineSource(options.args[0])) {
+    try {
+      const exporter = requireExporter(exportFormat);
+      const outputPath = options.args[1];
+      const songs = loadOfflineSongs(options.args[0], isVerboseExport(exporter, outputPath));
+      await writeExport(songs, exporter, outputPath, options.exportOptions);
+    } catch (error: any) {
+      console.error(`\n❌ Error: ${error.message}`);
+      if (options.debug && error.stack) {
+        console.error(error.stack);
+      }
+      process.exit(1);
+    }
+    process.exit(0);
+  }
+
   const client = new ProPresenterClient({
     host: options.host,
     port: options.port,
@@ -1093,18 +2338,66 @@ async function main(): Promise<void> {
         await inspectPresentation(client, options.args[0], options.format, options.debug);
         break;
 
-      case 'export': {
-        const playlistUuid =
-          options.args.length > 0 ? options.args[0] : await selectPlaylist(client);
-        await exportPlaylistLyrics(client, playlistUuid, options.format, options.debug);
+      case 'classify': {
+        if (options.args.length === 0) {
+          console.error('Error: classify command requires a presentation UUID or .pro path');
+          console.log('Usage: npm start -- classify <uuid>');
+          process.exit(1);
+        }
+        const presentation = await client.getPresentationByUuid(options.args[0]);
+        if (!presentation) {
+          throw new Error(`Presentation not found: ${options.args[0]}`);
+        }
+        printClassification([presentation], options.format);
         break;
       }
 
-      case 'pptx': {
+      case 'diff':
+        await printLyricDiff(options.args, client, options.format);
+        break;
+
+      case 'snapshot':
+        await snapshotLibraries(client, options.args, options.format);
+        break;
+
+      case 'search':
+        await indexLyrics(client, options.args.slice(1), options.format);
+        break;
+
+      case 'duplicates': {
+        const report = await scanLibraryDuplicates(client, options.args, {
+          onProgress: options.format === 'json' ? undefined : event => {
+            if (event.type === 'library') console.log(`Reading ${event.name} (${event.presentations} presentations)...`);
+            if (event.type === 'error') console.log(`  ✗ ${event.title}: ${event.message}`);
+          },
+        });
+        const scope = options.args.length > 0 ? options.args.join(', ') : 'all libraries';
+        printDuplicateReport(report, scope, options.format);
+        break;
+      }
+
+      case 'lint': {
+        if (options.args[0] === 'library') {
+          await lintLibrary(client, options.args.slice(1), options);
+          break;
+        }
+        const playlistUuid = options.args.length > 0 ? options.args[0] : await selectPlaylist(client);
+        const songs = await collectSongs(client, playlistUuid, options.format !== 'json', options.debug);
+        printLint(songs, await resolvePlaylistName(client, playlistUuid), options.exportOptions, options.format);
+        break;
+      }
+
+      case 'export':
+      case 'pptx':
+      case 'chordpro':
+      case 'opensong': {
+        const exporter = requireExporter(exportFormat);
         const playlistUuid =
           options.args.length > 0 ? options.args[0] : await selectPlaylist(client);
-        const pptxOutput = options.args[1] || `service-lyrics-${Date.now()}`;
-        await exportPlaylistToPptx(client, playlistUuid, pptxOutput, options.debug);
+        const outputPath = options.args[1];
+        const songs = await collectSongs(client, playlistUuid, isVerboseExport(exporter, outputPath), options.debug);
+        const playlistName = await resolvePlaylistName(client, playlistUuid);
+        await writeExport(songs, exporter, outputPath, options.exportOptions, { name: playlistName, id: playlistUuid });
         break;
       }
 
@@ -1112,6 +2405,15 @@ async function main(): Promise<void> {
         await watchSlides(client);
         break;
 
+      case 'service':
+        if (options.args[0] !== 'build') {
+          console.error(`Unknown service subcommand: "${options.args[0] || ''}"`);
+          console.log('Usage: npm start -- service [parse|build] <order.pdf> [grammar]');
+          process.exit(1);
+        }
+        await buildServiceCommand(client, options);
+        break;
+
       default:
         console.error(`❌ Unknown command: "${options.command}"\n`);
         printHelp();
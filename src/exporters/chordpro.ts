@@ -0,0 +1,85 @@
+/**
+ * ChordPro exporter - Lyrics for OnSong, SongBook and other band tools
+ *
+ * Each song gets a {title}, its credits, an arrangement line and one section environment per
+ * ProPresenter group. Multiple songs are separated with {new_song}.
+ */
+
+import type { ExtractedLyrics } from '../lyrics-extractor';
+import type { ExportOptionValues } from './types';
+import { createTextExporter } from './registry';
+import { classifySection, lyricSections, SectionKind } from './sections';
+
+const ENVIRONMENTS: Partial<Record<SectionKind, string>> = {
+  verse: 'verse',
+  chorus: 'chorus',
+  bridge: 'bridge',
+};
+
+function renderSong(song: ExtractedLyrics, options: ExportOptionValues): string {
+  const lines: string[] = [`{title: ${song.title}}`];
+  const sections = lyricSections(song);
+  const metadata = song.metadata;
+
+  if (metadata?.author || metadata?.artist) lines.push(`{artist: ${metadata.author || metadata.artist}}`);
+  if (metadata?.album) lines.push(`{album: ${metadata.album}}`);
+  const copyright = [metadata?.copyrightYear, metadata?.publisher].filter(Boolean).join(' ');
+  if (copyright) lines.push(`{copyright: ${copyright}}`);
+  if (metadata?.ccliNumber) lines.push(`{meta: ccli ${metadata.ccliNumber}}`);
+
+  if (options.includeArrangement !== false && sections.length > 0) {
+    lines.push(`{meta: arrangement ${sections.map(entry => entry.section.name).join(', ')}}`);
+  }
+
+  const written = new Set<string>();
+  for (const { section, lines: slides } of sections) {
+    const { kind } = classifySection(section.name);
+    const environment = ENVIRONMENTS[kind] ?? 'verse';
+    const repeat = written.has(section.name);
+
+    lines.push('');
+
+    // A repeated chorus can be recalled instead of written out again
+    if (repeat && !options.expandRepeats) {
+      lines.push(kind === 'chorus' ? `{chorus: ${section.name}}` : `{comment: ${section.name}}`);
+      continue;
+    }
+
+    lines.push(`{start_of_${environment}: ${section.name}}`);
+    slides.forEach((slideLines, index) => {
+      if (index > 0) lines.push('');
+      lines.push(...slideLines);
+    });
+    lines.push(`{end_of_${environment}}`);
+    written.add(section.name);
+  }
+
+  return lines.join('\n');
+}
+
+export const chordProExporter = createTextExporter({
+  id: 'chordpro',
+  label: 'ChordPro',
+  description: 'Section-tagged lyrics for OnSong, SongBook and other ChordPro apps',
+  extension: 'cho',
+  mimeType: 'text/plain',
+  options: [
+    {
+      key: 'includeArrangement',
+      label: 'Include arrangement line',
+      type: 'boolean',
+      default: true,
+      description: 'Adds {meta: arrangement ...} listing sections in sung order',
+    },
+    {
+      key: 'expandRepeats',
+      label: 'Write out repeated sections',
+      type: 'boolean',
+      default: false,
+      description: 'Repeat full lyrics instead of a {chorus} recall or comment',
+    },
+  ],
+  render: (songs, options) => songs
+    .map(song => renderSong(song, options))
+    .join('\n\n{new_song}\n'),
+});
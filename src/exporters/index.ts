@@ -0,0 +1,41 @@
+/**
+ * Export formats - Registers the built-in exporters
+ *
+ * To add a format, implement LyricsExporter in this folder and register it below.
+ */
+
+import { registerExporter } from './registry';
+import { textExporter, jsonExporter } from './text';
+import { pptxExporter } from './pptx';
+import { chordProExporter } from './chordpro';
+import { openSongExporter } from './opensong';
+
+registerExporter(textExporter);
+registerExporter(jsonExporter);
+registerExporter(pptxExporter);
+registerExporter(chordProExporter);
+registerExporter(openSongExporter);
+
+export {
+  registerExporter,
+  getExporter,
+  requireExporter,
+  listExporters,
+  describeExporter,
+  describeExportFormats,
+  resolveExportOptions,
+  describeExportProgress,
+  createTextExporter,
+  withExtension,
+} from './registry';
+export type {
+  LyricsExporter,
+  ExportFormatDescriptor,
+  ExportOptionField,
+  ExportOptionType,
+  ExportOptionValue,
+  ExportOptionValues,
+  ExportContext,
+  ExportProgressEvent,
+  SlideFitProgressEvent,
+} from './types';
@@ -0,0 +1,136 @@
+/**
+ * OpenSong exporter - One OpenSong XML song file per song, bundled as a ZIP
+ *
+ * Sections become [V1]/[C]/[B] style tags and the group order becomes the
+ * song's <presentation> sequence.
+ */
+
+import * as fs from 'fs';
+import type { ExtractedLyrics } from '../lyrics-extractor';
+import type { ExportOptionValues, LyricsExporter } from './types';
+import { withExtension } from './registry';
+import { classifySection, lyricSections, songFileName, SectionKind } from './sections';
+import { createZip } from '../utils/zip-writer';
+
+const TAG_LETTERS: Record<SectionKind, string> = {
+  verse: 'V',
+  chorus: 'C',
+  'pre-chorus': 'P',
+  bridge: 'B',
+  tag: 'T',
+  intro: 'I',
+  outro: 'E',
+  other: '',
+};
+
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+/**
+ * Assign a unique OpenSong tag to each distinct section name
+ */
+function buildTags(names: string[]): Map<string, string> {
+  const tags = new Map<string, string>();
+  const used = new Set<string>();
+  let verseCount = 0;
+
+  for (const name of names) {
+    if (tags.has(name)) continue;
+
+    const { kind, number } = classifySection(name);
+    let base = TAG_LETTERS[kind] || name.replace(/[^A-Za-z0-9]/g, '') || 'X';
+    if (kind === 'verse') {
+      base = `V${number ?? ++verseCount}`;
+    } else if (number !== undefined && TAG_LETTERS[kind]) {
+      base = `${base}${number}`;
+    }
+
+    let tag = base;
+    let suffix = 2;
+    while (used.has(tag)) {
+      tag = `${base}${suffix++}`;
+    }
+
+    used.add(tag);
+    tags.set(name, tag);
+  }
+
+  return tags;
+}
+
+export function renderOpenSongXml(song: ExtractedLyrics, options: ExportOptionValues = {}): string {
+  const sections = lyricSections(song);
+  const tags = buildTags(sections.map(entry => entry.section.name));
+
+  const lyricLines: string[] = [];
+  const written = new Set<string>();
+  for (const { section, lines: slides } of sections) {
+    if (written.has(section.name)) continue;
+    written.add(section.name);
+
+    lyricLines.push(`[${tags.get(section.name)}]`);
+    slides.forEach((slideLines, index) => {
+      if (index > 0) lyricLines.push('||');
+      lyricLines.push(...slideLines.map(line => ` ${line}`));
+    });
+    lyricLines.push('');
+  }
+
+  const order = options.includePresentationOrder === false
+    ? ''
+    : sections.map(entry => tags.get(entry.section.name)).join(' ');
+
+  return [
+    '<?xml version="1.0" encoding="UTF-8"?>',
+    '<song>',
+    `  <title>${escapeXml(song.title)}</title>`,
+    `  <author>${escapeXml(song.metadata?.author || '')}</author>`,
+    `  <copyright>${escapeXml([song.metadata?.copyrightYear, song.metadata?.publisher].filter(Boolean).join(' '))}</copyright>`,
+    `  <ccli>${escapeXml(song.metadata?.ccliNumber || '')}</ccli>`,
+    `  <presentation>${escapeXml(order)}</presentation>`,
+    `  <lyrics>${escapeXml(lyricLines.join('\n').trimEnd())}</lyrics>`,
+    '</song>',
+    '',
+  ].join('\n');
+}
+
+export const openSongExporter: LyricsExporter = {
+  id: 'opensong',
+  label: 'OpenSong',
+  description: 'OpenSong XML song files (one per song, zipped)',
+  extension: 'zip',
+  mimeType: 'application/zip',
+  options: [
+    {
+      key: 'includePresentationOrder',
+      label: 'Include presentation order',
+      type: 'boolean',
+      default: true,
+      description: 'Fills <presentation> with the section tags in sung order',
+    },
+  ],
+
+  async write(songs, outputPath, options) {
+    const used = new Set<string>();
+    const files = songs.map(song => {
+      // OpenSong song files have no extension and are named after the song
+      const base = songFileName(song.title);
+      let name = base;
+      let suffix = 2;
+      while (used.has(name.toLowerCase())) {
+        name = `${base} (${suffix++})`;
+      }
+      used.add(name.toLowerCase());
+      return { name, data: renderOpenSongXml(song, options) };
+    });
+
+    const finalPath = withExtension(outputPath, 'zip');
+    fs.writeFileSync(finalPath, createZip(files));
+    return finalPath;
+  },
+};
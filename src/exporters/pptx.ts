@@ -0,0 +1,111 @@
+/**
+ * PowerPoint exporter - Registry adapter for pptx-exporter
+ */
+
+import { exportToPowerPoint, DEFAULT_PPTX_TEXT_STYLE, PptxTextStyle } from '../pptx-exporter';
+import { requireTheme } from '../services/theme-store';
+import type { LyricsExporter } from './types';
+
+export const pptxExporter: LyricsExporter = {
+  id: 'pptx',
+  label: 'PowerPoint',
+  description: 'Themed slides with optional song titles and logo',
+  extension: 'pptx',
+  mimeType: 'application/vnd.openxmlformats-officedocument.presentationml.presentation',
+  options: [
+    { key: 'includeSongTitles', label: 'Include song title slides', type: 'boolean', default: true, fromSettings: true },
+    { key: 'logoPath', label: 'Logo image', type: 'string', fromSettings: true },
+    { key: 'theme', label: 'Theme', type: 'string', fromSettings: true, description: 'Slide design id (see `themes`); defaults to classic' },
+    { key: 'textColor', label: 'Text color', type: 'color', fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.textColor}` },
+    { key: 'fontFace', label: 'Font', type: 'string', fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.fontFace}` },
+    { key: 'fontSize', label: 'Lyric size (pt)', type: 'number', min: 8, max: 200, fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.fontSize}` },
+    { key: 'titleFontSize', label: 'Title size (pt)', type: 'number', min: 8, max: 200, fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.titleFontSize}` },
+    { key: 'bold', label: 'Bold', type: 'boolean', fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.bold}` },
+    { key: 'italic', label: 'Italic', type: 'boolean', fromSettings: true, description: `Defaults to the theme, then ${DEFAULT_PPTX_TEXT_STYLE.italic}` },
+    {
+      key: 'translationSource',
+      label: 'Second language',
+      type: 'select',
+      default: DEFAULT_PPTX_TEXT_STYLE.translationSource,
+      choices: [
+        { value: 'none', label: 'None (single language)' },
+        { value: 'element', label: 'Second text element' },
+        { value: 'notes', label: 'Slide notes' },
+      ],
+      description: 'Shows the translation below the lyrics in a smaller style',
+    },
+    { key: 'translationColor', label: 'Translation color', type: 'color', default: DEFAULT_PPTX_TEXT_STYLE.translationColor },
+    { key: 'translationFontSize', label: 'Translation size (pt)', type: 'number', default: DEFAULT_PPTX_TEXT_STYLE.translationFontSize, min: 8, max: 200 },
+    { key: 'translationItalic', label: 'Italic translation', type: 'boolean', default: DEFAULT_PPTX_TEXT_STYLE.translationItalic },
+    {
+      key: 'labelDisplay',
+      label: 'Slide labels',
+      type: 'select',
+      default: DEFAULT_PPTX_TEXT_STYLE.labelDisplay,
+      choices: [
+        { value: 'none', label: 'Hide' },
+        { value: 'notes', label: 'Speaker notes' },
+        { value: 'caption', label: 'Caption above lyrics' },
+      ],
+    },
+    { key: 'autoFit', label: 'Fit long slides', type: 'boolean', default: DEFAULT_PPTX_TEXT_STYLE.autoFit, description: 'Shrink text that overflows, then split it across slides' },
+    { key: 'minFontSize', label: 'Smallest lyric size (pt)', type: 'number', default: DEFAULT_PPTX_TEXT_STYLE.minFontSize, min: 8, max: 200 },
+    { key: 'maxFontSize', label: 'Largest lyric size (pt)', type: 'number', default: DEFAULT_PPTX_TEXT_STYLE.maxFontSize, min: 8, max: 200 },
+    {
+      key: 'templatePath',
+      label: 'Master template (.pptx)',
+      type: 'string',
+      fromSettings: true,
+      description: 'Reuse the slide master of an existing presentation instead of the theme',
+    },
+    {
+      key: 'copyrightFooter',
+      label: 'Copyright footer',
+      type: 'boolean',
+      default: process.env.PPTX_COPYRIGHT_FOOTER === 'true',
+      description: 'Song title, writers, copyright and CCLI numbers on the last slide of each song',
+    },
+    { key: 'ccliLicence', label: 'CCLI licence number', type: 'string', default: process.env.CCLI_LICENCE || undefined, description: 'Your church\'s licence, shown in the copyright footer' },
+    { key: 'lyricLayout', label: 'Template lyric layout', type: 'string', description: 'Layout name; defaults to the first text layout' },
+    { key: 'titleLayout', label: 'Template title layout', type: 'string', description: 'Layout name; defaults to the title layout' },
+  ],
+
+  async write(songs, outputPath, options, context) {
+    const styleOverrides: Partial<PptxTextStyle> = {
+      textColor: options.textColor as string | undefined,
+      fontFace: options.fontFace as string | undefined,
+      fontSize: options.fontSize as number | undefined,
+      titleFontSize: options.titleFontSize as number | undefined,
+      bold: options.bold as boolean | undefined,
+      italic: options.italic as boolean | undefined,
+      translationSource: options.translationSource as PptxTextStyle['translationSource'] | undefined,
+      translationColor: options.translationColor as string | undefined,
+      translationFontSize: options.translationFontSize as number | undefined,
+      translationItalic: options.translationItalic as boolean | undefined,
+      labelDisplay: options.labelDisplay as PptxTextStyle['labelDisplay'] | undefined,
+      autoFit: options.autoFit as boolean | undefined,
+      minFontSize: options.minFontSize as number | undefined,
+      maxFontSize: options.maxFontSize as number | undefined,
+    };
+    // Drop unset keys so they don't mask the defaults
+    for (const key of Object.keys(styleOverrides) as Array<keyof PptxTextStyle>) {
+      if (styleOverrides[key] === undefined) delete styleOverrides[key];
+    }
+
+    return exportToPowerPoint(songs, {
+      outputPath,
+      logoPath: (options.logoPath as string | undefined) || undefined,
+      includeSongTitles: options.includeSongTitles !== false,
+      styleOverrides,
+      theme: options.theme ? requireTheme(String(options.theme)) : undefined,
+      templatePath: (options.templatePath as string | undefined) || undefined,
+      templateLyricLayout: (options.lyricLayout as string | undefined) || undefined,
+      templateTitleLayout: (options.titleLayout as string | undefined) || undefined,
+      copyrightFooter: options.copyrightFooter === true,
+      ccliLicence: options.ccliLicence !== undefined ? String(options.ccliLicence) : undefined,
+      onSlideFit: context?.onProgress
+        ? event => context.onProgress!({ type: 'slide:fit', ...event })
+        : undefined,
+    });
+  },
+};
@@ -0,0 +1,58 @@
+/**
+ * Reflow options - Shared by every export format
+ *
+ * The registry adds these fields to each format it registers and reflows
+ * the songs before the format sees them, so text, JSON, PPTX and any new
+ * format break lines the same way.
+ */
+
+import {
+  reflowLyrics,
+  ReflowMode,
+  DEFAULT_REFLOW_WIDTH,
+  DEFAULT_REFLOW_LINES,
+} from '../services/lyric-reflow';
+import type { ExtractedLyrics } from '../lyrics-extractor';
+import type { ExportOptionField, ExportOptionValues, LyricsExporter } from './types';
+
+export const REFLOW_OPTION_FIELDS: ExportOptionField[] = [
+  {
+    key: 'reflow',
+    label: 'Line breaks',
+    type: 'select',
+    default: 'none',
+    choices: [
+      { value: 'none', label: 'As in ProPresenter' },
+      { value: 'paragraph', label: 'One paragraph per slide (bulletins)' },
+      { value: 'balance', label: 'Even lines of the target width' },
+      { value: 'split', label: 'Short slides (lower thirds)' },
+    ],
+  },
+  { key: 'reflowWidth', label: 'Target line width (characters)', type: 'number', default: DEFAULT_REFLOW_WIDTH, min: 10, max: 200 },
+  { key: 'reflowLines', label: 'Lines per slide when splitting', type: 'number', default: DEFAULT_REFLOW_LINES, min: 1, max: 12 },
+];
+
+export function applyReflow(songs: ExtractedLyrics[], options: ExportOptionValues): ExtractedLyrics[] {
+  return reflowLyrics(songs, {
+    mode: (options.reflow as ReflowMode | undefined) ?? 'none',
+    width: options.reflowWidth as number | undefined,
+    maxLines: options.reflowLines as number | undefined,
+  });
+}
+
+/**
+ * The exporter with the reflow options added and applied before rendering
+ */
+export function withReflow(exporter: LyricsExporter): LyricsExporter {
+  if (exporter.options.some(field => field.key === 'reflow')) {
+    return exporter;
+  }
+  const render = exporter.render;
+  return {
+    ...exporter,
+    options: [...exporter.options, ...REFLOW_OPTION_FIELDS],
+    render: render ? (songs, options) => render.call(exporter, applyReflow(songs, options), options) : undefined,
+    write: (songs, outputPath, options, context) =>
+      exporter.write(applyReflow(songs, options), outputPath, options, context),
+  };
+}
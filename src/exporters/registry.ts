@@ -0,0 +1,154 @@
+/**
+ * Exporter Registry
+ *
+ * Formats register themselves once here and then show up in the CLI
+ * --format flag, the web /api/export route and the desktop export panel.
+ * Every format gets the shared reflow options (see reflow.ts).
+ */
+
+import * as fs from 'fs';
+import type { ExtractedLyrics } from '../lyrics-extractor';
+import type {
+  ExportFormatDescriptor,
+  ExportOptionField,
+  ExportOptionValue,
+  ExportOptionValues,
+  ExportProgressEvent,
+  LyricsExporter,
+} from './types';
+import { withReflow } from './reflow';
+
+const exporters = new Map<string, LyricsExporter>();
+
+/**
+ * Register an export format. Re-registering an id replaces the previous exporter.
+ */
+export function registerExporter(exporter: LyricsExporter): void {
+  exporters.set(exporter.id.toLowerCase(), withReflow(exporter));
+}
+
+export function getExporter(id: string): LyricsExporter | undefined {
+  return exporters.get(id.toLowerCase());
+}
+
+/**
+ * Look up a format, throwing a helpful error listing the valid ids
+ */
+export function requireExporter(id: string): LyricsExporter {
+  const exporter = getExporter(id);
+  if (!exporter) {
+    const known = listExporters().map(e => e.id).join(', ');
+    throw new Error(`Unknown export format "${id}". Available formats: ${known}`);
+  }
+  return exporter;
+}
+
+export function listExporters(): LyricsExporter[] {
+  return Array.from(exporters.values());
+}
+
+export function describeExporter(exporter: LyricsExporter): ExportFormatDescriptor {
+  return {
+    id: exporter.id,
+    label: exporter.label,
+    description: exporter.description,
+    extension: exporter.extension,
+    mimeType: exporter.mimeType,
+    textual: typeof exporter.render === 'function',
+    options: exporter.options,
+  };
+}
+
+export function describeExportFormats(): ExportFormatDescriptor[] {
+  return listExporters().map(describeExporter);
+}
+
+function coerceOption(field: ExportOptionField, raw: unknown): ExportOptionValue | undefined {
+  if (raw === undefined || raw === null || raw === '') return undefined;
+
+  switch (field.type) {
+    case 'boolean':
+      if (typeof raw === 'boolean') return raw;
+      return !['false', '0', 'no', 'off'].includes(String(raw).trim().toLowerCase());
+    case 'number': {
+      const value = typeof raw === 'number' ? raw : parseFloat(String(raw));
+      if (Number.isNaN(value)) {
+        throw new Error(`Option "${field.key}" must be a number`);
+      }
+      if (field.min !== undefined && value < field.min) return field.min;
+      if (field.max !== undefined && value > field.max) return field.max;
+      return value;
+    }
+    case 'color':
+      return String(raw).replace(/^#/, '').trim();
+    case 'select': {
+      const value = String(raw);
+      if (field.choices && !field.choices.some(choice => choice.value === value)) {
+        const allowed = field.choices.map(choice => choice.value).join(', ');
+        throw new Error(`Option "${field.key}" must be one of: ${allowed}`);
+      }
+      return value;
+    }
+    default:
+      return String(raw);
+  }
+}
+
+/**
+ * Validate raw option values (strings from the CLI, JSON from the API) against
+ * a format's schema, applying defaults. Unknown keys are dropped.
+ */
+export function resolveExportOptions(
+  exporter: LyricsExporter,
+  raw: Record<string, unknown> = {}
+): ExportOptionValues {
+  const resolved: ExportOptionValues = {};
+  for (const field of exporter.options) {
+    const value = coerceOption(field, raw[field.key]);
+    resolved[field.key] = value !== undefined ? value : field.default;
+  }
+  return resolved;
+}
+
+/**
+ * One-line description of an export progress event for logs and the UIs
+ */
+export function describeExportProgress(event: ExportProgressEvent): string {
+  const where = event.section ? `${event.song} (${event.section})` : event.song;
+  const sizes = event.fontSizes.map(size => `${size}pt`).join(' + ');
+  const overflow = event.overflow ? ' — still overflows at the smallest size' : '';
+  switch (event.action) {
+    case 'split':
+      return `${where}: split across ${event.fontSizes.length} slides at ${sizes}${overflow}`;
+    case 'shrink':
+      return `${where}: shrunk to ${sizes}${overflow}`;
+    default:
+      return `${where}: fits at ${sizes}`;
+  }
+}
+
+/**
+ * Append the format's extension unless the path already has it
+ */
+export function withExtension(outputPath: string, extension: string): string {
+  const suffix = `.${extension}`;
+  return outputPath.toLowerCase().endsWith(suffix) ? outputPath : `${outputPath}${suffix}`;
+}
+
+/**
+ * Build an exporter for text-based formats: write() saves render() output as UTF-8
+ */
+export function createTextExporter(
+  definition: Omit<LyricsExporter, 'write' | 'render'> & {
+    render(songs: ExtractedLyrics[], options: ExportOptionValues): string;
+  }
+): LyricsExporter {
+  return {
+    ...definition,
+    async write(songs, outputPath, options) {
+      const finalPath = withExtension(outputPath, definition.extension);
+      fs.writeFileSync(finalPath, definition.render(songs, options), 'utf-8');
+      return finalPath;
+    },
+  };
+}
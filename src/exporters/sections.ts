@@ -0,0 +1,72 @@
+/**
+ * Section naming helpers shared by the song-format exporters
+ *
+ * ProPresenter group names are free text ("Verse 1", "Pre-Chorus", "CHORUS 2").
+ * These map them onto the section kinds and short tags band tools expect.
+ */
+
+import type { ExtractedLyrics, LyricSection } from '../lyrics-extractor';
+
+export type SectionKind =
+  | 'verse'
+  | 'chorus'
+  | 'pre-chorus'
+  | 'bridge'
+  | 'tag'
+  | 'intro'
+  | 'outro'
+  | 'other';
+
+export interface SectionInfo {
+  kind: SectionKind;
+  number?: number;
+}
+
+const SECTION_PATTERNS: Array<{ kind: SectionKind; pattern: RegExp }> = [
+  { kind: 'pre-chorus', pattern: /^(pre[\s-]?chorus|pc)\b/i },
+  { kind: 'chorus', pattern: /^(chorus|refrain|c)\b/i },
+  { kind: 'verse', pattern: /^(verse|v)\b/i },
+  { kind: 'bridge', pattern: /^(bridge|b)\b/i },
+  { kind: 'tag', pattern: /^(tag|vamp|t)\b/i },
+  { kind: 'intro', pattern: /^(intro|i)\b/i },
+  { kind: 'outro', pattern: /^(outro|ending|coda|e)\b/i },
+];
+
+// Short tags also appear glued to the number ("V1", "C2")
+const SHORT_TAG = /^(pc|v|c|b|t|i|e)(\d+)$/i;
+
+export function classifySection(name: string): SectionInfo {
+  const trimmed = name.trim();
+  const shortTag = SHORT_TAG.exec(trimmed);
+  const normalized = shortTag ? `${shortTag[1]} ${shortTag[2]}` : trimmed;
+
+  for (const { kind, pattern } of SECTION_PATTERNS) {
+    if (pattern.test(normalized)) {
+      const number = /(\d+)\s*$/.exec(normalized);
+      return { kind, number: number ? parseInt(number[1], 10) : undefined };
+    }
+  }
+
+  return { kind: 'other' };
+}
+
+/**
+ * Sections that carry lyric slides, in presentation order
+ */
+export function lyricSections(song: ExtractedLyrics): Array<{ section: LyricSection; lines: string[][] }> {
+  return song.sections
+    .map(section => ({
+      section,
+      lines: section.slides
+        .filter(slide => slide.isLyric)
+        .map(slide => slide.text.split('\n').map(line => line.trimEnd())),
+    }))
+    .filter(entry => entry.lines.length > 0);
+}
+
+/**
+ * Strip characters that aren't allowed in file names
+ */
+export function songFileName(title: string): string {
+  return title.replace(/[\\/:*?"<>|]+/g, '').replace(/\s+/g, ' ').trim() || 'Untitled';
+}
@@ -0,0 +1,30 @@
+/**
+ * Plain text and JSON exporters
+ */
+
+import { formatLyricsAsText } from '../lyrics-extractor';
+import { createTextExporter } from './registry';
+
+export const textExporter = createTextExporter({
+  id: 'text',
+  label: 'Plain Text',
+  description: 'Lyrics grouped by section, one song after another',
+  extension: 'txt',
+  mimeType: 'text/plain',
+  options: [],
+  render: (songs) => songs
+    .map(song => formatLyricsAsText(song))
+    .join('\n' + '-'.repeat(40) + '\n\n'),
+});
+
+export const jsonExporter = createTextExporter({
+  id: 'json',
+  label: 'JSON',
+  description: 'Structured lyrics data for further processing',
+  extension: 'json',
+  mimeType: 'application/json',
+  options: [
+    { key: 'pretty', label: 'Pretty print', type: 'boolean', default: true },
+  ],
+  render: (songs, options) => JSON.stringify(songs, null, options.pretty === false ? undefined : 2),
+});
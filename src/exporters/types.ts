@@ -0,0 +1,76 @@
+/**
+ * Exporter Types - Contract shared by every lyrics export format
+ */
+
+import type { ExtractedLyrics } from '../lyrics-extractor';
+
+export type ExportOptionType = 'boolean' | 'string' | 'number' | 'color' | 'select';
+
+export type ExportOptionValue = string | number | boolean;
+
+export type ExportOptionValues = Record<string, ExportOptionValue | undefined>;
+
+/**
+ * One configurable option of an export format. Front ends render these
+ * generically, so a new format needs no UI changes.
+ */
+export interface ExportOptionField {
+  key: string;
+  label: string;
+  type: ExportOptionType;
+  description?: string;
+  default?: ExportOptionValue;
+  /** Allowed values for 'select' fields */
+  choices?: Array<{ value: string; label: string }>;
+  min?: number;
+  max?: number;
+  /** Value is normally supplied from saved app settings rather than per export */
+  fromSettings?: boolean;
+}
+
+/**
+ * Serializable description of a format, as returned to the CLI, web and desktop UIs
+ */
+export interface ExportFormatDescriptor {
+  id: string;
+  label: string;
+  description: string;
+  extension: string;
+  mimeType: string;
+  /** True when the format can be printed to stdout instead of written to a file */
+  textual: boolean;
+  options: ExportOptionField[];
+}
+
+/**
+ * Layout decision for one lyric slide, for formats that lay out slides
+ */
+export interface SlideFitProgressEvent {
+  type: 'slide:fit';
+  song: string;
+  section: string;
+  action: 'fit' | 'shrink' | 'split';
+  fontSizes: number[];
+  overflow: boolean;
+}
+
+export type ExportProgressEvent = SlideFitProgressEvent;
+
+/**
+ * Non-serializable extras passed alongside the option values
+ */
+export interface ExportContext {
+  onProgress?: (event: ExportProgressEvent) => void;
+}
+
+export interface LyricsExporter extends Omit<ExportFormatDescriptor, 'textual'> {
+  /** Render to a string; only implemented by text-based formats */
+  render?(songs: ExtractedLyrics[], options: ExportOptionValues): string;
+  /** Write the export to disk and return the final file path */
+  write(
+    songs: ExtractedLyrics[],
+    outputPath: string,
+    options: ExportOptionValues,
+    context?: ExportContext
+  ): Promise<string>;
+}
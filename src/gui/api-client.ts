@@ -67,13 +67,27 @@ function handleAuthFailure(): never {
   throw new Error('Authentication failed');
 }
 
+/**
+ * Throw with the server's { error } message when there is one
+ */
+async function throwHttpError(res: Response): Promise<never> {
+  let message = `HTTP ${res.status}: ${res.statusText}`;
+  try {
+    const body = await res.json();
+    if (body?.error) message = body.error;
+  } catch {
+    // Not JSON — keep the status text
+  }
+  throw new Error(message);
+}
+
 async function get<T = any>(path: string): Promise<T> {
   const res = await fetch(path, {
     headers: jsonHeaders(),
     credentials: 'include', // Send session cookies
   });
   if (res.status === 401 || res.status === 403) handleAuthFailure();
-  if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
+  if (!res.ok) await throwHttpError(res);
   return res.json();
 }
 
@@ -85,7 +99,7 @@ async function post<T = any>(path: string, body?: any): Promise<T> {
     body: body !== undefined ? JSON.stringify(body) : undefined,
   });
   if (res.status === 401 || res.status === 403) handleAuthFailure();
-  if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
+  if (!res.ok) await throwHttpError(res);
   return res.json();
 }
 
@@ -97,7 +111,7 @@ async function put<T = any>(path: string, body?: any): Promise<T> {
     body: body !== undefined ? JSON.stringify(body) : undefined,
   });
   if (res.status === 401 || res.status === 403) handleAuthFailure();
-  if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
+  if (!res.ok) await throwHttpError(res);
   return res.json();
 }
 
@@ -108,7 +122,7 @@ async function del<T = any>(path: string): Promise<T> {
     credentials: 'include',
   });
   if (res.status === 401 || res.status === 403) handleAuthFailure();
-  if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
+  if (!res.ok) await throwHttpError(res);
   return res.json();
 }
 
@@ -199,6 +213,7 @@ function connectSSE(jobId: string): void {
         itemName: data.itemName,
         totalSongs: data.totalSongs,
         outputPath: data.outputPath,
+        format: data.format,
       };
 
       // When export completes, trigger browser download
@@ -261,7 +276,8 @@ const webApi = {
       libraryFilter: payload.libraryFilter,
       includeSongTitles: payload.includeSongTitles,
       styleOverrides: payload.styleOverrides,
-      logoPath: payload.logoPath,
+      format: payload.format,
+      formatOptions: payload.formatOptions,
     });
 
     // Auto-connect SSE for progress if a callback is registered
@@ -273,6 +289,14 @@ const webApi = {
     return { canceled: false, success: true, jobId };
   },
 
+  listExportFormats: () => get('/api/export/formats'),
+
+  listThemes: () => get('/api/themes'),
+
+  loadClassificationRules: () => get('/api/classification-rules'),
+  saveClassificationRules: (ruleSet: any) => put('/api/classification-rules', ruleSet),
+  resetClassificationRules: () => del('/api/classification-rules'),
+
   /**
    * Register a callback for export progress events.
    * Mirrors Electron's window.api.onExportProgress(callback) → unsubscribe.
@@ -319,6 +343,36 @@ const webApi = {
     });
   },
 
+  // PPTX master template — file input + upload to server
+  choosePptxTemplate: async () => {
+    return new Promise<{ canceled: boolean; filePath?: string }>((resolve, reject) => {
+      const input = document.createElement('input');
+      input.type = 'file';
+      input.accept = '.pptx,application/vnd.openxmlformats-officedocument.presentationml.presentation';
+      input.onchange = async () => {
+        if (input.files && input.files[0]) {
+          try {
+            const formData = new FormData();
+            formData.append('file', input.files[0]);
+            const res = await fetch('/api/pptx-template/upload', {
+              method: 'POST',
+              credentials: 'include',
+              body: formData,
+            });
+            if (!res.ok) throw new Error(`Upload failed: ${res.status}`);
+            const data = await res.json();
+            resolve({ canceled: false, filePath: data.filePath });
+          } catch (error) {
+            reject(error);
+          }
+        } else {
+          resolve({ canceled: true });
+        }
+      };
+      input.click();
+    });
+  },
+
   // Playlist template
   createPlaylistFromTemplate: (_config: any, templateId: string, playlistName: string) =>
     post('/api/service/create-playlist', { templateId, playlistName }),
@@ -351,13 +405,17 @@ const webApi = {
   // Library search
   searchPresentations: (_config: any, libraryIds: string[], query: string) =>
     get(`/api/libraries/${libraryIds.join(',')}/search?q=${encodeURIComponent(query)}`),
+  searchLyrics: (_config: any, libraryIds: string[], query: string) =>
+    get(`/api/libraries/${libraryIds.join(',')}/lyrics-search?q=${encodeURIComponent(query)}`),
+  indexLyrics: (_config: any, libraryIds: string[]) =>
+    post(`/api/libraries/${libraryIds.join(',')}/lyrics-index`, {}),
 
-  // Service Generator — PDF upload via file input + multipart
+  // Service Generator — service order upload (PDF, Word, text, Markdown) via file input + multipart
   choosePDF: async () => {
     return new Promise<{ canceled: boolean; filePath?: string }>((resolve) => {
       const input = document.createElement('input');
       input.type = 'file';
-      input.accept = '.pdf,application/pdf';
+      input.accept = '.pdf,application/pdf,.docx,.txt,.md';
       input.onchange = () => {
         if (input.files && input.files[0]) {
           (webApi as any)._pendingPDFFile = input.files[0];
@@ -370,15 +428,16 @@ const webApi = {
     });
   },
 
-  parsePDF: async (_filePath: string) => {
+  parsePDF: async (_filePath: string, grammarId?: string) => {
     const file = (webApi as any)._pendingPDFFile as File | undefined;
     if (!file) {
-      return { success: false, error: 'No PDF file selected' };
+      return { success: false, error: 'No service order file selected' };
     }
     delete (webApi as any)._pendingPDFFile;
 
     const formData = new FormData();
     formData.append('file', file);
+    if (grammarId) formData.append('grammar', grammarId);
 
     const res = await fetch('/api/service/parse-pdf', {
       method: 'POST',
@@ -389,6 +448,8 @@ const webApi = {
     return res.json();
   },
 
+  listServiceGrammars: () => get('/api/service/grammars'),
+
   matchSongs: (
     songItems: any[],
     _config: any,
@@ -411,6 +472,14 @@ const webApi = {
 
   focusPlaylistItem: (_config: any, playlistId: string, headerName: string) =>
     post('/api/service/focus-item', { playlistId, headerName }),
+
+  // Service Generator sessions
+  listServiceSessions: () => get('/api/service/sessions'),
+  getServiceSession: (id: string) =>
+    get(`/api/service/sessions/${encodeURIComponent(id)}`).catch(() => null),
+  saveServiceSession: (session: any) => post('/api/service/sessions', session),
+  deleteServiceSession: (id: string) =>
+    del(`/api/service/sessions/${encodeURIComponent(id)}`),
 };
 
 // ── window.api shim for web mode ──────────────────────────────────────
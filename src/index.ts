@@ -12,6 +12,9 @@ export type {
   PresentationInfo,
   PlaylistItem,
   LibraryInfo,
+  SongMetadata,
+  ArrangementInfo,
+  TextRun,
 } from './propresenter-client';
 
 export {
@@ -19,10 +22,110 @@ export {
   extractLyricsFromPlaylist,
   formatLyricsAsText,
   formatLyricsAsJSON,
+  formatSongCredits,
   getLyricsSummary,
+  resolveArrangement,
+  arrangeGroups,
 } from './lyrics-extractor';
 export type {
   LyricSlide,
   LyricSection,
   ExtractedLyrics,
+  ExtractOptions,
+  ArrangementRef,
 } from './lyrics-extractor';
+
+export {
+  readProFile,
+  readProPlaylist,
+  loadPresentationsFromPath,
+} from './services/pro-file-reader';
+export type { OfflinePlaylist } from './services/pro-file-reader';
+export { rtfToPlainText, rtfToRuns } from './utils/rtf';
+export { diffLyrics, formatLyricDiff, loadLyricsFile } from './services/lyric-diff';
+export type { LyricDiff, SectionDiff, SlideChange, SectionRename } from './services/lyric-diff';
+export { takeLibrarySnapshot, listSnapshotSongs, loadSnapshotVersion } from './services/snapshot-store';
+export type { SnapshotSong, SnapshotVersion, SnapshotRun } from './services/snapshot-store';
+export { buildLyricIndex, searchLyrics } from './services/lyric-search';
+export type { LyricSearchResult } from './services/lyric-search';
+export { findDuplicates, scanLibraryDuplicates } from './services/duplicate-finder';
+export type { DuplicateReport, DuplicateCluster } from './services/duplicate-finder';
+export { reflowLyrics } from './services/lyric-reflow';
+export type { ReflowMode, ReflowOptions } from './services/lyric-reflow';
+export { lintLyrics, lintSongs, loadLintDictionary, formatLintReport } from './services/lyric-linter';
+export type { LintRule, LintIssue, LintReport, LintOptions } from './services/lyric-linter';
+
+export {
+  registerExporter,
+  getExporter,
+  listExporters,
+  describeExportFormats,
+  resolveExportOptions,
+  createTextExporter,
+} from './exporters';
+export type {
+  LyricsExporter,
+  ExportFormatDescriptor,
+  ExportOptionField,
+  ExportOptionValues,
+} from './exporters';
+
+export {
+  classifySlide,
+  loadClassificationRules,
+  saveClassificationRules,
+  DEFAULT_CLASSIFICATION_RULES,
+} from './services/classification-rules';
+export type {
+  ClassificationRule,
+  ClassificationRuleSet,
+  ClassificationResult,
+} from './services/classification-rules';
+
+export { DEFAULT_PPTX_THEME } from './pptx-exporter';
+export type { PptxTheme, PptxTextStyle } from './pptx-exporter';
+export { listThemes, getTheme, saveTheme } from './services/theme-store';
+export type { ThemeSummary } from './services/theme-store';
+
+export { PDFParser } from './services/pdf-parser';
+export { readServiceOrderText, readServiceOrderLines, isServiceOrderFile, SERVICE_ORDER_EXTENSIONS } from './services/service-order-reader';
+export { readPdfLayout, layoutPages } from './services/pdf-layout';
+export type { PositionedText, PdfPageText } from './services/pdf-layout';
+export {
+  loadServiceLibraries,
+  matchServiceSongs,
+  matchVerseReferences,
+  serviceOrderItems,
+  selectedServiceItems,
+  insertServiceItems,
+  buildServicePlaylist,
+  formatServiceReview,
+} from './services/service-pipeline';
+export type { SongMatchResult, VerseMatchResult, ServicePlaylistItem } from './services/service-pipeline';
+export {
+  loadServiceSessions,
+  getServiceSession,
+  saveServiceSession,
+  deleteServiceSession,
+} from './services/service-session-store';
+export type { ServiceSession, ServiceSessionStep, ServiceSessionInput } from './services/service-session-store';
+export {
+  listServiceGrammars,
+  getServiceGrammar,
+  requireServiceGrammar,
+  validateServiceGrammar,
+  DEFAULT_SERVICE_GRAMMAR,
+} from './services/service-grammar';
+export type {
+  ServiceGrammar,
+  GrammarItemRule,
+  GrammarLineStyle,
+  GrammarPdfLayout,
+  ServiceGrammarSummary,
+} from './services/service-grammar';
+export type { ParsedService, ServiceSection, ServiceOrderLine } from './types/service-order';
+
+export { startSimulator, createSimulatorApp } from './simulator/server';
+export type { SimulatorOptions, RunningSimulator } from './simulator/server';
+export { loadFixtures, getDefaultFixtures } from './simulator/fixtures';
+export type { SimulatorFixtures } from './simulator/fixtures';
@@ -4,15 +4,30 @@
  * Transforms raw ProPresenter presentation data into normalized lyric JSON
  */
 
-import { PresentationInfo, GroupInfo, SlideInfo } from './propresenter-client';
+import { PresentationInfo, GroupInfo, SongMetadata, ArrangementInfo, TextRun } from './propresenter-client';
+import { classifySlide, loadClassificationRules, ClassificationRuleSet } from './services/classification-rules';
 
 export interface LyricSlide {
   index: number;
   text: string;
   section: string;
   isLyric: boolean;
+  /** Separate text elements, e.g. the original language and its translation */
+  elements?: string[];
+  notes?: string;
+  /** Slide label set in ProPresenter, e.g. "Last line" */
+  label?: string;
+  /** False for slides switched off in ProPresenter */
+  enabled?: boolean;
+  /** Formatted runs (italic responses, bold leader parts, colours) when the slide has any */
+  runs?: TextRun[];
 }
 
+/**
+ * Where a slide keeps its second language
+ */
+export type TranslationSource = 'none' | 'element' | 'notes';
+
 export interface LyricSection {
   name: string;
   slides: LyricSlide[];
@@ -25,79 +40,40 @@ export interface ExtractedLyrics {
   fullText: string;
   slideCount: number;
   lyricSlideCount: number;
+  metadata?: SongMetadata;
+  /** Name of the arrangement the sections follow, if any */
+  arrangement?: string;
 }
 
 /**
- * Heuristics for detecting if a slide contains lyrics vs other content
+ * Arrangement to play, as recorded on a playlist item
  */
-function isLikelyLyricSlide(slide: SlideInfo, groupName: string): boolean {
-  const text = slide.text.trim();
-
-  // Empty slides are not lyrics
-  if (!text) {
-    return false;
-  }
-
-  // Very short text (1-2 chars) is likely a placeholder
-  if (text.length < 3) {
-    return false;
-  }
-
-  // Check group name for common lyric section names
-  const lyricSectionPatterns = [
-    /verse/i,
-    /chorus/i,
-    /bridge/i,
-    /pre-?chorus/i,
-    /tag/i,
-    /outro/i,
-    /intro/i,
-    /hook/i,
-    /refrain/i,
-    /coda/i,
-    /vamp/i,
-    /ending/i,
-    /instrumental/i, // May have no text but is a song section
-  ];
-
-  const isLyricSection = lyricSectionPatterns.some(pattern => pattern.test(groupName));
-
-  // Check for scripture reference patterns (not lyrics)
-  const scripturePattern = /^\d*\s*[A-Za-z]+\s+\d+:\d+/;
-  if (scripturePattern.test(text)) {
-    return false;
-  }
-
-  // Check for common announcement patterns
-  const announcementPatterns = [
-    /^welcome/i,
-    /^announcements?/i,
-    /^upcoming events?/i,
-    /^this week/i,
-    /^next week/i,
-    /www\./i,
-    /https?:\/\//i,
-  ];
-  if (announcementPatterns.some(p => p.test(text))) {
-    return false;
-  }
+export interface ArrangementRef {
+  uuid?: string;
+  name?: string;
+}
 
-  // If in a lyric-named section, it's likely lyrics
-  if (isLyricSection) {
-    return true;
-  }
+export interface ExtractOptions {
+  /** Falls back to the presentation's selected arrangement, then to group order */
+  arrangement?: ArrangementRef;
+  /** Lyric classification rules; defaults to the saved rule set */
+  rules?: ClassificationRuleSet;
+}
 
-  // Heuristic: lyrics tend to have multiple short lines
-  const lines = text.split('\n').filter(l => l.trim());
-  const avgLineLength = lines.reduce((sum, l) => sum + l.length, 0) / lines.length;
+// Copyright slides usually carry the licence number, e.g. "CCLI Song # 7033123"
+const CCLI_NUMBER_PATTERN = /CCLI\s*(?:Song)?\s*(?:#|No\.?|Number)?\s*:?\s*(\d{4,8})/i;
 
-  // Lyrics typically have short lines (< 60 chars average)
-  if (lines.length >= 2 && avgLineLength < 60) {
-    return true;
+/**
+ * Find a CCLI song number in the presentation's slide text
+ */
+function findCcliNumber(groups: GroupInfo[]): string | undefined {
+  for (const group of groups) {
+    for (const slide of group.slides) {
+      const match = CCLI_NUMBER_PATTERN.exec(slide.text);
+      if (match) return match[1];
+    }
   }
-
-  // Default: assume it's lyrics if it has text
-  return true;
+  return undefined;
 }
 
 /**
@@ -111,16 +87,57 @@ function normalizeText(text: string): string {
     .trim();
 }
 
+/**
+ * Find the arrangement ProPresenter would play: the playlist item's choice,
+ * else the one selected in the presentation.
+ */
+export function resolveArrangement(
+  presentation: PresentationInfo,
+  ref?: ArrangementRef
+): ArrangementInfo | undefined {
+  const arrangements = presentation.arrangements || [];
+  if (arrangements.length === 0) return undefined;
+
+  const byUuid = (uuid?: string) => uuid ? arrangements.find(a => a.uuid === uuid) : undefined;
+  const byName = (name?: string) => name
+    ? arrangements.find(a => a.name.trim().toLowerCase() === name.trim().toLowerCase())
+    : undefined;
+
+  return byUuid(ref?.uuid)
+    ?? byName(ref?.name)
+    ?? byUuid(presentation.selectedArrangementUuid);
+}
+
+/**
+ * Groups in play order. Arrangements may repeat groups; references to groups
+ * that no longer exist are dropped.
+ */
+export function arrangeGroups(presentation: PresentationInfo, arrangement?: ArrangementInfo): GroupInfo[] {
+  if (!arrangement) return presentation.groups;
+
+  const findGroup = (ref: string) =>
+    presentation.groups.find(group => group.uuid === ref)
+    ?? presentation.groups.find(group => group.name.toLowerCase() === ref.toLowerCase());
+
+  const ordered = arrangement.groups
+    .map(findGroup)
+    .filter((group): group is GroupInfo => !!group);
+
+  return ordered.length > 0 ? ordered : presentation.groups;
+}
+
 /**
  * Extract lyrics from a presentation
  */
-export function extractLyrics(presentation: PresentationInfo): ExtractedLyrics {
+export function extractLyrics(presentation: PresentationInfo, options: ExtractOptions = {}): ExtractedLyrics {
   const sections: LyricSection[] = [];
   let totalSlides = 0;
   let lyricSlides = 0;
   const allLyricTexts: string[] = [];
+  const arrangement = resolveArrangement(presentation, options.arrangement);
+  const rules = options.rules ?? loadClassificationRules();
 
-  for (const group of presentation.groups) {
+  for (const group of arrangeGroups(presentation, arrangement)) {
     const section: LyricSection = {
       name: group.name,
       slides: [],
@@ -128,24 +145,35 @@ export function extractLyrics(presentation: PresentationInfo): ExtractedLyrics {
 
     for (const slide of group.slides) {
       totalSlides++;
-      const isLyric = isLikelyLyricSlide(slide, group.name);
+      const { isLyric } = classifySlide(slide, group.name, rules);
 
       if (isLyric) {
         lyricSlides++;
         allLyricTexts.push(normalizeText(slide.text));
       }
 
+      const notes = normalizeText(slide.notes || '');
       section.slides.push({
         index: slide.index,
         text: normalizeText(slide.text),
         section: group.name,
         isLyric,
+        elements: slide.elements?.map(normalizeText),
+        notes: notes || undefined,
+        label: slide.label?.trim() || undefined,
+        enabled: slide.enabled,
+        runs: slide.runs?.map(run => ({ ...run, text: run.text.replace(/\r\n?/g, '\n') })),
       });
     }
 
     sections.push(section);
   }
 
+  const ccliNumber = presentation.metadata?.ccliNumber || findCcliNumber(presentation.groups);
+  const metadata: SongMetadata | undefined = (presentation.metadata || ccliNumber)
+    ? { ...presentation.metadata, ccliNumber }
+    : undefined;
+
   return {
     title: presentation.name,
     uuid: presentation.uuid,
@@ -153,16 +181,67 @@ export function extractLyrics(presentation: PresentationInfo): ExtractedLyrics {
     fullText: allLyricTexts.join('\n\n'),
     slideCount: totalSlides,
     lyricSlideCount: lyricSlides,
+    metadata,
+    arrangement: arrangement?.name,
   };
 }
 
+/**
+ * Split a slide into its primary text and translation. Slides without a
+ * second language return an empty translation.
+ */
+export function splitTranslation(
+  slide: LyricSlide,
+  source: TranslationSource
+): { primary: string; translation: string } {
+  if (source === 'element' && slide.elements && slide.elements.length > 1) {
+    return { primary: slide.elements[0], translation: slide.elements.slice(1).join('\n') };
+  }
+  if (source === 'notes' && slide.notes) {
+    return { primary: slide.text, translation: slide.notes };
+  }
+  return { primary: slide.text, translation: '' };
+}
+
+/**
+ * The last non-empty lyric slide of a song, where a copyright footer goes
+ */
+export function lastLyricSlide(lyrics: ExtractedLyrics): LyricSlide | undefined {
+  const slides = lyrics.sections
+    .flatMap(section => section.slides)
+    .filter(slide => slide.isLyric && slide.text.trim() !== '');
+  return slides[slides.length - 1];
+}
+
+/**
+ * One-line song credits for a copyright notice, in the order CCLI asks for:
+ * title, writers, copyright, song number, then the church's licence number.
+ * Null when the song has no credits to show.
+ */
+export function formatSongCredits(lyrics: ExtractedLyrics, ccliLicence?: string): string | null {
+  const metadata = lyrics.metadata;
+  if (!metadata?.author && !metadata?.publisher && !metadata?.copyrightYear && !metadata?.ccliNumber) {
+    return null;
+  }
+
+  const copyright = [metadata.copyrightYear, metadata.publisher].filter(Boolean).join(' ');
+  const publicDomain = /public domain/i.test(metadata.publisher || '');
+  return [
+    `"${lyrics.title}"`,
+    metadata.author,
+    copyright && !publicDomain ? `© ${copyright}` : copyright,
+    metadata.ccliNumber ? `CCLI Song # ${metadata.ccliNumber}` : '',
+    ccliLicence?.trim() ? `CCLI Licence # ${ccliLicence.trim()}` : '',
+  ].filter(Boolean).join(' · ');
+}
+
 /**
  * Extract lyrics from multiple presentations (e.g., a playlist)
  */
 export function extractLyricsFromPlaylist(
   presentations: PresentationInfo[]
 ): ExtractedLyrics[] {
-  return presentations.map(extractLyrics);
+  return presentations.map(presentation => extractLyrics(presentation));
 }
 
 /**
@@ -172,7 +251,9 @@ export function formatLyricsAsText(lyrics: ExtractedLyrics): string {
   const lines: string[] = [];
 
   lines.push(`=== ${lyrics.title} ===`);
-  lines.push(`(${lyrics.lyricSlideCount} lyric slides)`);
+  lines.push(lyrics.arrangement
+    ? `(${lyrics.lyricSlideCount} lyric slides, arrangement: ${lyrics.arrangement})`
+    : `(${lyrics.lyricSlideCount} lyric slides)`);
   lines.push('');
 
   for (const section of lyrics.sections) {
@@ -7,7 +7,11 @@
 
 import PptxGenJS from 'pptxgenjs';
 import * as fs from 'fs';
-import { ExtractedLyrics } from './lyrics-extractor';
+import { ExtractedLyrics, TranslationSource, formatSongCredits, lastLyricSlide, splitTranslation } from './lyrics-extractor';
+import { exportWithPptxTemplate } from './services/pptx-template';
+import { fitText, largestFittingSize, divideLines, TextFitAction, TextFitResult } from './services/text-fit';
+import { runsForText } from './utils/text-runs';
+import type { TextRun } from './propresenter-client';
 
 // Re-export for convenience
 export type { ExtractedLyrics as LyricsData } from './lyrics-extractor';
@@ -19,6 +23,34 @@ export interface PptxTextStyle {
   titleFontSize: number;
   bold: boolean;
   italic: boolean;
+  /** Where the second language comes from; 'none' keeps the single-language layout */
+  translationSource: TranslationSource;
+  translationColor: string;
+  translationFontSize: number;
+  translationItalic: boolean;
+  labelDisplay: LabelDisplay;
+  /** Shrink long slides within the size bounds, then split them across slides */
+  autoFit: boolean;
+  minFontSize: number;
+  maxFontSize: number;
+}
+
+/**
+ * Where slide labels appear: nowhere, in the speaker notes, or as a caption above the lyrics
+ */
+export type LabelDisplay = 'none' | 'notes' | 'caption';
+
+const TRANSLATION_SOURCES: TranslationSource[] = ['none', 'element', 'notes'];
+const LABEL_DISPLAYS: LabelDisplay[] = ['none', 'notes', 'caption'];
+
+function envTranslationSource(): TranslationSource {
+  const value = (process.env.PPTX_TRANSLATION_SOURCE || 'none').toLowerCase() as TranslationSource;
+  return TRANSLATION_SOURCES.includes(value) ? value : 'none';
+}
+
+function envLabelDisplay(): LabelDisplay {
+  const value = (process.env.PPTX_LABEL_DISPLAY || 'notes').toLowerCase() as LabelDisplay;
+  return LABEL_DISPLAYS.includes(value) ? value : 'notes';
 }
 
 export const DEFAULT_PPTX_TEXT_STYLE: PptxTextStyle = {
@@ -28,26 +60,169 @@ export const DEFAULT_PPTX_TEXT_STYLE: PptxTextStyle = {
   titleFontSize: parseInt(process.env.PPTX_TITLE_FONT_SIZE || '54', 10),
   bold: process.env.PPTX_FONT_BOLD !== 'false',
   italic: process.env.PPTX_FONT_ITALIC !== 'false',
+  translationSource: envTranslationSource(),
+  translationColor: process.env.PPTX_TRANSLATION_COLOR || '7a8f96',
+  translationFontSize: parseInt(process.env.PPTX_TRANSLATION_FONT_SIZE || '32', 10),
+  translationItalic: process.env.PPTX_TRANSLATION_ITALIC !== 'false',
+  labelDisplay: envLabelDisplay(),
+  autoFit: process.env.PPTX_AUTO_FIT !== 'false',
+  minFontSize: parseInt(process.env.PPTX_MIN_FONT_SIZE || '28', 10),
+  maxFontSize: parseInt(process.env.PPTX_MAX_FONT_SIZE || '72', 10),
 };
 
-const LAYOUT = {
-  slideWidth: 13.333,
-  slideHeight: 7.5,
-  textX: 0.5,
-  textY: 2.0,
-  textW: 12.333,
-  textH: 3.5,
-  logoX: 6.0,
-  logoY: 6.2,
-  logoW: 1.2,
-  logoH: 1.0,
+/** The text style fields the desktop and web apps keep as settings */
+export const TEXT_STYLE_SETTINGS = ['textColor', 'fontFace', 'fontSize', 'titleFontSize', 'bold', 'italic'] as const;
+
+/**
+ * Text style from the apps. null marks a field the user has not set (or
+ * cleared by picking a theme), so the theme's value applies.
+ */
+export type PptxTextStyleOverrides = { [K in keyof PptxTextStyle]?: PptxTextStyle[K] | null };
+
+/**
+ * The fields the user set, whatever their value; unset ones are dropped
+ */
+export function explicitTextStyle(style: PptxTextStyleOverrides): Partial<PptxTextStyle> {
+  return Object.fromEntries(
+    Object.entries(style).filter(([, value]) => value !== undefined && value !== null)
+  ) as Partial<PptxTextStyle>;
+}
+
+/**
+ * How one lyric slide was laid out, reported while exporting
+ */
+export interface SlideFitEvent {
+  song: string;
+  section: string;
+  action: TextFitAction;
+  /** Font size of each PPTX slide produced from the lyric slide */
+  fontSizes: number[];
+  /** True if some text still overflows at the minimum size */
+  overflow: boolean;
+}
+
+export interface PptxBox {
+  x: number;
+  y: number;
+  w: number;
+  h: number;
+}
+
+export interface PptxBackground {
+  /** Hex color without '#' */
+  color: string;
+  /** Optional full-slide image, drawn over the color */
+  imagePath?: string | null;
+}
+
+/**
+ * Slide design for PPTX export. Geometry is in inches on a 13.333 x 7.5 slide.
+ */
+export interface PptxTheme {
+  id: string;
+  name: string;
+  description?: string;
+  background: PptxBackground;
+  text: PptxBox & { align: 'left' | 'center' | 'right'; valign: 'top' | 'middle' | 'bottom' };
+  logo: PptxBox & { show: boolean };
+  title: PptxBox & { background?: PptxBackground; showLogo: boolean };
+  /** Where the copyright footer goes on a song's last slide */
+  footer: PptxBox & { align: 'left' | 'center' | 'right' };
+  /** Font settings the theme starts from; explicit style overrides win */
+  fonts: Partial<Pick<PptxTextStyle, 'fontFace' | 'fontSize' | 'titleFontSize' | 'textColor' | 'bold' | 'italic'>>;
+}
+
+export const DEFAULT_PPTX_THEME: PptxTheme = {
+  id: 'classic',
+  name: 'Classic',
+  description: 'White slides, centred lyrics and the logo at the bottom',
+  background: { color: 'FFFFFF' },
+  text: { x: 0.5, y: 2.0, w: 12.333, h: 3.5, align: 'center', valign: 'middle' },
+  logo: { show: true, x: 6.0, y: 6.2, w: 1.2, h: 1.0 },
+  title: { x: 0.5, y: 3.0, w: 12.333, h: 1.5, showLogo: true },
+  footer: { x: 0.3, y: 6.95, w: 5.5, h: 0.45, align: 'left' },
+  fonts: {},
 };
 
+// Share of the text box given to the primary language in the dual-language layout
+const PRIMARY_SHARE = 0.6;
+
+// Label captions sit just above the lyrics text box
+const CAPTION_HEIGHT = 0.45;
+const CAPTION_FONT_SIZE = 18;
+
+const FOOTER_FONT_SIZE = 11;
+
 export interface ExportOptions {
   outputPath: string;
   logoPath?: string;
   includeSongTitles?: boolean;
   styleOverrides?: Partial<PptxTextStyle>;
+  theme?: PptxTheme;
+  /**
+   * Reference .pptx whose slide master, layouts, background art and fonts
+   * are reused. Replaces the theme and text style, except for translations,
+   * labels and the fit settings.
+   */
+  templatePath?: string;
+  /** Layout names in the template for lyric and title slides */
+  templateLyricLayout?: string;
+  templateTitleLayout?: string;
+  /** Called with the fit decision for every lyric slide */
+  onSlideFit?: (event: SlideFitEvent) => void;
+  /** Song credits (see formatSongCredits) on the last slide of each song */
+  copyrightFooter?: boolean;
+  /** The church's CCLI licence number, added to the footer */
+  ccliLicence?: string;
+}
+
+/**
+ * Read an image as base64. pptxgenjs image embedding crashes inside
+ * pkg-bundled executables due to dynamic fs imports, so images are skipped
+ * there. Electron and web server (plain Node.js) can embed them safely.
+ */
+function readImageBase64(filePath: string | null | undefined, what: string): string | null {
+  const isPkgBundled = !!(process as any).pkg;
+  try {
+    if (!isPkgBundled && filePath && fs.existsSync(filePath)) {
+      return fs.readFileSync(filePath).toString('base64');
+    }
+  } catch (error) {
+    console.log(`  (${what} skipped — file not readable)`);
+  }
+  return null;
+}
+
+function imageMime(filePath: string | null | undefined): string {
+  return /\.jpe?g$/i.test(filePath || '') ? 'image/jpeg' : 'image/png';
+}
+
+/**
+ * Text for addText: the plain string, or PowerPoint text runs when the slide
+ * carries formatting that still lines up with this piece of text. Run
+ * attributes override the text box style only where they are set.
+ */
+function slideText(text: string, runs: TextRun[] | undefined): string | PptxGenJS.TextProps[] {
+  const lines = runs ? runsForText(runs, text) : null;
+  if (!lines) {
+    return text;
+  }
+
+  const props: PptxGenJS.TextProps[] = [];
+  lines.forEach((line, lineIndex) => {
+    const parts: TextRun[] = line.length > 0 ? line : [{ text: '' }];
+    parts.forEach((run, runIndex) => {
+      const options: PptxGenJS.TextPropsOptions = {
+        breakLine: runIndex === parts.length - 1 && lineIndex < lines.length - 1,
+      };
+      if (run.bold !== undefined) options.bold = run.bold;
+      if (run.italic !== undefined) options.italic = run.italic;
+      if (run.underline) options.underline = { style: 'sng' };
+      if (run.color) options.color = run.color;
+      props.push({ text: run.text, options });
+    });
+  });
+  return props;
 }
 
 /**
@@ -57,66 +232,95 @@ export async function exportToPowerPoint(
   songs: ExtractedLyrics[],
   options: ExportOptions
 ): Promise<string> {
-  const pptx = new PptxGenJS();
+  const theme = options.theme ?? DEFAULT_PPTX_THEME;
 
   const textStyle: PptxTextStyle = {
     ...DEFAULT_PPTX_TEXT_STYLE,
+    ...theme.fonts,
     ...options.styleOverrides,
   };
 
+  if (options.templatePath) {
+    return exportWithPptxTemplate(songs, {
+      templatePath: options.templatePath,
+      outputPath: options.outputPath,
+      includeSongTitles: options.includeSongTitles,
+      lyricLayout: options.templateLyricLayout,
+      titleLayout: options.templateTitleLayout,
+      translationSource: textStyle.translationSource,
+      translationColor: textStyle.translationColor,
+      translationFontSize: textStyle.translationFontSize,
+      translationItalic: textStyle.translationItalic,
+      copyrightFooter: options.copyrightFooter,
+      ccliLicence: options.ccliLicence,
+      labelDisplay: textStyle.labelDisplay,
+      autoFit: textStyle.autoFit,
+      fontSize: textStyle.fontSize,
+      minFontSize: textStyle.minFontSize,
+      maxFontSize: textStyle.maxFontSize,
+      onSlideFit: options.onSlideFit,
+    });
+  }
+
+  const pptx = new PptxGenJS();
+
   // Set presentation properties
   pptx.author = 'ProPresenter Words';
   pptx.title = 'Song Lyrics';
   pptx.subject = 'Worship Song Lyrics';
   pptx.layout = 'LAYOUT_WIDE';
 
-  // Logo embedding: pptxgenjs addImage crashes inside pkg-bundled executables
-  // due to dynamic fs imports. Detect pkg via process.pkg and skip in that case.
-  // Electron and web server (plain Node.js) can embed logos safely.
-  const isPkgBundled = !!(process as any).pkg;
-  let logoBase64: string | null = null;
-  try {
-    if (!isPkgBundled && options.logoPath && fs.existsSync(options.logoPath)) {
-      const logoBuffer = fs.readFileSync(options.logoPath);
-      logoBase64 = logoBuffer.toString('base64');
-    }
-  } catch (error) {
-    // Logo loading failed - skip it
-    console.log('  (logo skipped — file not readable)');
-    logoBase64 = null;
-  }
+  const logoBase64 = theme.logo.show ? readImageBase64(options.logoPath, 'logo') : null;
+  const logoData = logoBase64 ? `${imageMime(options.logoPath)};base64,${logoBase64}` : null;
+
+  const backgroundFor = (background: PptxBackground): PptxGenJS.BackgroundProps => {
+    const image = readImageBase64(background.imagePath, 'background image');
+    return image
+      ? { data: `${imageMime(background.imagePath)};base64,${image}` }
+      : { color: background.color };
+  };
+  const slideBackground = backgroundFor(theme.background);
+  const titleBackground = theme.title.background ? backgroundFor(theme.title.background) : slideBackground;
+
+  const addLogo = (slide: PptxGenJS.Slide) => {
+    if (!logoData) return;
+    slide.addImage({
+      data: logoData,
+      x: theme.logo.x,
+      y: theme.logo.y,
+      w: theme.logo.w,
+      h: theme.logo.h,
+    });
+  };
 
   for (const song of songs) {
     // Optionally add a title slide for each song
     if (options.includeSongTitles) {
       const titleSlide = pptx.addSlide();
-      titleSlide.background = { color: 'FFFFFF' };
+      titleSlide.background = titleBackground;
 
       titleSlide.addText(song.title, {
-        x: LAYOUT.textX,
-        y: 3.0,
-        w: LAYOUT.textW,
-        h: 1.5,
+        x: theme.title.x,
+        y: theme.title.y,
+        w: theme.title.w,
+        h: theme.title.h,
         fontSize: textStyle.titleFontSize,
         fontFace: textStyle.fontFace,
         color: textStyle.textColor,
         bold: textStyle.bold,
         italic: textStyle.italic,
-        align: 'center',
+        align: theme.text.align,
         valign: 'middle',
       });
 
-      if (logoBase64) {
-        titleSlide.addImage({
-          data: `image/png;base64,${logoBase64}`,
-          x: LAYOUT.logoX,
-          y: LAYOUT.logoY,
-          w: LAYOUT.logoW,
-          h: LAYOUT.logoH,
-        });
+      if (theme.title.showLogo) {
+        addLogo(titleSlide);
       }
     }
 
+    const credits = options.copyrightFooter ? formatSongCredits(song, options.ccliLicence) : null;
+    const lastSlide = lastLyricSlide(song);
+
     // Add each slide from the song
     for (const section of song.sections) {
       for (const slideData of section.slides) {
@@ -125,38 +329,122 @@ export async function exportToPowerPoint(
           continue;
         }
 
-        const slide = pptx.addSlide();
-        slide.background = { color: 'FFFFFF' };
-
-        // Add the lyrics text (preserves line breaks from ProPresenter)
-        slide.addText(slideData.text, {
-          x: LAYOUT.textX,
-          y: LAYOUT.textY,
-          w: LAYOUT.textW,
-          h: LAYOUT.textH,
-          fontSize: textStyle.fontSize,
-          fontFace: textStyle.fontFace,
-          color: textStyle.textColor,
-          bold: textStyle.bold,
-          italic: textStyle.italic,
-          align: 'center',
-          valign: 'middle',
+        const { primary, translation } = splitTranslation(slideData, textStyle.translationSource);
+        const primaryH = translation ? theme.text.h * PRIMARY_SHARE : theme.text.h;
+        const translationH = theme.text.h - primaryH;
+
+        // Shrink, then split, long slides so they stay inside the text box
+        const fit: TextFitResult = textStyle.autoFit
+          ? fitText(primary, { w: theme.text.w, h: primaryH }, {
+            fontSize: textStyle.fontSize,
+            minFontSize: textStyle.minFontSize,
+            maxFontSize: textStyle.maxFontSize,
+            bold: textStyle.bold,
+          })
+          : { action: 'fit', chunks: [{ text: primary, fontSize: textStyle.fontSize, fits: true }] };
+        const translations = translation ? divideLines(translation, fit.chunks.length) : [];
+
+        options.onSlideFit?.({
+          song: song.title,
+          section: section.name,
+          action: fit.action,
+          fontSizes: fit.chunks.map(chunk => chunk.fontSize),
+          overflow: fit.chunks.some(chunk => !chunk.fits),
         });
 
-        if (logoBase64) {
-          slide.addImage({
-            data: `image/png;base64,${logoBase64}`,
-            x: LAYOUT.logoX,
-            y: LAYOUT.logoY,
-            w: LAYOUT.logoW,
-            h: LAYOUT.logoH,
+        fit.chunks.forEach((chunk, index) => {
+          const slide = pptx.addSlide();
+          slide.background = slideBackground;
+          const translationText = translations[index] || '';
+
+          // Add the lyrics text (preserves line breaks from ProPresenter)
+          slide.addText(slideText(chunk.text, slideData.runs), {
+            x: theme.text.x,
+            y: theme.text.y,
+            w: theme.text.w,
+            h: primaryH,
+            fontSize: chunk.fontSize,
+            fontFace: textStyle.fontFace,
+            color: textStyle.textColor,
+            bold: textStyle.bold,
+            italic: textStyle.italic,
+            align: theme.text.align,
+            valign: translation ? 'bottom' : theme.text.valign,
           });
-        }
 
-        // Add section name as notes (useful for presenter)
-        if (section.name) {
-          slide.addNotes(`Section: ${section.name}`);
-        }
+          // Dual-language layout: translation below in a smaller, lighter style
+          if (translationText) {
+            const translationSize = textStyle.autoFit
+              ? largestFittingSize(translationText, { w: theme.text.w, h: translationH }, {
+                fontSize: textStyle.translationFontSize,
+                minFontSize: Math.min(textStyle.minFontSize, textStyle.translationFontSize),
+                maxFontSize: textStyle.translationFontSize,
+              }) ?? Math.min(textStyle.minFontSize, textStyle.translationFontSize)
+              : textStyle.translationFontSize;
+
+            slide.addText(slideText(translationText, slideData.runs), {
+              x: theme.text.x,
+              y: theme.text.y + primaryH,
+              w: theme.text.w,
+              h: translationH,
+              fontSize: translationSize,
+              fontFace: textStyle.fontFace,
+              color: textStyle.translationColor,
+              bold: false,
+              italic: textStyle.translationItalic,
+              align: theme.text.align,
+              valign: 'top',
+            });
+          }
+
+          if (slideData.label && textStyle.labelDisplay === 'caption') {
+            slide.addText(slideData.label, {
+              x: theme.text.x,
+              y: Math.max(0, theme.text.y - CAPTION_HEIGHT),
+              w: theme.text.w,
+              h: CAPTION_HEIGHT,
+              fontSize: CAPTION_FONT_SIZE,
+              fontFace: textStyle.fontFace,
+              color: textStyle.translationColor,
+              bold: false,
+              italic: true,
+              align: theme.text.align,
+              valign: 'bottom',
+            });
+          }
+
+          addLogo(slide);
+
+          if (credits && slideData === lastSlide && index === fit.chunks.length - 1) {
+            slide.addText(credits, {
+              x: theme.footer.x,
+              y: theme.footer.y,
+              w: theme.footer.w,
+              h: theme.footer.h,
+              fontSize: FOOTER_FONT_SIZE,
+              fontFace: textStyle.fontFace,
+              color: textStyle.translationColor,
+              bold: false,
+              italic: false,
+              align: theme.footer.align,
+              valign: 'middle',
+              fit: 'shrink',
+            });
+          }
+
+          // Add section name (and label) as notes (useful for presenter)
+          const notes: string[] = [];
+          if (section.name) {
+            const part = fit.chunks.length > 1 ? ` (${index + 1}/${fit.chunks.length})` : '';
+            notes.push(`Section: ${section.name}${part}`);
+          }
+          if (slideData.label && textStyle.labelDisplay === 'notes') {
+            notes.push(`Label: ${slideData.label}`);
+          }
+          if (notes.length > 0) {
+            slide.addNotes(notes.join('\n'));
+          }
+        });
       }
     }
   }
@@ -13,26 +13,74 @@ import {
   StatusUpdateJSON,
 } from 'renewedvision-propresenter';
 import { EventEmitter } from 'events';
+import { rtfBlobsToRuns } from './utils/rtf';
 
 export interface ConnectionConfig {
   host: string;
   port: number;
 }
 
+/**
+ * A stretch of slide text with its own formatting. Attributes are only set
+ * where they differ from the text element's base style; '\n' separates lines.
+ */
+export interface TextRun {
+  text: string;
+  bold?: boolean;
+  italic?: boolean;
+  underline?: boolean;
+  /** Hex color without '#' */
+  color?: string;
+}
+
 export interface SlideInfo {
   index: number;
   text: string;
+  /** Text of each text element, when the slide has more than one (e.g. a translation) */
+  elements?: string[];
+  /** Formatted runs covering text, only when part of the slide is formatted */
+  runs?: TextRun[];
   notes: string;
   label: string;
   enabled: boolean;
 }
 
 export interface GroupInfo {
+  uuid?: string;
   name: string;
   color: string;
   slides: SlideInfo[];
 }
 
+export interface ArrangementInfo {
+  uuid: string;
+  name: string;
+  /** Group UUIDs, or group names where the source has no UUIDs, in play order */
+  groups: string[];
+}
+
+/**
+ * Song credits from the presentation's CCLI details
+ */
+export interface SongMetadata {
+  ccliNumber?: string;
+  /** Writers, e.g. "John Newton" */
+  author?: string;
+  /** Artist or recording credits */
+  artist?: string;
+  publisher?: string;
+  copyrightYear?: number;
+  album?: string;
+}
+
+/**
+ * Drop empty fields; undefined when nothing is left
+ */
+export function compactMetadata(metadata: SongMetadata): SongMetadata | undefined {
+  const entries = Object.entries(metadata).filter(([, value]) => value !== undefined && value !== '');
+  return entries.length > 0 ? Object.fromEntries(entries) as SongMetadata : undefined;
+}
+
 export interface PresentationInfo {
   uuid: string;
   name: string;
@@ -40,6 +88,10 @@ export interface PresentationInfo {
   groups: GroupInfo[];
   hasTimeline: boolean;
   destination: string;
+  metadata?: SongMetadata;
+  arrangements?: ArrangementInfo[];
+  /** Arrangement selected in the presentation itself, used when a playlist item names none */
+  selectedArrangementUuid?: string;
 }
 
 export interface PlaylistItem {
@@ -48,6 +100,8 @@ export interface PlaylistItem {
   type: string;
   isHeader: boolean;
   presentationUuid?: string;  // UUID of the actual presentation content
+  arrangementUuid?: string;   // Arrangement chosen for this playlist item
+  arrangementName?: string;
   children?: PlaylistItem[];
 }
 
@@ -56,6 +110,16 @@ export interface LibraryInfo {
   name: string;
 }
 
+/**
+ * RTF from an API response: either the raw document or base64 of it
+ */
+function decodeRtf(value: unknown): string | null {
+  if (typeof value !== 'string' || !value) return null;
+  if (value.trimStart().startsWith('{\\rtf')) return value;
+  const decoded = Buffer.from(value, 'base64').toString('utf-8');
+  return decoded.trimStart().startsWith('{\\rtf') ? decoded : null;
+}
+
 export class ProPresenterClient extends EventEmitter {
   private client: ProPresenter;
   private connected: boolean = false;
@@ -156,6 +220,8 @@ export class ProPresenterClient extends EventEmitter {
       type: item.type || item.field_type || 'unknown',
       isHeader: item.type === 'header' || item.is_header || false,
       presentationUuid: item.presentation_info?.presentation_uuid,
+      arrangementUuid: item.presentation_info?.arrangement_uuid || undefined,
+      arrangementName: item.presentation_info?.arrangement_name || undefined,
       children: item.items || item.children ? this.parsePlaylistItems(item.items || item.children) : undefined,
     }));
   }
@@ -238,11 +304,72 @@ export class ProPresenterClient extends EventEmitter {
       hasTimeline: presentation.has_timeline || false,
       destination: presentation.destination || 'presentation',
       groups: this.parseGroups(presentation.cue_groups || presentation.groups || []),
+      metadata: this.parseMetadata(presentation),
+      arrangements: this.parseArrangements(presentation.arrangements),
+      selectedArrangementUuid: this.refUuid(presentation.current_arrangement ?? presentation.selected_arrangement),
+    };
+  }
+
+  /**
+   * Arrangement and group references arrive as plain strings or id objects
+   */
+  private refUuid(ref: any): string | undefined {
+    if (!ref) return undefined;
+    if (typeof ref === 'string') return ref;
+    return ref.uuid || ref.id?.uuid || ref.name || ref.id?.name || undefined;
+  }
+
+  /**
+   * Arrangements as sent with a presentation. These field names have not been
+   * checked against a captured ProPresenter 7 /v1 response (only the simulator
+   * sends them), so any other shape is ignored and lyrics keep library order.
+   */
+  private parseArrangements(arrangements: unknown): ArrangementInfo[] | undefined {
+    if (!Array.isArray(arrangements) || arrangements.length === 0) {
+      return undefined;
+    }
+    const parsed = arrangements
+      .filter((arrangement: any) => arrangement && typeof arrangement === 'object')
+      .map((arrangement: any) => {
+        const groups = arrangement.groups ?? arrangement.group_identifiers;
+        return {
+          uuid: arrangement.id?.uuid || arrangement.uuid || '',
+          name: arrangement.id?.name || arrangement.name || 'Unnamed Arrangement',
+          groups: (Array.isArray(groups) ? groups : [])
+            .map((group: any) => this.refUuid(group))
+            .filter((ref: string | undefined): ref is string => !!ref),
+        };
+      });
+    return parsed.length > 0 ? parsed : undefined;
+  }
+
+  /**
+   * CCLI credits. The field names follow the simulator and the .pro CCLI
+   * record; they have not been checked against a captured ProPresenter 7 /v1
+   * response, so only string and number values are read and anything else
+   * leaves the song without credits.
+   */
+  private parseMetadata(presentation: any): SongMetadata | undefined {
+    const ccli = presentation.ccli && typeof presentation.ccli === 'object' ? presentation.ccli : {};
+    const text = (value: any): string | undefined => (typeof value === 'string' && value.trim()) || undefined;
+    const number = (value: any): string | undefined =>
+      typeof value === 'number' && value > 0 ? String(value) : text(value);
+    const songNumber = number(ccli.song_number ?? ccli.songNumber ?? presentation.ccli_song_number);
+    const year = Number(number(ccli.copyright_year ?? ccli.copyrightYear));
+    const metadata: SongMetadata = {
+      ccliNumber: songNumber,
+      author: text(ccli.author),
+      artist: text(ccli.artist_credits ?? ccli.artistCredits),
+      publisher: text(ccli.publisher),
+      copyrightYear: year > 0 ? year : undefined,
+      album: text(ccli.album),
     };
+    return compactMetadata(metadata);
   }
 
   private parseGroups(groups: any[]): GroupInfo[] {
     return groups.map((group: any) => ({
+      uuid: group.group?.uuid || group.uuid || group.id?.uuid || undefined,
       name: group.group?.name || group.name || 'Unnamed Group',
       color: group.group?.color || group.color || '',
       slides: this.parseSlides(group.cues || group.slides || []),
@@ -263,9 +390,35 @@ export class ProPresenterClient extends EventEmitter {
         text = typeof slide.cue.text === 'string' ? slide.cue.text : slide.cue.text.text || '';
       }
 
+      // Separate text elements (e.g. a translation). Only the simulator is known
+      // to send these field names; a real /v1 response has not been checked, so
+      // anything that isn't a list leaves the slide with its single text.
+      const elementList = slide.elements ?? slide.text_elements ?? slide.slide?.elements;
+      const textElements: any[] = Array.isArray(elementList) ? elementList : [];
+      const elements = textElements
+        .map((element: any) => {
+          if (typeof element === 'string') return element;
+          const value = element?.text ?? element?.element?.text;
+          const elementText = typeof value === 'string' ? value : value?.text;
+          return typeof elementText === 'string' ? elementText : '';
+        })
+        .filter((value: string) => value.trim().length > 0);
+
+      // The Network API normally sends plain text only; use the RTF when a
+      // response carries it so formatting survives
+      const rtf = textElements
+        .map((element: any) => decodeRtf(
+          element?.text?.rtf_data ?? element?.rtf_data ?? element?.element?.text?.rtf_data
+        ))
+        .filter((value: string | null): value is string => value !== null);
+      const slideRtf = decodeRtf(slide.text?.rtf_data ?? slide.slide?.text?.rtf_data);
+      const runs = rtfBlobsToRuns(rtf.length > 0 ? rtf : slideRtf ? [slideRtf] : []);
+
       return {
         index: slide.index ?? index,
-        text: text || '',
+        text: text || elements.join('\n'),
+        elements: elements.length > 1 ? elements : undefined,
+        runs,
         notes: slide.notes || slide.slide?.notes || '',
         label: slide.label || slide.slide?.label || '',
         enabled: slide.enabled ?? slide.slide?.enabled ?? true,
@@ -33,6 +33,9 @@ import { fontRoutes } from './routes/fonts';
 import { serviceGeneratorRoutes } from './routes/service-generator';
 import { userRoutes } from './routes/users';
 import { launchRoutes } from './routes/launch';
+import { reportRoutes } from './routes/reports';
+import { presentationRoutes } from './routes/presentations';
+import { lintRoutes } from './routes/lint';
 import { ensureUsersFile, getAllowedEmails, getUsersFilePath } from './services/user-store';
 import { log, pruneOldLogs } from './services/logger';
 import { createViewerRoutes } from './routes/viewer';
@@ -183,6 +186,9 @@ app.use('/api', fontRoutes);
 app.use('/api', serviceGeneratorRoutes);
 app.use('/api', userRoutes);
 app.use('/api', launchRoutes);
+app.use('/api', reportRoutes);
+app.use('/api', presentationRoutes);
+app.use('/api', lintRoutes);
 
 // Serve static React build (production)
 const staticDir = path.join(__dirname, '..', '..', 'dist-web');
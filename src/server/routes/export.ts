@@ -1,12 +1,14 @@
 /**
- * Export routes — PPTX generation with SSE progress
+ * Export routes — lyrics export in any registered format with SSE progress
  *
- * Maps to IPC handlers: export:start, export:progress
+ * Maps to IPC handlers: export:start, export:progress, export:formats
  *
  * Flow:
  *   1. POST /api/export → starts export, returns { jobId }
  *   2. GET  /api/export/:id/progress → SSE stream of progress events
- *   3. GET  /api/export/:id/download → download the generated PPTX file
+ *   3. GET  /api/export/:id/download → download the generated file
+ *
+ * GET /api/export/formats lists the available formats and their options.
  */
 
 import { Router, Request, Response } from 'express';
@@ -16,9 +18,12 @@ import * as os from 'os';
 import * as path from 'path';
 import { ProPresenterClient } from '../../propresenter-client';
 import { collectPlaylistLyrics, PlaylistProgressEvent } from '../../services/playlist-exporter';
-import { exportToPowerPoint, PptxTextStyle } from '../../pptx-exporter';
+import { PptxTextStyleOverrides, TEXT_STYLE_SETTINGS, explicitTextStyle } from '../../pptx-exporter';
+import { describeExportFormats, getExporter, resolveExportOptions, describeExportProgress } from '../../exporters';
+import type { ExportProgressEvent } from '../../exporters';
 import { findLogoPath } from '../../services/logo';
 import { loadSettings, saveSettings, AppSettings } from '../services/settings-store';
+import { recordExportUsage } from '../../services/usage-store';
 
 export const exportRoutes = Router();
 
@@ -101,9 +106,28 @@ function forwardProgress(job: ExportJob, playlistId: string, event: PlaylistProg
   }
 }
 
+function forwardExportProgress(job: ExportJob, playlistId: string, event: ExportProgressEvent): void {
+  broadcastEvent(job, {
+    playlistId,
+    type: event.overflow ? 'warning' : event.action === 'split' ? 'slide:split' : 'slide:fit',
+    itemName: event.song,
+    message: describeExportProgress(event),
+  });
+}
+
+/**
+ * GET /api/export/formats
+ * List registered export formats with their option schemas.
+ */
+exportRoutes.get('/export/formats', (_req: Request, res: Response) => {
+  res.json(describeExportFormats());
+});
+
 /**
  * POST /api/export
- * Start a PPTX export. Returns { jobId }.
+ * Start an export (PPTX unless `format` is given). Returns { jobId }.
+ * The logo and master template come from settings (see the upload routes);
+ * file paths in `formatOptions` are ignored.
  */
 exportRoutes.post('/export', async (req: Request, res: Response) => {
   const {
@@ -112,7 +136,8 @@ exportRoutes.post('/export', async (req: Request, res: Response) => {
     libraryFilter,
     includeSongTitles,
     styleOverrides,
-    logoPath,
+    format = 'pptx',
+    formatOptions,
   } = req.body;
 
   if (!playlistId || !playlistName) {
@@ -120,6 +145,11 @@ exportRoutes.post('/export', async (req: Request, res: Response) => {
     return;
   }
 
+  if (!getExporter(String(format))) {
+    res.status(400).json({ error: `Unknown export format "${format}"` });
+    return;
+  }
+
   const jobId = crypto.randomUUID();
   const job: ExportJob = {
     id: jobId,
@@ -131,7 +161,15 @@ exportRoutes.post('/export', async (req: Request, res: Response) => {
   jobs.set(jobId, job);
 
   // Start export in background (don't await)
-  runExport(job, { playlistId, playlistName, libraryFilter, includeSongTitles, styleOverrides, logoPath });
+  runExport(job, {
+    playlistId,
+    playlistName,
+    libraryFilter,
+    includeSongTitles,
+    styleOverrides,
+    format: String(format),
+    formatOptions,
+  });
 
   res.json({ jobId });
 });
@@ -194,7 +232,7 @@ exportRoutes.get('/export/:id/progress', (req: Request, res: Response) => {
 
 /**
  * GET /api/export/:id/download
- * Download the generated PPTX file.
+ * Download the generated export file.
  */
 exportRoutes.get('/export/:id/download', (req: Request, res: Response) => {
   const job = jobs.get(String(req.params.id));
@@ -207,7 +245,7 @@ exportRoutes.get('/export/:id/download', (req: Request, res: Response) => {
     return;
   }
 
-  const fileName = job.fileName || 'export.pptx';
+  const fileName = job.fileName || 'export';
   res.download(job.filePath, fileName, (err) => {
     if (err && !res.headersSent) {
       res.status(500).json({ error: 'Failed to send file' });
@@ -222,8 +260,9 @@ async function runExport(
     playlistName: string;
     libraryFilter?: string | null;
     includeSongTitles?: boolean;
-    styleOverrides?: Partial<PptxTextStyle>;
-    logoPath?: string | null;
+    styleOverrides?: PptxTextStyleOverrides;
+    format: string;
+    formatOptions?: Record<string, unknown>;
   }
 ): Promise<void> {
   job.status = 'running';
@@ -249,17 +288,22 @@ async function runExport(
     broadcastEvent(job, {
       playlistId: payload.playlistId,
       type: 'pptx:start',
+      format: payload.format,
       totalSongs: result.songs.length,
     });
 
     const effectiveIncludeSongTitles =
       payload.includeSongTitles ?? settings.includeSongTitles ?? true;
 
-    const effectiveLogoPath =
-      payload.logoPath?.trim() || settings.logoPath || findLogoPath([]);
+    // Files come from settings (set by the upload routes), never from the request
+    const effectiveLogoPath = settings.logoPath || findLogoPath([]);
+    const clientOptions = { ...payload.formatOptions };
+    delete clientOptions.templatePath;
+    delete clientOptions.logoPath;
 
-    // Resolve style overrides (merge stored + payload)
-    const storedStyle: Partial<PptxTextStyle> = {
+    // Resolve style overrides (merge stored + payload); fields the user has
+    // not set (null) are dropped so the selected theme's fonts apply
+    const storedStyle: PptxTextStyleOverrides = {
       textColor: settings.textColor,
       fontFace: settings.fontFace,
       fontSize: settings.fontSize,
@@ -267,31 +311,42 @@ async function runExport(
       bold: settings.bold,
       italic: settings.italic,
     };
-    const mergedStyle: Partial<PptxTextStyle> = {
+    const mergedStyle: PptxTextStyleOverrides = {
       ...storedStyle,
       ...payload.styleOverrides,
     };
     mergedStyle.textColor = sanitizeColor(mergedStyle.textColor);
 
+    // Settings-backed values first, then anything format-specific from the payload
+    const exporter = getExporter(payload.format)!;
+    const options = resolveExportOptions(exporter, {
+      ...explicitTextStyle(mergedStyle),
+      includeSongTitles: effectiveIncludeSongTitles,
+      logoPath: effectiveLogoPath || undefined,
+      theme: settings.pptxTheme || undefined,
+      templatePath: settings.pptxTemplatePath || undefined,
+      ...clientOptions,
+    });
+
     // Write to temp directory
-    const fileName = `${slugify(payload.playlistName)}-${Date.now()}.pptx`;
+    const fileName = `${slugify(payload.playlistName)}-${Date.now()}.${exporter.extension}`;
     const outputPath = path.join(os.tmpdir(), fileName);
 
     const lyricsOnly = result.songs.map(entry => entry.lyrics);
-    await exportToPowerPoint(lyricsOnly, {
-      outputPath,
-      logoPath: effectiveLogoPath || undefined,
-      includeSongTitles: effectiveIncludeSongTitles,
-      styleOverrides: mergedStyle,
+    // Exporters may change the extension or write a bundle; use the path they report
+    const finalPath = await exporter.write(lyricsOnly, outputPath, options, {
+      onProgress: (event) => forwardExportProgress(job, payload.playlistId, event),
     });
+    recordExportUsage(lyricsOnly, { name: payload.playlistName, id: payload.playlistId });
 
     job.status = 'complete';
-    job.filePath = outputPath;
-    job.fileName = fileName;
+    job.filePath = finalPath;
+    job.fileName = path.basename(finalPath);
 
     broadcastEvent(job, {
       playlistId: payload.playlistId,
       type: 'pptx:complete',
+      format: payload.format,
       downloadUrl: `/api/export/${job.id}/download`,
     });
 
@@ -322,8 +377,11 @@ async function runExport(
       if (typeof payload.styleOverrides.titleFontSize === 'number') valuesToPersist.titleFontSize = payload.styleOverrides.titleFontSize;
       if (typeof payload.styleOverrides.bold === 'boolean') valuesToPersist.bold = payload.styleOverrides.bold;
       if (typeof payload.styleOverrides.italic === 'boolean') valuesToPersist.italic = payload.styleOverrides.italic;
+      // Fields cleared in the app (e.g. by picking a theme) are cleared here too
+      for (const key of TEXT_STYLE_SETTINGS) {
+        if (payload.styleOverrides[key] === null) valuesToPersist[key] = null;
+      }
     }
-    if (payload.logoPath) valuesToPersist.logoPath = payload.logoPath;
     saveSettings(valuesToPersist);
 
   } catch (error: any) {
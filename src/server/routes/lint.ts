@@ -0,0 +1,75 @@
+/**
+ * Lint routes — lyric quality checks for a playlist or whole libraries
+ *
+ * Web-only (mirrors the `lint` CLI command). Options come as query
+ * parameters: max-line, max-lines, rules, format=json|text.
+ */
+
+import { Router, Request, Response } from 'express';
+import { ProPresenterClient } from '../../propresenter-client';
+import { extractLyrics, ExtractedLyrics } from '../../lyrics-extractor';
+import { collectPlaylistLyrics } from '../../services/playlist-exporter';
+import { crawlLibraries } from '../../services/library-crawler';
+import { lintSongs, parseLintOptions, formatLintReport, LintReport } from '../../services/lyric-linter';
+import { loadSettings } from '../services/settings-store';
+
+export const lintRoutes = Router();
+
+function queryOptions(req: Request): Record<string, string | undefined> {
+  const value = (name: string) => (req.query[name] !== undefined ? String(req.query[name]) : undefined);
+  // The word list is a server path, so it can't be chosen by the client
+  return { 'max-line': value('max-line'), 'max-lines': value('max-lines'), rules: value('rules') };
+}
+
+function sendReport(req: Request, res: Response, songs: ExtractedLyrics[], scope: string): void {
+  let report: LintReport;
+  try {
+    report = lintSongs(songs, parseLintOptions(queryOptions(req)));
+  } catch (error: any) {
+    res.status(400).json({ error: error.message });
+    return;
+  }
+  if (String(req.query.format || 'json').toLowerCase() === 'text') {
+    res.type('text/plain; charset=utf-8').send(formatLintReport(report, scope));
+    return;
+  }
+  res.json(report);
+}
+
+/**
+ * GET /api/playlists/:uuid/lint
+ * Check the songs of a playlist (filtered to the song library, as for export)
+ */
+lintRoutes.get('/playlists/:uuid/lint', async (req: Request, res: Response) => {
+  try {
+    const settings = loadSettings();
+    const client = new ProPresenterClient({ host: settings.host, port: settings.port });
+    await client.connect();
+    const result = await collectPlaylistLyrics(client, String(req.params.uuid), {
+      libraryFilter: settings.libraryFilter || null,
+    });
+    sendReport(req, res, result.songs.map(entry => entry.lyrics), 'playlist');
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to lint playlist' });
+  }
+});
+
+/**
+ * GET /api/libraries/:ids/lint
+ * Check every presentation in one or more libraries (comma-separated UUIDs or names)
+ */
+lintRoutes.get('/libraries/:ids/lint', async (req: Request, res: Response) => {
+  try {
+    const settings = loadSettings();
+    const client = new ProPresenterClient({ host: settings.host, port: settings.port });
+    const songs: ExtractedLyrics[] = [];
+    const libraries = await crawlLibraries(client, {
+      libraries: String(req.params.ids).split(',').filter(Boolean),
+    }, ({ presentation }) => {
+      songs.push(extractLyrics(presentation));
+    });
+    sendReport(req, res, songs, libraries.map(library => library.name).join(', '));
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to lint libraries' });
+  }
+});
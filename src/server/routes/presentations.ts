@@ -0,0 +1,142 @@
+/**
+ * Presentation routes — per-song tools that work on a live presentation
+ *
+ * Web-only (mirrors the `diff` and `snapshot history` CLI commands)
+ */
+
+import { Router, Request, Response } from 'express';
+import multer from 'multer';
+import * as path from 'path';
+import { ProPresenterClient } from '../../propresenter-client';
+import { extractLyrics, formatLyricsAsText, ExtractedLyrics } from '../../lyrics-extractor';
+import { diffLyrics, formatLyricDiff, LyricDiff } from '../../services/lyric-diff';
+import { findSnapshotSong, loadSnapshotVersion } from '../../services/snapshot-store';
+import { parseProPresentation } from '../../services/pro-file-reader';
+import { loadSettings } from '../services/settings-store';
+
+export const presentationRoutes = Router();
+
+// Multer for .pro uploads to compare against — kept in memory, never written
+const proUpload = multer({
+  storage: multer.memoryStorage(),
+  limits: { fileSize: 20 * 1024 * 1024 }, // 20MB
+  fileFilter: (_req, file, cb) => {
+    if (path.extname(file.originalname).toLowerCase() === '.pro') {
+      cb(null, true);
+    } else {
+      cb(new Error('Only ProPresenter .pro files are accepted'));
+    }
+  },
+});
+
+async function loadLiveLyrics(uuid: string): Promise<ExtractedLyrics> {
+  const settings = loadSettings();
+  const client = new ProPresenterClient({ host: settings.host, port: settings.port });
+  await client.connect();
+  const presentation = await client.getPresentationByUuid(uuid);
+  if (!presentation) {
+    throw Object.assign(new Error(`Presentation not found: ${uuid}`), { status: 404 });
+  }
+  return extractLyrics(presentation);
+}
+
+function sendDiff(res: Response, diff: LyricDiff, format: string): void {
+  if (format === 'text') {
+    res.type('text/plain; charset=utf-8').send(formatLyricDiff(diff));
+    return;
+  }
+  res.json(diff);
+}
+
+/**
+ * GET /api/presentations/:uuid/diff?snapshot=<version>&format=json|text
+ * Compare the presentation as it is now in ProPresenter with a stored
+ * snapshot (the latest when no version is given). Files are compared by
+ * sending them to the POST form; the server never reads a client's path.
+ */
+presentationRoutes.get('/presentations/:uuid/diff', async (req: Request, res: Response) => {
+  try {
+    const uuid = String(req.params.uuid);
+    const snapshot = req.query.snapshot !== undefined ? String(req.query.snapshot) : undefined;
+
+    const current = await loadLiveLyrics(uuid);
+    const stored = loadSnapshotVersion(uuid, snapshot || undefined);
+    const diff = diffLyrics(stored.lyrics, current, {
+      before: `snapshot v${stored.number}, ${stored.version.takenAt.slice(0, 10)}`,
+      after: 'ProPresenter',
+    });
+    sendDiff(res, diff, String(req.query.format || 'json').toLowerCase());
+  } catch (error: any) {
+    res.status(error.status || 500).json({ error: error.message || 'Failed to compare lyrics' });
+  }
+});
+
+/**
+ * GET /api/presentations/:uuid/history
+ * Stored snapshot versions of a song, oldest first
+ */
+presentationRoutes.get('/presentations/:uuid/history', (req: Request, res: Response) => {
+  try {
+    const song = findSnapshotSong(String(req.params.uuid));
+    if (!song) {
+      res.status(404).json({ error: 'No snapshots of this presentation' });
+      return;
+    }
+    res.json(song);
+  } catch (error: any) {
+    res.status(error.status || 500).json({ error: error.message });
+  }
+});
+
+/**
+ * GET /api/presentations/:uuid/history/:version?format=json|text
+ * One stored version's lyrics, e.g. to restore them after an accidental edit
+ */
+presentationRoutes.get('/presentations/:uuid/history/:version', (req: Request, res: Response) => {
+  try {
+    const stored = loadSnapshotVersion(String(req.params.uuid), String(req.params.version));
+    if (String(req.query.format || 'json').toLowerCase() === 'text') {
+      res.type('text/plain; charset=utf-8').send(formatLyricsAsText(stored.lyrics));
+      return;
+    }
+    res.json(stored);
+  } catch (error: any) {
+    res.status(error.status || 500).json({ error: error.message });
+  }
+});
+
+/**
+ * POST /api/presentations/:uuid/diff
+ * Same comparison with an earlier version sent by the client: either a .pro
+ * file as multipart form data ('file' field, optional 'format'), or JSON
+ * { against: ExtractedLyrics, format? } with a song from a JSON export.
+ */
+presentationRoutes.post('/presentations/:uuid/diff', proUpload.single('file'), async (req: Request, res: Response) => {
+  try {
+    let previous: ExtractedLyrics;
+    let before: string;
+    if (req.file) {
+      try {
+        previous = extractLyrics(parseProPresentation(req.file.buffer, req.file.originalname));
+      } catch (error: any) {
+        res.status(400).json({ error: error.message || 'Could not read the .pro file' });
+        return;
+      }
+      before = req.file.originalname;
+    } else {
+      const against = req.body?.against as ExtractedLyrics | undefined;
+      if (!against || !Array.isArray(against.sections)) {
+        res.status(400).json({ error: 'Send a .pro file, or against: extracted lyrics (a song from a JSON export)' });
+        return;
+      }
+      previous = against;
+      before = 'uploaded';
+    }
+
+    const current = await loadLiveLyrics(String(req.params.uuid));
+    const diff = diffLyrics(previous, current, { before, after: 'ProPresenter' });
+    sendDiff(res, diff, String(req.body?.format || 'json').toLowerCase());
+  } catch (error: any) {
+    res.status(error.status || 500).json({ error: error.message || 'Failed to compare lyrics' });
+  }
+});
@@ -0,0 +1,43 @@
+/**
+ * Report routes — CCLI song usage reporting
+ *
+ * Web-only (mirrors the `report` CLI command)
+ */
+
+import { Router, Request, Response } from 'express';
+import { buildUsageReport, formatUsageReportCsv } from '../../services/usage-store';
+
+export const reportRoutes = Router();
+
+const DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+/**
+ * GET /api/reports/usage?from=YYYY-MM-DD&to=YYYY-MM-DD&format=json|csv
+ * Song usage for a date range (inclusive). CSV is returned as a download.
+ */
+reportRoutes.get('/reports/usage', (req: Request, res: Response) => {
+  try {
+    const from = req.query.from ? String(req.query.from) : undefined;
+    const to = req.query.to ? String(req.query.to) : undefined;
+    const format = String(req.query.format || 'json').toLowerCase();
+
+    if ((from && !DATE_PATTERN.test(from)) || (to && !DATE_PATTERN.test(to))) {
+      res.status(400).json({ error: 'from and to must be dates in YYYY-MM-DD format' });
+      return;
+    }
+
+    const report = buildUsageReport(from, to);
+
+    if (format === 'csv') {
+      const fileName = `song-usage-${from || 'start'}-to-${to || 'today'}.csv`;
+      res.setHeader('Content-Type', 'text/csv; charset=utf-8');
+      res.setHeader('Content-Disposition', `attachment; filename="${fileName}"`);
+      res.send(formatUsageReportCsv(report));
+      return;
+    }
+
+    res.json(report);
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to build usage report' });
+  }
+});
@@ -2,9 +2,12 @@
  * Service Generator routes — PDF parsing, song/verse matching, playlist building
  *
  * Maps to IPC handlers:
- *   pdf:parse, songs:match, verses:fetch, verses:match,
+ *   pdf:parse, grammars:list, songs:match, verses:fetch, verses:match,
  *   playlist:build-service, playlist:create-from-template,
- *   playlist:focus-item, library:search-presentations
+ *   playlist:focus-item, library:search-presentations,
+ *   library:search-lyrics, library:index-lyrics,
+ *   service-sessions:list, service-sessions:get, service-sessions:save,
+ *   service-sessions:delete
  */
 
 import { Router, Request, Response } from 'express';
@@ -13,40 +16,59 @@ import * as os from 'os';
 import * as path from 'path';
 import * as fs from 'fs';
 import { ProPresenterClient } from '../../propresenter-client';
-import { SongMatcher } from '../../services/song-matcher';
-import { loadAliases, aliasesToCustomMappings } from '../../services/alias-store';
+import {
+  loadServiceLibraries,
+  matchServiceSongs,
+  matchVerseReferences,
+  buildServicePlaylist,
+  recordServicePlaylistUsage,
+  ServicePlaylistItem,
+} from '../../services/service-pipeline';
 import { loadSettings } from '../services/settings-store';
+import { buildLyricIndex, resolveLibraryIds, searchLyrics, unindexedLibraries } from '../../services/lyric-search';
+import { listServiceGrammars, requireServiceGrammar } from '../../services/service-grammar';
+import { isServiceOrderFile } from '../../services/service-order-reader';
+import {
+  loadServiceSessions,
+  getServiceSession,
+  saveServiceSession,
+  validateServiceSession,
+  deleteServiceSession,
+} from '../../services/service-session-store';
 
 export const serviceGeneratorRoutes = Router();
 
-// Multer for PDF uploads — store in temp directory
+// Multer for service order uploads (PDF, Word, text, Markdown) — store in temp directory
 const upload = multer({
   dest: os.tmpdir(),
   limits: { fileSize: 20 * 1024 * 1024 }, // 20MB
   fileFilter: (_req, file, cb) => {
-    if (file.mimetype === 'application/pdf' || path.extname(file.originalname).toLowerCase() === '.pdf') {
+    if (file.mimetype === 'application/pdf' || isServiceOrderFile(file.originalname)) {
       cb(null, true);
     } else {
-      cb(new Error('Only PDF files are accepted'));
+      cb(new Error('Only PDF, Word (.docx), text and Markdown files are accepted'));
     }
   },
 });
 
 /**
  * POST /api/service/parse-pdf
- * Upload and parse a service order PDF.
- * Accepts multipart form data with a 'file' field.
+ * Upload and parse a service order (.pdf, .docx, .txt or .md).
+ * Accepts multipart form data with a 'file' field and an optional 'grammar'
+ * (service grammar id; defaults to the one in settings).
  */
 serviceGeneratorRoutes.post('/service/parse-pdf', upload.single('file'), async (req: Request, res: Response) => {
   try {
     if (!req.file) {
-      res.status(400).json({ success: false, error: 'No PDF file uploaded' });
+      res.status(400).json({ success: false, error: 'No service order file uploaded' });
       return;
     }
 
     const { PDFParser } = await import('../../services/pdf-parser');
-    const parser = new PDFParser();
-    const result = await parser.parsePDF(req.file.path);
+    const parser = new PDFParser(requireServiceGrammar(req.body?.grammar || loadSettings().serviceGrammar));
+    // Uploads are stored without an extension; the original name gives the format
+    const extension = path.extname(req.file.originalname) || '.pdf';
+    const result = await parser.parseFile(req.file.path, extension);
 
     // Clean up temp file
     try { fs.unlinkSync(req.file.path); } catch { /* ignore */ }
@@ -64,13 +86,83 @@ serviceGeneratorRoutes.post('/service/parse-pdf', upload.single('file'), async (
 
     const specialServiceType = result.specialServiceType;
 
-    res.json({ success: true, items, specialServiceType });
+    res.json({ success: true, items, specialServiceType, service: result });
   } catch (error: any) {
     // Clean up temp file on error
     if (req.file) {
       try { fs.unlinkSync(req.file.path); } catch { /* ignore */ }
     }
-    res.json({ success: false, error: error.message || 'Failed to parse PDF' });
+    res.json({ success: false, error: error.message || 'Failed to parse service order' });
+  }
+});
+
+/**
+ * GET /api/service/grammars
+ * List the service order grammars (built-in and custom).
+ */
+serviceGeneratorRoutes.get('/service/grammars', (_req: Request, res: Response) => {
+  try {
+    res.json(listServiceGrammars());
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to list grammars' });
+  }
+});
+
+/**
+ * GET /api/service/sessions
+ * List saved Service Generator sessions, most recently updated first.
+ */
+serviceGeneratorRoutes.get('/service/sessions', (_req: Request, res: Response) => {
+  try {
+    res.json(loadServiceSessions());
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to list sessions' });
+  }
+});
+
+/**
+ * GET /api/service/sessions/:id
+ * Get a saved session to resume.
+ */
+serviceGeneratorRoutes.get('/service/sessions/:id', (req: Request, res: Response) => {
+  const session = getServiceSession(String(req.params.id));
+  if (!session) {
+    res.status(404).json({ error: 'Session not found' });
+    return;
+  }
+  res.json(session);
+});
+
+/**
+ * POST /api/service/sessions
+ * Save (create or update) a session. Updating an id that is not stored
+ * (e.g. a discarded session) returns 404.
+ *
+ * Body: { id?, name, playlistId, step?, parsed?, items?, songMatches?, verseMatches?, verses?, versesSkipped?, build? }
+ */
+serviceGeneratorRoutes.post('/service/sessions', (req: Request, res: Response) => {
+  try {
+    const errors = validateServiceSession(req.body);
+    if (errors.length > 0) {
+      res.status(400).json({ error: errors.join('; ') });
+      return;
+    }
+
+    res.json(saveServiceSession(req.body));
+  } catch (error: any) {
+    res.status(error.status || 500).json({ error: error.message || 'Failed to save session' });
+  }
+});
+
+/**
+ * DELETE /api/service/sessions/:id
+ * Discard a session.
+ */
+serviceGeneratorRoutes.delete('/service/sessions/:id', (req: Request, res: Response) => {
+  try {
+    res.json({ deleted: deleteServiceSession(String(req.params.id)) });
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to discard session' });
   }
 });
 
@@ -96,110 +188,12 @@ serviceGeneratorRoutes.post('/service/match-songs', async (req: Request, res: Re
 
     const settings = loadSettings();
     const client = new ProPresenterClient({ host: settings.host, port: settings.port });
-
-    const aliases = loadAliases();
-    const customMappings = aliasesToCustomMappings(aliases);
-    const matcher = new SongMatcher(0.7, customMappings);
-
-    const libraries = await client.getLibraries();
-
-    // Fetch worship presentations
-    const worshipPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    for (const libraryId of (libraryIds || [])) {
-      if (!libraryId || libraryId === kidsLibraryId || libraryId === serviceContentLibraryId) continue;
-      try {
-        const presentations = await client.getLibraryPresentations(libraryId);
-        const library = libraries.find(l => l.uuid === libraryId);
-        const libraryName = library?.name || 'Unknown';
-        for (const pres of presentations) {
-          worshipPresentations.push({ uuid: pres.uuid, name: pres.name, library: libraryName, libraryId });
-        }
-      } catch { /* skip unreadable libraries */ }
-    }
-
-    // Fetch service content presentations
-    const serviceContentPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    if (serviceContentLibraryId) {
-      try {
-        const presentations = await client.getLibraryPresentations(serviceContentLibraryId);
-        const library = libraries.find(l => l.uuid === serviceContentLibraryId);
-        const libraryName = library?.name || 'Service Content';
-        for (const pres of presentations) {
-          serviceContentPresentations.push({ uuid: pres.uuid, name: pres.name, library: libraryName, libraryId: serviceContentLibraryId });
-        }
-      } catch { /* skip */ }
-    }
-
-    // Fetch kids presentations
-    const kidsPresentations: Array<{ uuid: string; name: string; library: string; libraryId: string }> = [];
-    if (kidsLibraryId) {
-      try {
-        const presentations = await client.getLibraryPresentations(kidsLibraryId);
-        const library = libraries.find(l => l.uuid === kidsLibraryId);
-        const libraryName = library?.name || 'Kids';
-        for (const pres of presentations) {
-          kidsPresentations.push({ uuid: pres.uuid, name: pres.name, library: libraryName, libraryId: kidsLibraryId });
-        }
-      } catch { /* skip */ }
-    }
-
-    // Match each song
-    const results = [];
-    for (let i = 0; i < songItems.length; i++) {
-      const item = songItems[i];
-      const isKids = item.isKidsVideo || item.praiseSlot === 'kids';
-      const isVideo = item.praiseSlot === 'kids_video' || (item.text && item.text.includes('(Video)'));
-      const isNonKidsVideo = isVideo && !isKids;
-
-      const songText = typeof item.text === 'string' ? item.text : String(item.text || '');
-      if (!songText) continue;
-
-      let presentationsToMatch;
-      if (isKids) {
-        presentationsToMatch = kidsPresentations;
-      } else if (isNonKidsVideo) {
-        presentationsToMatch = serviceContentPresentations;
-      } else {
-        presentationsToMatch = worshipPresentations;
-      }
-
-      const songSection = { type: 'song' as const, title: songText, position: i };
-      let matches = await matcher.matchSongs([songSection], presentationsToMatch);
-      let match = matches[0];
-
-      // Cross-library fallback for kids
-      if (isKids && (!match.bestMatch || match.bestMatch.confidence < 0.7)) {
-        const allPresentations = [...worshipPresentations, ...serviceContentPresentations, ...kidsPresentations];
-        const fallbackMatches = await matcher.matchSongs([songSection], allPresentations);
-        const fallbackMatch = fallbackMatches[0];
-        if (fallbackMatch.bestMatch && fallbackMatch.bestMatch.confidence > (match.bestMatch?.confidence || 0)) {
-          match = fallbackMatch;
-        }
-      }
-
-      results.push({
-        songName: match.pdfTitle,
-        praiseSlot: item.praiseSlot,
-        isKidsVideo: isKids,
-        matches: match.matches.map(m => ({
-          uuid: m.presentation.uuid,
-          name: m.presentation.name,
-          library: m.presentation.library,
-          confidence: Math.round(m.confidence * 100),
-        })),
-        bestMatch: match.bestMatch ? {
-          uuid: match.bestMatch.presentation.uuid,
-          name: match.bestMatch.presentation.name,
-          library: match.bestMatch.presentation.library,
-          confidence: Math.round(match.bestMatch.confidence * 100),
-        } : undefined,
-        requiresReview: match.requiresReview,
-        selectedMatch: match.bestMatch && !match.requiresReview ? {
-          uuid: match.bestMatch.presentation.uuid,
-          name: match.bestMatch.presentation.name,
-        } : undefined,
-      });
-    }
+    const libraries = await loadServiceLibraries(client, {
+      libraryIds: libraryIds || [],
+      kidsLibraryId,
+      serviceContentLibraryId,
+    });
+    const results = await matchServiceSongs(songItems, libraries);
 
     res.json({ success: true, results });
   } catch (error: any) {
@@ -239,58 +233,7 @@ serviceGeneratorRoutes.post('/service/match-verses', async (req: Request, res: R
     const settings = loadSettings();
     const client = new ProPresenterClient({ host: settings.host, port: settings.port });
     const presentations = await client.getLibraryPresentations(serviceContentLibraryId);
-
-    // Pre-filter to Bible verse presentations
-    const bibleTranslationPattern = /\b(niv|esv|nlt|kjv|nkjv|nasb|csb|msg)\b/i;
-    const biblePresentations = presentations.filter(pres => bibleTranslationPattern.test(pres.name));
-    const searchPresentations = biblePresentations.length > 0 ? biblePresentations : presentations;
-
-    const results = verseReferences.map(reference => {
-      const normalizedRef = reference.toLowerCase().trim();
-      const normalizedRefStripped = normalizedRef.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-
-      const refParts = normalizedRef.match(/^(\d?\s*[a-z]+)\s+(\d+)/);
-      const refBook = refParts ? refParts[1].replace(/\s+/g, ' ').trim() : '';
-      const refChapter = refParts ? refParts[2] : '';
-
-      const matches = searchPresentations
-        .filter(pres => {
-          const presName = pres.name.toLowerCase();
-          const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-          return presName.includes(normalizedRef) ||
-                 presNameStripped.includes(normalizedRefStripped) ||
-                 normalizedRef.includes(presName) ||
-                 normalizedRefStripped.includes(presNameStripped) ||
-                 (refBook && refChapter && presName.includes(refBook) && presName.includes(refChapter));
-        })
-        .map(pres => {
-          const presName = pres.name.toLowerCase();
-          const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
-          let confidence = 0;
-          if (presName === normalizedRef || presNameStripped === normalizedRefStripped) {
-            confidence = 100;
-          } else if (presName.includes(normalizedRef) || normalizedRef.includes(presName) ||
-                     presNameStripped.includes(normalizedRefStripped) || normalizedRefStripped.includes(presNameStripped)) {
-            confidence = 85;
-          } else {
-            confidence = 60;
-          }
-          return { uuid: pres.uuid, name: pres.name, confidence };
-        })
-        .sort((a, b) => b.confidence - a.confidence)
-        .slice(0, 5);
-
-      const bestMatch = matches[0];
-      const requiresReview = !bestMatch || bestMatch.confidence < 85;
-
-      return {
-        reference,
-        matches,
-        bestMatch,
-        requiresReview,
-        selectedMatch: bestMatch && !requiresReview ? { uuid: bestMatch.uuid, name: bestMatch.name } : undefined,
-      };
-    });
+    const results = matchVerseReferences(verseReferences, presentations);
 
     res.json({ success: true, results });
   } catch (error: any) {
@@ -313,125 +256,16 @@ serviceGeneratorRoutes.post('/service/build-playlist', async (req: Request, res:
       return;
     }
 
-    const settings = loadSettings();
-    const { host, port } = settings;
-
-    // Step 1: Fetch current playlist
-    const getResponse = await fetch(`http://${host}:${port}/v1/playlist/${playlistId}`);
-    if (!getResponse.ok) {
-      throw new Error(`Failed to fetch playlist: ${getResponse.status}`);
-    }
-    const playlistData = await getResponse.json() as any;
-    const currentItems = playlistData.items || [];
-
-    // Step 2: Group by slot
-    const itemsBySlot: Record<string, any[]> = {
-      praise1: [], praise2: [], praise3: [], kids: [], reading: [],
-    };
-    for (const item of items) {
-      const slot = item.praiseSlot || 'praise1';
-      if (itemsBySlot[slot]) {
-        itemsBySlot[slot].push(item);
-      }
-    }
-
-    // Step 3: Header → slot mapping
-    const headerToSlot: Record<string, string> = {
-      'praise 1': 'praise1', 'praise1': 'praise1',
-      'praise 2': 'praise2', 'praise2': 'praise2',
-      'praise 3': 'praise3', 'praise3': 'praise3',
-      'kids talk': 'kids', 'kids': 'kids', 'kids song': 'kids', 'kids video': 'kids',
-      'reading': 'reading', 'bible': 'reading',
-    };
-
-    // Step 4: Build new playlist
-    const newItems: any[] = [];
-    let skipUntilNextHeader = false;
-
-    for (const item of currentItems) {
-      const isHeader = item.type === 'header';
-      const itemName = (item.id?.name || item.name || '').toLowerCase().trim();
-
-      if (isHeader) {
-        const matchedSlot = headerToSlot[itemName];
-        if (matchedSlot) {
-          newItems.push(item);
-          const slotItems = itemsBySlot[matchedSlot] || [];
-          for (const songItem of slotItems) {
-            newItems.push({
-              id: { name: songItem.name, uuid: songItem.uuid, index: newItems.length },
-              type: 'presentation',
-              is_hidden: false,
-              is_pco: false,
-              target_uuid: songItem.uuid,
-              presentation_info: {
-                presentation_uuid: songItem.uuid,
-                arrangement_name: '',
-                arrangement_uuid: '',
-              },
-              destination: 'presentation',
-            });
-          }
-          skipUntilNextHeader = slotItems.length > 0;
-        } else {
-          skipUntilNextHeader = false;
-          newItems.push(item);
-        }
-      } else {
-        if (!skipUntilNextHeader) {
-          newItems.push(item);
-        }
-      }
-    }
-
-    // Step 5: Clean items
-    const cleanedItems = newItems.map((item: any, index: number) => {
-      let itemUuid = item.id?.uuid || '';
-      if (item.type === 'presentation' && !itemUuid && item.presentation_info?.presentation_uuid) {
-        itemUuid = item.presentation_info.presentation_uuid;
-      }
-
-      const cleaned: any = {
-        id: { name: item.id?.name || item.name || 'Untitled', index, uuid: itemUuid },
-        type: item.type,
-        is_hidden: item.is_hidden || false,
-        is_pco: item.is_pco || false,
-      };
-
-      // Include target_uuid (required by ProPresenter for deserialization)
-      cleaned.target_uuid = item.target_uuid || '';
-
-      if (item.type === 'header' && item.header_color) {
-        cleaned.header_color = item.header_color;
-      }
-      if (item.type === 'presentation') {
-        if (item.presentation_info) {
-          cleaned.presentation_info = {
-            presentation_uuid: item.presentation_info.presentation_uuid,
-            arrangement_name: item.presentation_info.arrangement_name || '',
-            arrangement_uuid: item.presentation_info.arrangement_uuid || '',
-          };
-        }
-        if (item.duration) cleaned.duration = item.duration;
-      }
-      if (item.destination) cleaned.destination = item.destination;
-
-      return cleaned;
-    });
-
-    // Step 6: PUT
-    const putResponse = await fetch(`http://${host}:${port}/v1/playlist/${playlistId}`, {
-      method: 'PUT',
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify(cleanedItems),
-    });
+    const { host, port } = loadSettings();
+    const playlist = await buildServicePlaylist(host, port, playlistId, items);
 
-    if (!putResponse.ok) {
-      const errorText = await putResponse.text();
-      throw new Error(`Failed to update playlist: ${putResponse.status} ${errorText}`);
-    }
+    await recordServicePlaylistUsage(
+      new ProPresenterClient({ host, port }),
+      { name: playlist.name, id: playlistId },
+      items as ServicePlaylistItem[]
+    );
 
-    res.json({ success: true, itemCount: cleanedItems.length });
+    res.json({ success: true, itemCount: playlist.itemCount });
   } catch (error: any) {
     res.json({ success: false, error: error.message || 'Failed to build playlist' });
   }
@@ -562,6 +396,59 @@ serviceGeneratorRoutes.get('/libraries/:ids/search', async (req: Request, res: R
   }
 });
 
+/**
+ * GET /api/libraries/:ids/lyrics-search?q=<words of a lyric line>
+ * Full-text lyric search. :ids are library UUIDs or names. Libraries that
+ * were never indexed are crawled first.
+ */
+serviceGeneratorRoutes.get('/libraries/:ids/lyrics-search', async (req: Request, res: Response) => {
+  try {
+    const query = (String(req.query.q || '')).trim();
+
+    if (!query) {
+      res.json({ success: true, results: [] });
+      return;
+    }
+
+    const settings = loadSettings();
+    const client = new ProPresenterClient({ host: settings.host, port: settings.port });
+    const libraryIds = await resolveLibraryIds(client, String(req.params.ids).split(','));
+    const missing = unindexedLibraries(libraryIds);
+    if (missing.length > 0) {
+      await buildLyricIndex(client, { libraries: missing });
+    }
+
+    const results = searchLyrics(query, { libraryIds }).map(result => ({
+      uuid: result.uuid,
+      name: result.title,
+      library: result.library,
+      section: result.section,
+      snippet: result.snippet,
+    }));
+    res.json({ success: true, results });
+  } catch (error: any) {
+    res.json({ success: false, error: error.message, results: [] });
+  }
+});
+
+/**
+ * POST /api/libraries/:ids/lyrics-index
+ * Re-crawl libraries into the lyric index, picking up new and edited songs
+ */
+serviceGeneratorRoutes.post('/libraries/:ids/lyrics-index', async (req: Request, res: Response) => {
+  try {
+    const libraryIds = String(req.params.ids).split(',').filter(Boolean);
+    const settings = loadSettings();
+    const indexed = await buildLyricIndex(
+      new ProPresenterClient({ host: settings.host, port: settings.port }),
+      { libraries: libraryIds }
+    );
+    res.json({ success: true, songs: indexed.reduce((total, library) => total + library.songs, 0) });
+  } catch (error: any) {
+    res.json({ success: false, error: error.message });
+  }
+});
+
 /**
  * POST /api/service/fetch-verses
  * Stub for Bible verse text fetch.
@@ -1,8 +1,9 @@
 /**
  * Settings routes — load and save app settings
  *
- * Maps to IPC handlers: settings:load, settings:save
- * Also handles logo file uploads for the web proxy.
+ * Maps to IPC handlers: settings:load, settings:save, themes:list, rules:*
+ * Also handles logo and PPTX master template uploads, PPTX themes and lyric
+ * classification rules for the web proxy.
  */
 
 import { Router, Request, Response } from 'express';
@@ -11,6 +12,12 @@ import * as fs from 'fs';
 import * as path from 'path';
 import * as os from 'os';
 import { loadSettings, saveSettings } from '../services/settings-store';
+import { listThemes, getTheme, saveTheme, deleteTheme } from '../../services/theme-store';
+import {
+  loadClassificationRules,
+  saveClassificationRules,
+  resetClassificationRules,
+} from '../../services/classification-rules';
 
 export const settingsRoutes = Router();
 
@@ -46,6 +53,31 @@ const logoUpload = multer({
   },
 });
 
+// Multer for PPTX master templates — one template, replaced on each upload
+const templateUpload = multer({
+  storage: multer.diskStorage({
+    destination: (_req, _file, cb) => {
+      ensureUploadsDir();
+      cb(null, UPLOADS_DIR);
+    },
+    filename: (_req, _file, cb) => {
+      cb(null, 'template.pptx');
+    },
+  }),
+  limits: { fileSize: 50 * 1024 * 1024 }, // 50MB
+  fileFilter: (_req, file, cb) => {
+    if (path.extname(file.originalname).toLowerCase() === '.pptx') {
+      cb(null, true);
+    } else {
+      cb(new Error('Only .pptx files are accepted'));
+    }
+  },
+});
+
+// File paths only ever come from the upload routes, so a client can keep or
+// clear them but not point the server at another file
+const UPLOADED_FILE_SETTINGS = ['logoPath', 'pptxTemplatePath'] as const;
+
 /**
  * GET /api/settings
  * Load current settings.
@@ -65,7 +97,14 @@ settingsRoutes.get('/settings', (_req: Request, res: Response) => {
  */
 settingsRoutes.put('/settings', (req: Request, res: Response) => {
   try {
-    const updated = saveSettings(req.body);
+    const current = loadSettings();
+    const changes = { ...req.body };
+    for (const key of UPLOADED_FILE_SETTINGS) {
+      if (key in changes && changes[key] !== null && changes[key] !== current[key]) {
+        delete changes[key];
+      }
+    }
+    const updated = saveSettings(changes);
     res.json(updated);
   } catch (error: any) {
     res.status(500).json({ error: error.message || 'Failed to save settings' });
@@ -106,3 +145,127 @@ settingsRoutes.delete('/logo', (_req: Request, res: Response) => {
     res.status(500).json({ success: false, error: error.message || 'Failed to clear logo' });
   }
 });
+
+/**
+ * POST /api/pptx-template/upload
+ * Upload a .pptx whose slide master PPTX exports reuse.
+ * Stores to ~/.propresenter-words/uploads/template.pptx
+ * and saves the path to settings.
+ */
+settingsRoutes.post('/pptx-template/upload', templateUpload.single('file'), (req: Request, res: Response) => {
+  try {
+    if (!req.file) {
+      res.status(400).json({ success: false, error: 'No .pptx file uploaded' });
+      return;
+    }
+
+    const pptxTemplatePath = req.file.path;
+    saveSettings({ pptxTemplatePath });
+
+    res.json({ success: true, filePath: pptxTemplatePath });
+  } catch (error: any) {
+    res.status(500).json({ success: false, error: error.message || 'Failed to upload template' });
+  }
+});
+
+/**
+ * DELETE /api/pptx-template
+ * Stop using the uploaded template; exports go back to the theme.
+ */
+settingsRoutes.delete('/pptx-template', (_req: Request, res: Response) => {
+  try {
+    saveSettings({ pptxTemplatePath: null });
+    res.json({ success: true });
+  } catch (error: any) {
+    res.status(500).json({ success: false, error: error.message || 'Failed to clear template' });
+  }
+});
+
+/**
+ * GET /api/themes
+ * List built-in and user PPTX themes.
+ */
+settingsRoutes.get('/themes', (_req: Request, res: Response) => {
+  try {
+    res.json(listThemes());
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to list themes' });
+  }
+});
+
+/**
+ * GET /api/themes/:id
+ */
+settingsRoutes.get('/themes/:id', (req: Request, res: Response) => {
+  const theme = getTheme(String(req.params.id));
+  if (!theme) {
+    res.status(404).json({ error: `Theme not found: ${req.params.id}` });
+    return;
+  }
+  res.json(theme);
+});
+
+/**
+ * PUT /api/themes/:id
+ * Create or replace a user theme. Missing parts are filled from the classic theme.
+ */
+settingsRoutes.put('/themes/:id', (req: Request, res: Response) => {
+  try {
+    const theme = saveTheme({ ...req.body, id: String(req.params.id) });
+    res.json(theme);
+  } catch (error: any) {
+    res.status(400).json({ error: error.message || 'Failed to save theme' });
+  }
+});
+
+/**
+ * DELETE /api/themes/:id
+ * Remove a user theme. Built-in presets cannot be deleted.
+ */
+settingsRoutes.delete('/themes/:id', (req: Request, res: Response) => {
+  try {
+    const id = String(req.params.id);
+    if (!deleteTheme(id)) {
+      res.status(404).json({ success: false, error: `Theme not found: ${id}` });
+      return;
+    }
+    if (loadSettings().pptxTheme === id) {
+      saveSettings({ pptxTheme: null });
+    }
+    res.json({ success: true });
+  } catch (error: any) {
+    res.status(400).json({ success: false, error: error.message || 'Failed to delete theme' });
+  }
+});
+
+/**
+ * GET /api/classification-rules
+ * Current lyric classification rules (built-in unless customised).
+ */
+settingsRoutes.get('/classification-rules', (_req: Request, res: Response) => {
+  res.json(loadClassificationRules());
+});
+
+/**
+ * PUT /api/classification-rules
+ * Replace the rule set. Responds 400 with every problem if it's invalid.
+ */
+settingsRoutes.put('/classification-rules', (req: Request, res: Response) => {
+  try {
+    res.json(saveClassificationRules(req.body));
+  } catch (error: any) {
+    res.status(400).json({ error: error.message || 'Invalid classification rules' });
+  }
+});
+
+/**
+ * DELETE /api/classification-rules
+ * Go back to the built-in rules.
+ */
+settingsRoutes.delete('/classification-rules', (_req: Request, res: Response) => {
+  try {
+    res.json(resetClassificationRules());
+  } catch (error: any) {
+    res.status(500).json({ error: error.message || 'Failed to reset classification rules' });
+  }
+});
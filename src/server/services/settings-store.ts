@@ -7,7 +7,6 @@
 import * as fs from 'fs';
 import * as path from 'path';
 import * as os from 'os';
-import { DEFAULT_PPTX_TEXT_STYLE } from '../../pptx-exporter';
 
 const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
 const SETTINGS_FILE = path.join(CONFIG_DIR, 'web-settings.json');
@@ -17,13 +16,18 @@ export interface AppSettings {
   port: number;
   libraryFilter: string | null;
   includeSongTitles: boolean;
-  textColor: string;
-  fontFace: string;
-  fontSize: number;
-  titleFontSize: number;
-  bold: boolean;
-  italic: boolean;
+  // PPTX text style; unset (null) fields fall back to the theme, then the defaults
+  textColor?: string | null;
+  fontFace?: string | null;
+  fontSize?: number | null;
+  titleFontSize?: number | null;
+  bold?: boolean | null;
+  italic?: boolean | null;
   logoPath?: string | null;
+  /** Uploaded .pptx whose slide master PPTX exports use instead of the theme */
+  pptxTemplatePath?: string | null;
+  /** PPTX theme id; unset means the classic layout */
+  pptxTheme?: string | null;
   lastPlaylistId?: string;
   // Service Generator
   enableServiceGenerator?: boolean;
@@ -31,6 +35,8 @@ export interface AppSettings {
   kidsLibraryId?: string | null;
   serviceContentLibraryId?: string | null;
   templatePlaylistId?: string | null;
+  /** Service order grammar id; unset means the built-in one */
+  serviceGrammar?: string | null;
 }
 
 const DEFAULT_HOST = process.env.PROPRESENTER_HOST || '127.0.0.1';
@@ -42,12 +48,6 @@ const DEFAULT_SETTINGS: AppSettings = {
   port: DEFAULT_PORT,
   libraryFilter: DEFAULT_LIBRARY,
   includeSongTitles: true,
-  textColor: DEFAULT_PPTX_TEXT_STYLE.textColor,
-  fontFace: DEFAULT_PPTX_TEXT_STYLE.fontFace,
-  fontSize: DEFAULT_PPTX_TEXT_STYLE.fontSize,
-  titleFontSize: DEFAULT_PPTX_TEXT_STYLE.titleFontSize,
-  bold: DEFAULT_PPTX_TEXT_STYLE.bold,
-  italic: DEFAULT_PPTX_TEXT_STYLE.italic,
   logoPath: null,
 };
 
@@ -0,0 +1,311 @@
+/**
+ * Lyric Classification Rules
+ *
+ * Decides whether a slide is a lyric slide from a declarative rule set
+ * instead of hard-coded heuristics. Rules are checked from the highest
+ * priority down and the first match decides; slides no rule matches get
+ * the rule set's default action.
+ *
+ * Before any rule, slides switched off in ProPresenter and slides whose
+ * label is on the skip list are excluded.
+ *
+ * Rules live in ~/.propresenter-words/classification-rules.json. Without
+ * that file the built-in rules are used, which match the old heuristics.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import type { SlideInfo } from '../propresenter-client';
+
+export type ClassificationAction = 'include' | 'exclude';
+
+/**
+ * Conditions of a rule. Every condition given must hold for the rule to match.
+ * Patterns are case-insensitive regexes, or "/pattern/flags" for other flags.
+ */
+export interface ClassificationMatch {
+  /** Group (section) name */
+  group?: string;
+  /** Slide text, trimmed */
+  text?: string;
+  /** Slide label */
+  label?: string;
+  /** Whether the slide is enabled in ProPresenter */
+  slideEnabled?: boolean;
+  /** Trimmed text length bounds, inclusive */
+  minLength?: number;
+  maxLength?: number;
+}
+
+export interface ClassificationRule {
+  id: string;
+  description?: string;
+  action: ClassificationAction;
+  /** Higher runs first */
+  priority: number;
+  /** Turn a rule off without deleting it */
+  disabled?: boolean;
+  match: ClassificationMatch;
+}
+
+export interface ClassificationRuleSet {
+  /** Action for slides no rule matches */
+  defaultAction: ClassificationAction;
+  /** Exclude slides disabled in ProPresenter before any rule runs (default true) */
+  skipDisabled?: boolean;
+  /** Slide labels (case-insensitive, exact) whose slides are always excluded */
+  skipLabels?: string[];
+  rules: ClassificationRule[];
+}
+
+export interface ClassificationResult {
+  isLyric: boolean;
+  /** Id of the deciding rule; null when the default action applied */
+  ruleId: string | null;
+  reason: string;
+}
+
+export const DEFAULT_SKIP_LABELS = ['Blank', 'Black', 'Logo', 'Clear', 'Background'];
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const RULES_FILE = path.join(CONFIG_DIR, 'classification-rules.json');
+
+export const DEFAULT_CLASSIFICATION_RULES: ClassificationRuleSet = {
+  defaultAction: 'include',
+  skipDisabled: true,
+  skipLabels: DEFAULT_SKIP_LABELS,
+  rules: [
+    {
+      id: 'too-short',
+      description: 'Empty slides and 1-2 character placeholders',
+      action: 'exclude',
+      priority: 100,
+      match: { maxLength: 2 },
+    },
+    {
+      id: 'scripture-reference',
+      description: 'Text starting with a Bible reference, e.g. "John 3:16"',
+      action: 'exclude',
+      priority: 90,
+      match: { text: '/^\\d*\\s*[A-Za-z]+\\s+\\d+:\\d+/' },
+    },
+    {
+      id: 'announcements',
+      description: 'Welcome and notice slides, web addresses',
+      action: 'exclude',
+      priority: 90,
+      match: { text: '^(welcome|announcements?|upcoming events?|this week|next week)|www\\.|https?://' },
+    },
+    {
+      id: 'lyric-sections',
+      description: 'Groups named like song sections',
+      action: 'include',
+      priority: 50,
+      match: { group: 'verse|chorus|bridge|pre-?chorus|tag|outro|intro|hook|refrain|coda|vamp|ending|instrumental' },
+    },
+  ],
+};
+
+const ACTIONS: ClassificationAction[] = ['include', 'exclude'];
+
+function ensureConfigDir(): void {
+  if (!fs.existsSync(CONFIG_DIR)) {
+    fs.mkdirSync(CONFIG_DIR, { recursive: true });
+  }
+}
+
+/**
+ * Turn "pattern" or "/pattern/flags" into a RegExp. The g and y flags are
+ * dropped: they make test() remember where it last matched.
+ */
+export function parsePattern(pattern: string): RegExp {
+  const literal = pattern.match(/^\/(.*)\/([a-z]*)$/s);
+  return literal ? new RegExp(literal[1], literal[2].replace(/[gy]/g, '')) : new RegExp(pattern, 'i');
+}
+
+/**
+ * Check a rule set's shape and patterns. Returns a list of problems.
+ */
+export function validateClassificationRules(ruleSet: unknown): string[] {
+  const errors: string[] = [];
+  const value = ruleSet as Partial<ClassificationRuleSet> | null;
+
+  if (!value || typeof value !== 'object') {
+    return ['Rules must be a JSON object'];
+  }
+  if (!ACTIONS.includes(value.defaultAction as ClassificationAction)) {
+    errors.push('defaultAction must be "include" or "exclude"');
+  }
+  if (value.skipDisabled !== undefined && typeof value.skipDisabled !== 'boolean') {
+    errors.push('skipDisabled must be true or false');
+  }
+  if (value.skipLabels !== undefined
+    && (!Array.isArray(value.skipLabels) || value.skipLabels.some(label => typeof label !== 'string'))) {
+    errors.push('skipLabels must be a list of labels');
+  }
+  if (!Array.isArray(value.rules)) {
+    errors.push('rules must be an array');
+    return errors;
+  }
+
+  const ids = new Set<string>();
+  value.rules.forEach((rule, index) => {
+    const where = `Rule ${rule?.id ? `"${rule.id}"` : `#${index + 1}`}`;
+    if (!rule || typeof rule !== 'object') {
+      errors.push(`${where} must be an object`);
+      return;
+    }
+    if (!rule.id) errors.push(`${where} needs an id`);
+    if (rule.id && ids.has(rule.id)) errors.push(`${where} has a duplicate id`);
+    ids.add(rule.id);
+    if (!ACTIONS.includes(rule.action)) errors.push(`${where}: action must be "include" or "exclude"`);
+    if (typeof rule.priority !== 'number') errors.push(`${where}: priority must be a number`);
+    if (!rule.match || typeof rule.match !== 'object' || Object.keys(rule.match).length === 0) {
+      errors.push(`${where}: match needs at least one condition`);
+      return;
+    }
+    for (const key of ['group', 'text', 'label'] as const) {
+      const pattern = rule.match[key];
+      if (pattern === undefined) continue;
+      try {
+        parsePattern(String(pattern));
+      } catch (error: any) {
+        errors.push(`${where}: invalid ${key} pattern (${error.message})`);
+      }
+    }
+  });
+
+  return errors;
+}
+
+let cachedRules: ClassificationRuleSet | null = null;
+
+/**
+ * Load the rule set, falling back to the built-in rules
+ */
+export function loadClassificationRules(): ClassificationRuleSet {
+  if (cachedRules) return cachedRules;
+  try {
+    if (fs.existsSync(RULES_FILE)) {
+      const parsed = JSON.parse(fs.readFileSync(RULES_FILE, 'utf-8'));
+      const errors = validateClassificationRules(parsed);
+      if (errors.length === 0) {
+        cachedRules = parsed as ClassificationRuleSet;
+        return cachedRules;
+      }
+      console.warn(`Ignoring ${RULES_FILE}: ${errors[0]}`);
+    }
+  } catch (error: any) {
+    console.warn(`Ignoring ${RULES_FILE}: ${error.message}`);
+  }
+  cachedRules = DEFAULT_CLASSIFICATION_RULES;
+  return cachedRules;
+}
+
+/**
+ * Validate and save the rule set. Throws with every problem found.
+ */
+export function saveClassificationRules(ruleSet: ClassificationRuleSet): ClassificationRuleSet {
+  const errors = validateClassificationRules(ruleSet);
+  if (errors.length > 0) {
+    throw new Error(errors.join('; '));
+  }
+  ensureConfigDir();
+  fs.writeFileSync(RULES_FILE, JSON.stringify(ruleSet, null, 2), 'utf-8');
+  cachedRules = ruleSet;
+  return ruleSet;
+}
+
+/**
+ * Go back to the built-in rules by removing the rules file
+ */
+export function resetClassificationRules(): ClassificationRuleSet {
+  if (fs.existsSync(RULES_FILE)) {
+    fs.unlinkSync(RULES_FILE);
+  }
+  cachedRules = null;
+  return DEFAULT_CLASSIFICATION_RULES;
+}
+
+export function getClassificationRulesPath(): string {
+  return RULES_FILE;
+}
+
+interface CompiledRule {
+  rule: ClassificationRule;
+  group?: RegExp;
+  text?: RegExp;
+  label?: RegExp;
+}
+
+const compiledCache = new WeakMap<ClassificationRuleSet, CompiledRule[]>();
+
+function compileRules(ruleSet: ClassificationRuleSet): CompiledRule[] {
+  const cached = compiledCache.get(ruleSet);
+  if (cached) return cached;
+
+  const compiled = ruleSet.rules
+    .filter(rule => !rule.disabled)
+    // Stable sort keeps file order for equal priorities
+    .map((rule, order) => ({ rule, order }))
+    .sort((a, b) => b.rule.priority - a.rule.priority || a.order - b.order)
+    .map(({ rule }) => ({
+      rule,
+      group: rule.match.group !== undefined ? parsePattern(rule.match.group) : undefined,
+      text: rule.match.text !== undefined ? parsePattern(rule.match.text) : undefined,
+      label: rule.match.label !== undefined ? parsePattern(rule.match.label) : undefined,
+    }));
+
+  compiledCache.set(ruleSet, compiled);
+  return compiled;
+}
+
+function matches(compiled: CompiledRule, slide: SlideInfo, groupName: string, text: string): boolean {
+  const { match } = compiled.rule;
+  if (compiled.group && !compiled.group.test(groupName)) return false;
+  if (compiled.text && !compiled.text.test(text)) return false;
+  if (compiled.label && !compiled.label.test(slide.label || '')) return false;
+  if (match.slideEnabled !== undefined && (slide.enabled !== false) !== match.slideEnabled) return false;
+  if (match.minLength !== undefined && text.length < match.minLength) return false;
+  if (match.maxLength !== undefined && text.length > match.maxLength) return false;
+  return true;
+}
+
+/**
+ * Classify one slide and say which rule decided it
+ */
+export function classifySlide(
+  slide: SlideInfo,
+  groupName: string,
+  ruleSet: ClassificationRuleSet = loadClassificationRules()
+): ClassificationResult {
+  const text = slide.text.trim();
+
+  if (ruleSet.skipDisabled !== false && slide.enabled === false) {
+    return { isLyric: false, ruleId: 'skip-disabled', reason: 'Slide is disabled in ProPresenter' };
+  }
+
+  const label = (slide.label || '').trim().toLowerCase();
+  const skipLabels = ruleSet.skipLabels ?? DEFAULT_SKIP_LABELS;
+  if (label && skipLabels.some(skip => skip.trim().toLowerCase() === label)) {
+    return { isLyric: false, ruleId: 'skip-label', reason: `Label "${slide.label}" is skipped` };
+  }
+
+  for (const compiled of compileRules(ruleSet)) {
+    if (matches(compiled, slide, groupName, text)) {
+      const { rule } = compiled;
+      return {
+        isLyric: rule.action === 'include',
+        ruleId: rule.id,
+        reason: rule.description || rule.id,
+      };
+    }
+  }
+
+  return {
+    isLyric: ruleSet.defaultAction === 'include',
+    ruleId: null,
+    reason: `No rule matched (default: ${ruleSet.defaultAction})`,
+  };
+}
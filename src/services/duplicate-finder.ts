@@ -0,0 +1,253 @@
+/**
+ * Duplicate Finder
+ * Finds copies of the same song in the libraries, e.g. a hymn imported twice
+ * under slightly different titles. Each song is fingerprinted by its
+ * normalized title and lyric lines; songs are paired when their lyrics are
+ * the same or mostly the same, or when their titles match the way the
+ * Service Generator matches them and the lyrics overlap. Pairs are joined
+ * into clusters.
+ */
+
+import Fuse from 'fuse.js';
+import { createHash } from 'crypto';
+import type { ProPresenterClient } from '../propresenter-client';
+import { extractLyrics, ExtractedLyrics } from '../lyrics-extractor';
+import { normalizeSongTitle, TITLE_FUSE_OPTIONS } from './song-matcher';
+import { diffLyrics, LyricDiff } from './lyric-diff';
+import { crawlLibraries } from './library-crawler';
+
+export interface DuplicateInput {
+  library: string;
+  lyrics: ExtractedLyrics;
+}
+
+export type DuplicateReason = 'identical-lyrics' | 'similar-lyrics' | 'similar-title';
+
+export interface DuplicateSong {
+  uuid: string;
+  title: string;
+  library: string;
+  slideCount: number;
+  lyricSlideCount: number;
+  sections: string[];
+}
+
+export interface DuplicateComparison {
+  /** The song compared with the cluster's reference song */
+  uuid: string;
+  reasons: DuplicateReason[];
+  /** 0-1, from the fuzzy title match; 0 when below the title threshold */
+  titleSimilarity: number;
+  /** 0-1, share of lyric lines in common */
+  lyricSimilarity: number;
+  /** Slide and section changes going from the reference to this song */
+  changes: LyricDiff['summary'];
+  /** Distinct lyric lines only one of the two has */
+  linesOnlyInReference: number;
+  linesOnlyInThis: number;
+}
+
+export interface DuplicateCluster {
+  /** Reference first: the copy with the most lyric slides */
+  songs: DuplicateSong[];
+  comparisons: DuplicateComparison[];
+}
+
+export interface DuplicateReport {
+  songsScanned: number;
+  clusters: DuplicateCluster[];
+}
+
+export interface DuplicateOptions {
+  /** Lyric similarity at which two songs are duplicates whatever their titles (default 0.7) */
+  lyricThreshold?: number;
+  /** Title similarity for a title match (default 0.85, as in the Service Generator) */
+  titleThreshold?: number;
+  /** Lyric similarity a title match also needs (default 0.4), so same-named different songs stay apart */
+  titleLyricThreshold?: number;
+}
+
+export type DuplicateProgressEvent =
+  | { type: 'library'; name: string; presentations: number }
+  | { type: 'error'; title: string; message: string };
+
+// Lines shared by this many songs (Amen, Hallelujah) don't suggest a duplicate
+const COMMON_LINE_SONGS = 10;
+
+interface Fingerprint {
+  input: DuplicateInput;
+  title: string;
+  lines: Set<string>;
+  hash: string | null;
+}
+
+function fingerprint(input: DuplicateInput): Fingerprint {
+  const lines = new Set(
+    input.lyrics.fullText
+      .split('\n')
+      .map(normalizeSongTitle)
+      .filter(line => line.length > 0)
+  );
+  const hash = lines.size > 0
+    ? createHash('sha1').update([...lines].sort().join('\n')).digest('hex')
+    : null;
+  return { input, title: normalizeSongTitle(input.lyrics.title), lines, hash };
+}
+
+function lineSimilarity(a: Set<string>, b: Set<string>): number {
+  if (a.size === 0 || b.size === 0) return 0;
+  let shared = 0;
+  for (const line of a) {
+    if (b.has(line)) shared++;
+  }
+  return (shared * 2) / (a.size + b.size);
+}
+
+function describeSong(input: DuplicateInput): DuplicateSong {
+  const { lyrics } = input;
+  return {
+    uuid: lyrics.uuid,
+    title: lyrics.title,
+    library: input.library,
+    slideCount: lyrics.slideCount,
+    lyricSlideCount: lyrics.lyricSlideCount,
+    sections: [...new Set(lyrics.sections.map(section => section.name))],
+  };
+}
+
+/**
+ * Group songs that look like copies of each other
+ */
+export function findDuplicates(inputs: DuplicateInput[], options: DuplicateOptions = {}): DuplicateReport {
+  const lyricThreshold = options.lyricThreshold ?? 0.7;
+  const titleThreshold = options.titleThreshold ?? 0.85;
+  const titleLyricThreshold = options.titleLyricThreshold ?? 0.4;
+  const prints = inputs.map(fingerprint);
+
+  // Candidate pairs: songs sharing an uncommon line, or with similar titles
+  const candidates = new Map<string, { a: number; b: number; titleSimilarity: number }>();
+  const addCandidate = (a: number, b: number, titleSimilarity = 0) => {
+    if (a === b) return;
+    const [low, high] = a < b ? [a, b] : [b, a];
+    const key = `${low}:${high}`;
+    const existing = candidates.get(key);
+    if (!existing) {
+      candidates.set(key, { a: low, b: high, titleSimilarity });
+    } else if (titleSimilarity > existing.titleSimilarity) {
+      existing.titleSimilarity = titleSimilarity;
+    }
+  };
+
+  const songsByLine = new Map<string, number[]>();
+  prints.forEach((print, index) => {
+    for (const line of print.lines) {
+      if (!songsByLine.has(line)) songsByLine.set(line, []);
+      songsByLine.get(line)!.push(index);
+    }
+  });
+  for (const songs of songsByLine.values()) {
+    if (songs.length < 2 || songs.length > COMMON_LINE_SONGS) continue;
+    for (let i = 0; i < songs.length; i++) {
+      for (let j = i + 1; j < songs.length; j++) addCandidate(songs[i], songs[j]);
+    }
+  }
+
+  const fuse = new Fuse(prints.map((print, index) => ({ name: print.title, index })), TITLE_FUSE_OPTIONS);
+  prints.forEach((print, index) => {
+    if (!print.title) return;
+    for (const result of fuse.search(print.title)) {
+      const similarity = 1 - (result.score ?? 1);
+      if (similarity >= titleThreshold) addCandidate(index, result.item.index, similarity);
+    }
+  });
+
+  // Join duplicate pairs into clusters
+  const parent = prints.map((_, index) => index);
+  const find = (index: number): number => {
+    while (parent[index] !== index) {
+      parent[index] = parent[parent[index]];
+      index = parent[index];
+    }
+    return index;
+  };
+  const pairReasons = new Map<string, { reasons: DuplicateReason[]; titleSimilarity: number; lyricSimilarity: number }>();
+
+  for (const [key, { a, b, titleSimilarity }] of candidates) {
+    const lyricSimilarity = lineSimilarity(prints[a].lines, prints[b].lines);
+    const reasons: DuplicateReason[] = [];
+    if (prints[a].hash && prints[a].hash === prints[b].hash) reasons.push('identical-lyrics');
+    else if (lyricSimilarity >= lyricThreshold) reasons.push('similar-lyrics');
+    const emptyLyrics = prints[a].lines.size === 0 || prints[b].lines.size === 0;
+    if (titleSimilarity >= titleThreshold && (lyricSimilarity >= titleLyricThreshold || emptyLyrics)) {
+      reasons.push('similar-title');
+    }
+    if (reasons.length === 0) continue;
+    pairReasons.set(key, { reasons, titleSimilarity, lyricSimilarity });
+    parent[find(a)] = find(b);
+  }
+
+  const groups = new Map<number, number[]>();
+  prints.forEach((_, index) => {
+    const root = find(index);
+    if (!groups.has(root)) groups.set(root, []);
+    groups.get(root)!.push(index);
+  });
+
+  const clusters: DuplicateCluster[] = [];
+  for (const members of groups.values()) {
+    if (members.length < 2) continue;
+    members.sort((x, y) => prints[y].input.lyrics.lyricSlideCount - prints[x].input.lyrics.lyricSlideCount
+      || prints[x].input.lyrics.title.localeCompare(prints[y].input.lyrics.title));
+    const [reference, ...others] = members;
+    const referencePrint = prints[reference];
+
+    clusters.push({
+      songs: members.map(index => describeSong(prints[index].input)),
+      comparisons: others.map(index => {
+        const print = prints[index];
+        const [low, high] = reference < index ? [reference, index] : [index, reference];
+        const pair = pairReasons.get(`${low}:${high}`);
+        const titleSimilarity = pair?.titleSimilarity ?? 0;
+        const lyricSimilarity = pair?.lyricSimilarity ?? lineSimilarity(referencePrint.lines, print.lines);
+        return {
+          uuid: print.input.lyrics.uuid,
+          // Members joined through another copy have no direct pair with the reference
+          reasons: pair?.reasons ?? [],
+          titleSimilarity: Math.round(titleSimilarity * 100) / 100,
+          lyricSimilarity: Math.round(lyricSimilarity * 100) / 100,
+          changes: diffLyrics(referencePrint.input.lyrics, print.input.lyrics).summary,
+          linesOnlyInReference: [...referencePrint.lines].filter(line => !print.lines.has(line)).length,
+          linesOnlyInThis: [...print.lines].filter(line => !referencePrint.lines.has(line)).length,
+        };
+      }),
+    });
+  }
+
+  clusters.sort((x, y) => x.songs[0].title.localeCompare(y.songs[0].title));
+  return { songsScanned: inputs.length, clusters };
+}
+
+/**
+ * Read every song in the libraries (all by default) and look for duplicates
+ */
+export async function scanLibraryDuplicates(
+  client: ProPresenterClient,
+  libraries: string[] = [],
+  options: DuplicateOptions & { onProgress?: (event: DuplicateProgressEvent) => void } = {}
+): Promise<DuplicateReport> {
+  const inputs: DuplicateInput[] = [];
+  const seen = new Set<string>();
+  await crawlLibraries(client, {
+    libraries,
+    onLibrary: (library, presentations) => {
+      options.onProgress?.({ type: 'library', name: library.name, presentations });
+    },
+    onError: (title, error) => options.onProgress?.({ type: 'error', title, message: error.message }),
+  }, ({ library, uuid, presentation }) => {
+    // A presentation listed in two libraries is one song, not a duplicate
+    if (seen.has(uuid)) return;
+    seen.add(uuid);
+    inputs.push({ library: library.name, lyrics: extractLyrics(presentation) });
+  });
+  return findDuplicates(inputs, options);
+}
@@ -0,0 +1,80 @@
+/**
+ * Library Crawler
+ * Visits every presentation in a set of libraries. Shared by the features that
+ * need the whole library: snapshots, the lyric search index and duplicate
+ * detection.
+ */
+
+import type { LibraryInfo, PresentationInfo, ProPresenterClient } from '../propresenter-client';
+
+export interface CrawledPresentation {
+  library: LibraryInfo;
+  uuid: string;
+  name: string;
+  presentation: PresentationInfo;
+  /** 1-based position within the library */
+  position: number;
+  total: number;
+}
+
+export interface CrawlOptions {
+  /** Library UUIDs or names; all libraries when empty */
+  libraries?: string[];
+  /** Called before a library's presentations are visited */
+  onLibrary?: (library: LibraryInfo, presentations: number) => void;
+  /** Called for presentations that could not be read; the crawl carries on */
+  onError?: (name: string, error: Error) => void;
+}
+
+/**
+ * Libraries matching the given UUIDs or names (case-insensitive), or all of them
+ */
+export async function resolveLibraries(client: ProPresenterClient, wanted: string[] = []): Promise<LibraryInfo[]> {
+  const keys = wanted.map(value => value.trim().toLowerCase()).filter(Boolean);
+  const libraries = (await client.getLibraries()).filter(library => keys.length === 0
+    || keys.includes(library.uuid.toLowerCase())
+    || keys.includes(library.name.trim().toLowerCase()));
+
+  if (keys.length > 0 && libraries.length === 0) {
+    throw new Error(`No library matching ${wanted.map(value => `"${value}"`).join(' or ')}`);
+  }
+  return libraries;
+}
+
+/**
+ * Fetch each presentation of the libraries in turn and hand it to visit.
+ * Returns the libraries crawled.
+ */
+export async function crawlLibraries(
+  client: ProPresenterClient,
+  options: CrawlOptions,
+  visit: (item: CrawledPresentation) => void | Promise<void>
+): Promise<LibraryInfo[]> {
+  const libraries = await resolveLibraries(client, options.libraries);
+
+  for (const library of libraries) {
+    const presentations = await client.getLibraryPresentations(library.uuid);
+    options.onLibrary?.(library, presentations.length);
+
+    for (const [index, item] of presentations.entries()) {
+      try {
+        const presentation = await client.getPresentationByUuid(item.uuid);
+        if (!presentation) {
+          throw new Error('presentation not found');
+        }
+        await visit({
+          library,
+          uuid: item.uuid,
+          name: item.name,
+          presentation,
+          position: index + 1,
+          total: presentations.length,
+        });
+      } catch (error: any) {
+        options.onError?.(item.name, error);
+      }
+    }
+  }
+
+  return libraries;
+}
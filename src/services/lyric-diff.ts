@@ -0,0 +1,391 @@
+/**
+ * Lyric Diff - Compare two versions of a song's lyrics
+ *
+ * Works on extracted lyrics, so either side can come from ProPresenter, a
+ * .pro file or a saved JSON export. Only lyric slides are compared. Sections
+ * are paired by name; sections that disappear on one side and appear on the
+ * other with mostly the same lines count as renamed.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { extractLyrics, ExtractedLyrics } from '../lyrics-extractor';
+import { readProFile } from './pro-file-reader';
+
+export type SlideChangeType = 'added' | 'removed' | 'changed';
+
+export interface SlideChange {
+  type: SlideChangeType;
+  /** Section name on the newer side (the older name for removed sections) */
+  section: string;
+  /** 1-based position among the section's lyric slides on each side */
+  beforeIndex?: number;
+  afterIndex?: number;
+  before?: string;
+  after?: string;
+}
+
+export interface SectionRename {
+  from: string;
+  to: string;
+}
+
+export type SectionStatus = 'unchanged' | 'changed' | 'added' | 'removed' | 'renamed';
+
+export interface SectionDiff {
+  name: string;
+  /** Set when the section was renamed */
+  previousName?: string;
+  status: SectionStatus;
+  changes: SlideChange[];
+}
+
+export interface LyricDiffSide {
+  title: string;
+  uuid: string;
+  /** Where this version came from, e.g. "ProPresenter" or a file name */
+  label: string;
+}
+
+export interface LyricDiff {
+  before: LyricDiffSide;
+  after: LyricDiffSide;
+  identical: boolean;
+  summary: {
+    slidesAdded: number;
+    slidesRemoved: number;
+    slidesChanged: number;
+    sectionsAdded: number;
+    sectionsRemoved: number;
+    sectionsRenamed: number;
+  };
+  renames: SectionRename[];
+  sections: SectionDiff[];
+}
+
+// Share of lines two sections must have in common to count as a rename
+const RENAME_SIMILARITY = 0.5;
+
+interface SectionSlides {
+  name: string;
+  slides: string[];
+}
+
+type SequenceOp<T> =
+  | { type: 'same'; before: T; after: T; beforeIndex: number; afterIndex: number }
+  | { type: 'remove'; before: T; beforeIndex: number }
+  | { type: 'add'; after: T; afterIndex: number };
+
+/**
+ * Longest-common-subsequence diff of two short lists
+ */
+function diffSequences<T>(before: T[], after: T[], equal: (a: T, b: T) => boolean): SequenceOp<T>[] {
+  const rows = before.length + 1;
+  const cols = after.length + 1;
+  const lengths: number[][] = Array.from({ length: rows }, () => new Array(cols).fill(0));
+
+  for (let i = before.length - 1; i >= 0; i--) {
+    for (let j = after.length - 1; j >= 0; j--) {
+      lengths[i][j] = equal(before[i], after[j])
+        ? lengths[i + 1][j + 1] + 1
+        : Math.max(lengths[i + 1][j], lengths[i][j + 1]);
+    }
+  }
+
+  const ops: SequenceOp<T>[] = [];
+  let i = 0;
+  let j = 0;
+  while (i < before.length && j < after.length) {
+    if (equal(before[i], after[j])) {
+      ops.push({ type: 'same', before: before[i], after: after[j], beforeIndex: i, afterIndex: j });
+      i++;
+      j++;
+    } else if (lengths[i + 1][j] >= lengths[i][j + 1]) {
+      ops.push({ type: 'remove', before: before[i], beforeIndex: i });
+      i++;
+    } else {
+      ops.push({ type: 'add', after: after[j], afterIndex: j });
+      j++;
+    }
+  }
+  for (; i < before.length; i++) ops.push({ type: 'remove', before: before[i], beforeIndex: i });
+  for (; j < after.length; j++) ops.push({ type: 'add', after: after[j], afterIndex: j });
+  return ops;
+}
+
+function normalizeLine(line: string): string {
+  return line.trim().replace(/\s+/g, ' ').toLowerCase();
+}
+
+function sameSlide(a: string, b: string): boolean {
+  return a.split('\n').map(normalizeLine).join('\n') === b.split('\n').map(normalizeLine).join('\n');
+}
+
+/**
+ * Lyric slides per section. A section repeated by the arrangement is compared once.
+ */
+function collectSections(lyrics: ExtractedLyrics): SectionSlides[] {
+  const seen = new Set<string>();
+  const sections: SectionSlides[] = [];
+  for (const section of lyrics.sections) {
+    const key = section.name.trim().toLowerCase();
+    if (seen.has(key)) continue;
+    seen.add(key);
+    sections.push({
+      name: section.name,
+      slides: section.slides.filter(slide => slide.isLyric && slide.text.trim()).map(slide => slide.text),
+    });
+  }
+  return sections;
+}
+
+/**
+ * Dice coefficient over the sections' non-empty lines
+ */
+function sectionSimilarity(a: SectionSlides, b: SectionSlides): number {
+  const lines = (section: SectionSlides) => section.slides
+    .flatMap(slide => slide.split('\n'))
+    .map(normalizeLine)
+    .filter(Boolean);
+  const left = lines(a);
+  const right = lines(b);
+  if (left.length === 0 && right.length === 0) return 1;
+
+  const remaining = new Map<string, number>();
+  for (const line of right) remaining.set(line, (remaining.get(line) ?? 0) + 1);
+  let shared = 0;
+  for (const line of left) {
+    const count = remaining.get(line) ?? 0;
+    if (count > 0) {
+      shared++;
+      remaining.set(line, count - 1);
+    }
+  }
+  return (shared * 2) / (left.length + right.length);
+}
+
+/**
+ * Slide changes within a pair of sections. A removal directly followed by an
+ * addition is reported as one changed slide.
+ */
+function diffSectionSlides(before: string[], after: string[], section: string): SlideChange[] {
+  const ops = diffSequences(before, after, sameSlide);
+  const changes: SlideChange[] = [];
+
+  let k = 0;
+  while (k < ops.length) {
+    if (ops[k].type === 'same') {
+      k++;
+      continue;
+    }
+    const removed: Extract<SequenceOp<string>, { type: 'remove' }>[] = [];
+    const added: Extract<SequenceOp<string>, { type: 'add' }>[] = [];
+    while (k < ops.length && ops[k].type !== 'same') {
+      const op = ops[k];
+      if (op.type === 'remove') removed.push(op);
+      if (op.type === 'add') added.push(op);
+      k++;
+    }
+    const paired = Math.min(removed.length, added.length);
+    for (let n = 0; n < paired; n++) {
+      changes.push({
+        type: 'changed',
+        section,
+        beforeIndex: removed[n].beforeIndex + 1,
+        afterIndex: added[n].afterIndex + 1,
+        before: removed[n].before,
+        after: added[n].after,
+      });
+    }
+    for (const op of removed.slice(paired)) {
+      changes.push({ type: 'removed', section, beforeIndex: op.beforeIndex + 1, before: op.before });
+    }
+    for (const op of added.slice(paired)) {
+      changes.push({ type: 'added', section, afterIndex: op.afterIndex + 1, after: op.after });
+    }
+  }
+
+  return changes;
+}
+
+/**
+ * Compare two versions of a song
+ */
+export function diffLyrics(
+  before: ExtractedLyrics,
+  after: ExtractedLyrics,
+  labels: { before?: string; after?: string } = {}
+): LyricDiff {
+  const beforeSections = collectSections(before);
+  const afterSections = collectSections(after);
+  const key = (section: SectionSlides) => section.name.trim().toLowerCase();
+
+  // Pair sections by name, then pair leftovers with similar content as renames
+  const pairs = new Map<SectionSlides, SectionSlides>();
+  const unmatchedBefore = beforeSections.filter(section => {
+    const match = afterSections.find(candidate => key(candidate) === key(section));
+    if (match) pairs.set(match, section);
+    return !match;
+  });
+  const unmatchedAfter = afterSections.filter(section => !pairs.has(section));
+
+  const candidates = unmatchedBefore
+    .flatMap(old => unmatchedAfter.map(renamed => ({ old, renamed, score: sectionSimilarity(old, renamed) })))
+    .filter(candidate => candidate.score >= RENAME_SIMILARITY)
+    .sort((a, b) => b.score - a.score);
+  const renamedFrom = new Map<SectionSlides, SectionSlides>();
+  for (const { old, renamed } of candidates) {
+    if (renamedFrom.has(renamed) || [...renamedFrom.values()].includes(old)) continue;
+    renamedFrom.set(renamed, old);
+  }
+
+  const sections: SectionDiff[] = [];
+  for (const section of afterSections) {
+    const previous = pairs.get(section) ?? renamedFrom.get(section);
+    if (!previous) {
+      sections.push({
+        name: section.name,
+        status: 'added',
+        changes: section.slides.map((text, index) => ({
+          type: 'added' as const, section: section.name, afterIndex: index + 1, after: text,
+        })),
+      });
+      continue;
+    }
+
+    const changes = diffSectionSlides(previous.slides, section.slides, section.name);
+    const renamed = renamedFrom.has(section);
+    sections.push({
+      name: section.name,
+      previousName: renamed ? previous.name : undefined,
+      status: renamed ? 'renamed' : changes.length > 0 ? 'changed' : 'unchanged',
+      changes,
+    });
+  }
+
+  const used = new Set([...pairs.values(), ...renamedFrom.values()]);
+  for (const section of beforeSections) {
+    if (used.has(section)) continue;
+    sections.push({
+      name: section.name,
+      status: 'removed',
+      changes: section.slides.map((text, index) => ({
+        type: 'removed' as const, section: section.name, beforeIndex: index + 1, before: text,
+      })),
+    });
+  }
+
+  const allChanges = sections.flatMap(section => section.changes);
+  const count = (type: SlideChangeType) => allChanges.filter(change => change.type === type).length;
+  const countSections = (status: SectionStatus) => sections.filter(section => section.status === status).length;
+  const renames = sections
+    .filter(section => section.previousName)
+    .map(section => ({ from: section.previousName!, to: section.name }));
+
+  return {
+    before: { title: before.title, uuid: before.uuid, label: labels.before || 'before' },
+    after: { title: after.title, uuid: after.uuid, label: labels.after || 'after' },
+    identical: allChanges.length === 0 && renames.length === 0,
+    summary: {
+      slidesAdded: count('added'),
+      slidesRemoved: count('removed'),
+      slidesChanged: count('changed'),
+      sectionsAdded: countSections('added'),
+      sectionsRemoved: countSections('removed'),
+      sectionsRenamed: renames.length,
+    },
+    renames,
+    sections,
+  };
+}
+
+function slideLines(change: SlideChange): string[] {
+  const before = change.before ? change.before.split('\n') : [];
+  const after = change.after ? change.after.split('\n') : [];
+  return diffSequences(before, after, (a, b) => normalizeLine(a) === normalizeLine(b)).map(op => {
+    if (op.type === 'same') return ` ${op.after}`;
+    if (op.type === 'remove') return `-${op.before}`;
+    return `+${op.after}`;
+  });
+}
+
+function hunkHeader(section: SectionDiff, change?: SlideChange): string {
+  const name = section.previousName ? `${section.previousName} → ${section.name}` : section.name;
+  if (!change) return `@@ ${name} (renamed) @@`;
+  const position = change.type === 'removed'
+    ? `slide ${change.beforeIndex}`
+    : change.type === 'added' || change.beforeIndex === change.afterIndex
+      ? `slide ${change.afterIndex}`
+      : `slide ${change.beforeIndex} → ${change.afterIndex}`;
+  const status = section.status === 'added' || section.status === 'removed' ? ` (section ${section.status})` : '';
+  return `@@ ${name}, ${position} ${change.type}${status} @@`;
+}
+
+/**
+ * Render a diff as unified-style text: one hunk per changed slide, with the
+ * slide's lines prefixed by " ", "-" or "+"
+ */
+export function formatLyricDiff(diff: LyricDiff): string {
+  const lines = [
+    `--- ${diff.before.title} (${diff.before.label})`,
+    `+++ ${diff.after.title} (${diff.after.label})`,
+  ];
+
+  if (diff.identical) {
+    lines.push('', 'No lyric changes.');
+    return lines.join('\n');
+  }
+
+  for (const section of diff.sections) {
+    if (section.status === 'renamed' && section.changes.length === 0) {
+      lines.push(hunkHeader(section));
+    }
+    for (const change of section.changes) {
+      lines.push(hunkHeader(section, change), ...slideLines(change));
+    }
+  }
+
+  const { summary } = diff;
+  lines.push(
+    '',
+    `${summary.slidesChanged} changed, ${summary.slidesAdded} added, ${summary.slidesRemoved} removed slides; `
+      + `${summary.sectionsRenamed} renamed, ${summary.sectionsAdded} added, ${summary.sectionsRemoved} removed sections`
+  );
+  return lines.join('\n');
+}
+
+/**
+ * Load one song's lyrics from a .pro file or a JSON export (a single song or
+ * the list written by the json format). With several songs in the JSON, the
+ * one matching the uuid or title is used.
+ */
+export function loadLyricsFile(filePath: string, prefer: { uuid?: string; title?: string } = {}): ExtractedLyrics {
+  const ext = path.extname(filePath).toLowerCase();
+  if (ext === '.pro') {
+    return extractLyrics(readProFile(filePath));
+  }
+  if (ext !== '.json') {
+    throw new Error(`Cannot compare ${filePath}: use a .pro file or a JSON lyrics export`);
+  }
+
+  const parsed = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+  const songs: ExtractedLyrics[] = (Array.isArray(parsed) ? parsed : [parsed])
+    .filter(song => song && Array.isArray(song.sections));
+  if (songs.length === 0) {
+    throw new Error(`${filePath} does not contain exported lyrics`);
+  }
+  const match = songs.find(song => prefer.uuid && song.uuid === prefer.uuid)
+    ?? songs.find(song => prefer.title && song.title.toLowerCase() === prefer.title.toLowerCase());
+  if (match) return match;
+  if (songs.length > 1) {
+    throw new Error(`${filePath} contains ${songs.length} songs and none matches "${prefer.title || prefer.uuid || ''}"`);
+  }
+  return songs[0];
+}
+
+/**
+ * Whether a diff argument names a file rather than a presentation UUID
+ */
+export function isLyricsFile(arg: string): boolean {
+  return ['.pro', '.json'].includes(path.extname(arg).toLowerCase());
+}
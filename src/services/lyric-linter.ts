@@ -0,0 +1,518 @@
+/**
+ * Lyric Linter
+ * Checks songs for the problems that show up on screen on a Sunday: typos,
+ * inconsistent capitalization, stray punctuation at line ends, lines too long
+ * for the slide, slides with too many lines, and empty or unnamed groups.
+ *
+ * Spelling uses the system word list (or one given), the words already used
+ * across the indexed libraries, and ~/.propresenter-words/lint-words.txt for
+ * names and words the dictionary doesn't know.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import type { ExtractedLyrics } from '../lyrics-extractor';
+import { normalizeForSearch, indexedVocabulary, getLyricIndexPath } from './lyric-search';
+
+export type LintRule =
+  | 'spelling'
+  | 'capitalization'
+  | 'trailing-punctuation'
+  | 'long-line'
+  | 'too-many-lines'
+  | 'empty-group'
+  | 'unnamed-group';
+
+export const LINT_RULES: LintRule[] = [
+  'spelling',
+  'capitalization',
+  'trailing-punctuation',
+  'long-line',
+  'too-many-lines',
+  'empty-group',
+  'unnamed-group',
+];
+
+export type LintSeverity = 'warning' | 'info';
+
+export interface LintIssue {
+  rule: LintRule;
+  severity: LintSeverity;
+  section: string;
+  /** 1-based slide within the group; absent for group-level issues */
+  slide?: number;
+  /** 1-based line within the slide */
+  line?: number;
+  /** The offending line */
+  text?: string;
+  message: string;
+  suggestion?: string;
+}
+
+export interface SongLintResult {
+  uuid: string;
+  title: string;
+  issues: LintIssue[];
+}
+
+export interface LintReport {
+  songs: SongLintResult[];
+  issueCount: number;
+  counts: Partial<Record<LintRule, number>>;
+  /** Word list used for spelling; null when spelling was not checked */
+  dictionary: string | null;
+  /** Why spelling was asked for but not checked, e.g. no word list on this machine */
+  spellingUnavailable?: string;
+}
+
+export interface LintOptions {
+  /** Characters per line (default 40) */
+  maxLineLength?: number;
+  /** Lines per slide (default 4) */
+  maxLines?: number;
+  /** Rules to run; all by default */
+  rules?: LintRule[];
+  /** Loaded with loadLintDictionary() when omitted; null skips spelling */
+  dictionary?: LintDictionary | null;
+}
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const CUSTOM_WORDS_FILE = path.join(CONFIG_DIR, 'lint-words.txt');
+const SYSTEM_WORD_LISTS = ['/usr/share/dict/words', '/usr/dict/words'];
+const NO_WORD_LIST = 'No word list found (Windows and the packaged apps have none); set one with dictionary=<file>';
+
+const DEFAULT_MAX_LINE_LENGTH = 40;
+const DEFAULT_MAX_LINES = 4;
+// Library words used in this many songs count as spelled right
+const VOCABULARY_MIN_SONGS = 2;
+
+const SEVERITY: Record<LintRule, LintSeverity> = {
+  'spelling': 'warning',
+  'capitalization': 'info',
+  'trailing-punctuation': 'info',
+  'long-line': 'warning',
+  'too-many-lines': 'warning',
+  'empty-group': 'warning',
+  'unnamed-group': 'warning',
+};
+
+// Endings tried when a word isn't listed itself (many word lists have no plurals)
+const SUFFIXES: Array<[string, string]> = [
+  ['ies', 'y'], ['es', ''], ['s', ''], ['ied', 'y'], ['ed', 'e'], ['ed', ''], ['d', ''],
+  ['ing', 'e'], ['ing', ''], ['in', 'ing'], ['eth', ''], ['eth', 'e'], ['est', ''],
+  ['er', ''], ['ly', ''], ['ness', ''],
+];
+
+function ensureConfigDir(): void {
+  if (!fs.existsSync(CONFIG_DIR)) {
+    fs.mkdirSync(CONFIG_DIR, { recursive: true });
+  }
+}
+
+function readWordList(filePath: string): string[] {
+  return fs.readFileSync(filePath, 'utf-8')
+    .split(/\r?\n/)
+    .map(word => normalizeForSearch(word))
+    .filter(word => word.length > 0 && !word.includes(' '));
+}
+
+function editDistance(a: string, b: string, limit: number): number {
+  if (Math.abs(a.length - b.length) > limit) return limit + 1;
+  let previous = Array.from({ length: b.length + 1 }, (_, j) => j);
+  for (let i = 1; i <= a.length; i++) {
+    const current = [i];
+    let rowMin = i;
+    for (let j = 1; j <= b.length; j++) {
+      const cost = a[i - 1] === b[j - 1] ? 0 : 1;
+      current[j] = Math.min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + cost);
+      rowMin = Math.min(rowMin, current[j]);
+    }
+    if (rowMin > limit) return limit + 1;
+    previous = current;
+  }
+  return previous[b.length];
+}
+
+/**
+ * Known words for the spelling check
+ */
+export class LintDictionary {
+  private words: Set<string>;
+  private vocabulary: Set<string>;
+  private byInitial?: Map<string, string[]>;
+
+  /** source describes where the words came from, for the report */
+  constructor(public readonly source: string, words: Iterable<string>, vocabulary: Iterable<string> = []) {
+    this.words = new Set(words);
+    this.vocabulary = new Set(vocabulary);
+  }
+
+  has(word: string): boolean {
+    const normalized = normalizeForSearch(word);
+    if (this.knows(normalized)) return true;
+    return SUFFIXES.some(([suffix, replacement]) => normalized.length > suffix.length + 2
+      && normalized.endsWith(suffix)
+      && this.knows(normalized.slice(0, -suffix.length) + replacement));
+  }
+
+  /**
+   * Closest known word, preferring words the libraries already use
+   */
+  suggest(word: string): string | undefined {
+    const normalized = normalizeForSearch(word);
+    const limit = normalized.length <= 5 ? 1 : 2;
+    const closest = (candidates: Iterable<string>): string | undefined => {
+      let best: { word: string; distance: number } | undefined;
+      for (const candidate of candidates) {
+        const distance = editDistance(normalized, candidate, limit);
+        if (distance <= limit && (!best || distance < best.distance)) best = { word: candidate, distance };
+      }
+      return best?.word;
+    };
+
+    // Typos rarely change the first letter, which keeps this quick on big word lists
+    return closest(this.vocabulary) ?? closest(this.wordsStartingWith(normalized[0]));
+  }
+
+  private wordsStartingWith(initial: string): string[] {
+    if (!this.byInitial) {
+      this.byInitial = new Map();
+      for (const word of this.words) {
+        const list = this.byInitial.get(word[0]);
+        if (list) list.push(word);
+        else this.byInitial.set(word[0], [word]);
+      }
+    }
+    return this.byInitial.get(initial) ?? [];
+  }
+
+  private knows(normalized: string): boolean {
+    return this.words.has(normalized) || this.vocabulary.has(normalized);
+  }
+}
+
+// Last dictionary loaded, reused until one of its files changes (the web app lints per request)
+let cachedDictionary: { key: string; dictionary: LintDictionary } | null = null;
+
+function modifiedTime(filePath: string): number {
+  return fs.existsSync(filePath) ? fs.statSync(filePath).mtimeMs : 0;
+}
+
+/**
+ * Load the dictionary: a word list (the system one by default), the lyric
+ * index vocabulary and custom words. Returns null when there is no word list.
+ */
+export function loadLintDictionary(wordListPath?: string): LintDictionary | null {
+  const listPath = wordListPath ?? SYSTEM_WORD_LISTS.find(candidate => fs.existsSync(candidate));
+  if (!listPath) {
+    return null;
+  }
+  if (!fs.existsSync(listPath)) {
+    throw new Error(`Word list not found: ${listPath}`);
+  }
+
+  const key = [listPath, ...[listPath, CUSTOM_WORDS_FILE, getLyricIndexPath()].map(modifiedTime)].join('|');
+  if (cachedDictionary?.key === key) {
+    return cachedDictionary.dictionary;
+  }
+
+  const words = readWordList(listPath);
+  if (fs.existsSync(CUSTOM_WORDS_FILE)) {
+    words.push(...readWordList(CUSTOM_WORDS_FILE));
+  }
+  const dictionary = new LintDictionary(listPath, words, indexedVocabulary(VOCABULARY_MIN_SONGS));
+  cachedDictionary = { key, dictionary };
+  return dictionary;
+}
+
+/**
+ * Add words to the custom list so the spelling check accepts them
+ */
+export function addLintWords(words: string[]): string[] {
+  const existing = fs.existsSync(CUSTOM_WORDS_FILE) ? readWordList(CUSTOM_WORDS_FILE) : [];
+  const added = words
+    .map(word => word.trim())
+    .filter(word => word && !existing.includes(normalizeForSearch(word)));
+  if (added.length > 0) {
+    ensureConfigDir();
+    fs.appendFileSync(CUSTOM_WORDS_FILE, added.map(word => `${word}\n`).join(''), 'utf-8');
+  }
+  return added;
+}
+
+export function getLintWordsPath(): string {
+  return CUSTOM_WORDS_FILE;
+}
+
+interface LintLine {
+  section: string;
+  slide: number;
+  line: number;
+  text: string;
+}
+
+function wordsOf(text: string): string[] {
+  return text.match(/[\p{L}][\p{L}'’]*/gu) || [];
+}
+
+function checkSpelling(lines: LintLine[], dictionary: LintDictionary, add: (issue: Omit<LintIssue, 'severity'>) => void): void {
+  const unknown = new Map<string, { line: LintLine; word: string; count: number }>();
+  for (const line of lines) {
+    for (const word of wordsOf(line.text)) {
+      // Short acronyms (CCLI, UK) and single letters aren't worth checking
+      if (word.length < 2 || (word.length <= 4 && word === word.toUpperCase())) continue;
+      if (dictionary.has(word)) continue;
+      const key = normalizeForSearch(word);
+      const entry = unknown.get(key);
+      if (entry) entry.count++;
+      else unknown.set(key, { line, word, count: 1 });
+    }
+  }
+
+  for (const { line, word, count } of unknown.values()) {
+    const suggestion = dictionary.suggest(word);
+    add({
+      rule: 'spelling',
+      section: line.section,
+      slide: line.slide,
+      line: line.line,
+      text: line.text,
+      message: `"${word}" is not in the dictionary${count > 1 ? ` (${count} times)` : ''}`,
+      suggestion,
+    });
+  }
+}
+
+function checkCapitalization(lines: LintLine[], add: (issue: Omit<LintIssue, 'severity'>) => void): void {
+  // Line starts: follow whichever style most lines use
+  const starts = lines
+    .map(line => ({ line, first: line.text.match(/\p{L}/u)?.[0] }))
+    .filter((entry): entry is { line: LintLine; first: string } => !!entry.first);
+  const upper = starts.filter(entry => entry.first !== entry.first.toLowerCase());
+  const lower = starts.filter(entry => entry.first === entry.first.toLowerCase());
+  const oddStarts = upper.length >= lower.length ? lower : upper;
+  if (upper.length > 0 && lower.length > 0) {
+    for (const { line } of oddStarts) {
+      add({
+        rule: 'capitalization',
+        section: line.section,
+        slide: line.slide,
+        line: line.line,
+        text: line.text,
+        message: oddStarts === lower
+          ? `Line starts in lowercase; ${upper.length} other lines start with a capital`
+          : `Line starts with a capital; ${lower.length} other lines start in lowercase`,
+      });
+    }
+  }
+
+  // Words inside lines written both ways, e.g. "You" and "you"
+  const forms = new Map<string, Map<string, LintLine[]>>();
+  for (const line of lines) {
+    for (const word of wordsOf(line.text).slice(1)) {
+      if (word === 'I' || word.startsWith('I\'') || word.startsWith('I’')) continue;
+      if (word.length > 1 && word === word.toUpperCase()) continue;
+      const key = word.toLowerCase();
+      if (!forms.has(key)) forms.set(key, new Map());
+      const seen = forms.get(key)!;
+      if (!seen.has(word)) seen.set(word, []);
+      seen.get(word)!.push(line);
+    }
+  }
+  for (const seen of forms.values()) {
+    if (seen.size < 2) continue;
+    const ranked = [...seen].sort((a, b) => b[1].length - a[1].length);
+    const [usual, usualLines] = ranked[0];
+    for (const [form, formLines] of ranked.slice(1)) {
+      const line = formLines[0];
+      add({
+        rule: 'capitalization',
+        section: line.section,
+        slide: line.slide,
+        line: line.line,
+        text: line.text,
+        message: `"${form}" is written "${usual}" elsewhere in the song (${usualLines.length}×)`
+          + (formLines.length > 1 ? `; ${formLines.length} lines use "${form}"` : ''),
+        suggestion: usual,
+      });
+    }
+  }
+}
+
+/**
+ * Lint options from string settings (CLI --option or query parameters):
+ * max-line, max-lines, rules (comma-separated) and dictionary (word list path)
+ */
+export function parseLintOptions(raw: Record<string, string | undefined>): LintOptions {
+  const options: LintOptions = {};
+  const number = (value: string | undefined, name: string) => {
+    const parsed = parseInt(value ?? '', 10);
+    if (!(parsed > 0)) throw new Error(`${name} must be a positive number`);
+    return parsed;
+  };
+
+  if (raw['max-line']) options.maxLineLength = number(raw['max-line'], 'max-line');
+  if (raw['max-lines']) options.maxLines = number(raw['max-lines'], 'max-lines');
+  if (raw.rules) {
+    const rules = raw.rules.split(',').map(rule => rule.trim()).filter(Boolean);
+    const unknown = rules.filter(rule => !LINT_RULES.includes(rule as LintRule));
+    if (unknown.length > 0) {
+      throw new Error(`Unknown lint rule: ${unknown.join(', ')} (rules: ${LINT_RULES.join(', ')})`);
+    }
+    options.rules = rules as LintRule[];
+  }
+  if (raw.dictionary) {
+    options.dictionary = loadLintDictionary(raw.dictionary);
+  }
+  return options;
+}
+
+/**
+ * Check one song
+ */
+export function lintLyrics(lyrics: ExtractedLyrics, options: LintOptions = {}): SongLintResult {
+  const rules = new Set(options.rules ?? LINT_RULES);
+  const maxLineLength = options.maxLineLength ?? DEFAULT_MAX_LINE_LENGTH;
+  const maxLines = options.maxLines ?? DEFAULT_MAX_LINES;
+  const issues: LintIssue[] = [];
+  const add = (issue: Omit<LintIssue, 'severity'>) => {
+    if (rules.has(issue.rule)) issues.push({ ...issue, severity: SEVERITY[issue.rule] });
+  };
+
+  const lines: LintLine[] = [];
+  // Arrangements can repeat a group; check each group once
+  const checked = new Set<string>();
+  for (const section of lyrics.sections) {
+    if (checked.has(section.name)) continue;
+    checked.add(section.name);
+
+    if (!section.name.trim() || section.name.trim().toLowerCase() === 'unnamed group') {
+      add({ rule: 'unnamed-group', section: section.name, message: 'Group has no name' });
+    }
+    const blank = section.slides.every(slide => !slide.text.trim());
+    if (blank && !/blank/i.test(section.name)) {
+      add({
+        rule: 'empty-group',
+        section: section.name,
+        message: section.slides.length === 0 ? 'Group has no slides' : 'Every slide in the group is blank',
+      });
+    }
+
+    section.slides.forEach((slide, position) => {
+      if (!slide.isLyric || slide.enabled === false) return;
+      // Bilingual slides: only the first language is checked
+      const text = slide.elements && slide.elements.length > 1 ? slide.elements[0] : slide.text;
+      const slideLines = text.split('\n');
+      const filled = slideLines.filter(line => line.trim()).length;
+      if (filled > maxLines) {
+        add({
+          rule: 'too-many-lines',
+          section: section.name,
+          slide: position + 1,
+          message: `Slide has ${filled} lines (max ${maxLines})`,
+        });
+      }
+
+      slideLines.forEach((raw, index) => {
+        const lineText = raw.trim();
+        if (!lineText) return;
+        const line: LintLine = { section: section.name, slide: position + 1, line: index + 1, text: lineText };
+        lines.push(line);
+
+        if (lineText.length > maxLineLength) {
+          add({ ...line, rule: 'long-line', message: `Line is ${lineText.length} characters (max ${maxLineLength})` });
+        }
+        const ending = lineText.match(/[,.;:]$/)?.[0];
+        if (ending && /\p{L}/u.test(lineText)) {
+          add({
+            ...line,
+            rule: 'trailing-punctuation',
+            message: `Line ends with "${lineText.endsWith('...') ? '...' : ending}"`,
+          });
+        }
+      });
+    });
+  }
+
+  if (rules.has('capitalization')) {
+    checkCapitalization(lines, add);
+  }
+  if (rules.has('spelling') && options.dictionary) {
+    checkSpelling(lines, options.dictionary, add);
+  }
+
+  const order = (issue: LintIssue) => [...checked].indexOf(issue.section) * 1e6 + (issue.slide ?? 0) * 1e3 + (issue.line ?? 0);
+  issues.sort((a, b) => order(a) - order(b));
+  return { uuid: lyrics.uuid, title: lyrics.title, issues };
+}
+
+/**
+ * Check a set of songs, e.g. a playlist or library
+ */
+export function lintSongs(songs: ExtractedLyrics[], options: LintOptions = {}): LintReport {
+  const rules = options.rules ?? LINT_RULES;
+  const dictionary = options.dictionary !== undefined
+    ? options.dictionary
+    : rules.includes('spelling') ? loadLintDictionary() : null;
+
+  const results = songs.map(song => lintLyrics(song, { ...options, dictionary }));
+  const counts: Partial<Record<LintRule, number>> = {};
+  for (const issue of results.flatMap(result => result.issues)) {
+    counts[issue.rule] = (counts[issue.rule] ?? 0) + 1;
+  }
+
+  const report: LintReport = {
+    songs: results,
+    issueCount: results.reduce((total, result) => total + result.issues.length, 0),
+    counts,
+    dictionary: rules.includes('spelling') && dictionary ? dictionary.source : null,
+  };
+  if (rules.includes('spelling') && !dictionary) {
+    report.spellingUnavailable = NO_WORD_LIST;
+  }
+  return report;
+}
+
+/**
+ * Plain-text report, grouped by song and slide
+ */
+export function formatLintReport(report: LintReport, scope: string): string {
+  const out: string[] = [];
+  const flagged = report.songs.filter(song => song.issues.length > 0);
+  out.push(`Lint: ${scope} (${report.issueCount} issues in ${flagged.length} of ${report.songs.length} songs)`);
+  out.push('='.repeat(60));
+
+  for (const song of flagged) {
+    out.push('');
+    out.push(`${song.title} (${song.issues.length})`);
+    let lastPlace = '';
+    for (const issue of song.issues) {
+      const place = [issue.section || '(no name)', issue.slide ? `slide ${issue.slide}` : '', issue.line ? `line ${issue.line}` : '']
+        .filter(Boolean)
+        .join(' · ');
+      if (place !== lastPlace) {
+        out.push(`  ${place}`);
+        if (issue.text) out.push(`    > ${issue.text}`);
+        lastPlace = place;
+      }
+      const icon = issue.severity === 'warning' ? '⚠' : 'ℹ';
+      const suggestion = issue.suggestion ? ` (did you mean "${issue.suggestion}"?)` : '';
+      out.push(`    ${icon} ${issue.rule}: ${issue.message}${suggestion}`);
+    }
+  }
+
+  out.push('');
+  const clean = report.songs.length - flagged.length;
+  if (clean > 0) out.push(`✓ ${clean} songs have no issues`);
+  const totals = Object.entries(report.counts).map(([rule, count]) => `${rule} ${count}`);
+  if (totals.length > 0) out.push(`Issues: ${totals.join(', ')}`);
+  if (report.dictionary) {
+    out.push(`Spelling checked against ${report.dictionary} and ${CUSTOM_WORDS_FILE}`);
+  } else if (report.spellingUnavailable) {
+    out.push(`⚠ Spelling not checked: ${report.spellingUnavailable}`);
+  } else {
+    out.push('Spelling not checked (rule off)');
+  }
+  return out.join('\n');
+}
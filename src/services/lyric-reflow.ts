@@ -0,0 +1,174 @@
+/**
+ * Lyric Reflow - Export-time line breaking
+ *
+ * ProPresenter line breaks suit the projector, not every other use. Reflow
+ * rewrites the lyric slides of extracted songs before they are exported:
+ *
+ *   paragraph  each slide becomes one line, for printed bulletins
+ *   balance    each slide is re-wrapped into even lines of about a target width
+ *   split      long lines are wrapped and slides cut into slides of a few
+ *              lines, for lower thirds
+ *
+ * Only whitespace changes, so formatted runs are carried over.
+ */
+
+import type { ExtractedLyrics, LyricSlide } from '../lyrics-extractor';
+import type { TextRun } from '../propresenter-client';
+import { divideLines } from './text-fit';
+import { partitionRuns, restyler } from '../utils/text-runs';
+
+export type ReflowMode = 'none' | 'paragraph' | 'balance' | 'split';
+
+export const REFLOW_MODES: ReflowMode[] = ['none', 'paragraph', 'balance', 'split'];
+
+export interface ReflowOptions {
+  mode: ReflowMode;
+  /** Target characters per line for balance and split (default 36) */
+  width?: number;
+  /** Lines per slide for split (default 2) */
+  maxLines?: number;
+}
+
+export const DEFAULT_REFLOW_WIDTH = 36;
+export const DEFAULT_REFLOW_LINES = 2;
+
+function wordsOf(text: string): string[] {
+  return text.split(/\s+/).filter(Boolean);
+}
+
+/**
+ * Fill lines with words up to the limit; a word longer than the limit gets a line of its own
+ */
+function wrapWords(words: string[], limit: number): string[] {
+  const lines: string[] = [];
+  let current = '';
+  for (const word of words) {
+    if (current && current.length + 1 + word.length > limit) {
+      lines.push(current);
+      current = word;
+    } else {
+      current = current ? `${current} ${word}` : word;
+    }
+  }
+  if (current) lines.push(current);
+  return lines;
+}
+
+/**
+ * All lines joined into one
+ */
+export function joinLines(text: string): string {
+  return wordsOf(text).join(' ');
+}
+
+/**
+ * Re-wrap text into as few lines as the width allows, with line lengths as
+ * even as possible
+ */
+export function balanceLines(text: string, width: number): string {
+  const words = wordsOf(text);
+  if (words.length === 0) return '';
+  const total = words.join(' ').length;
+  const count = Math.max(1, Math.ceil(total / width));
+
+  // The shortest line limit that still fits the words into that many lines
+  let limit = Math.max(Math.ceil(total / count), ...words.map(word => word.length));
+  while (wrapWords(words, limit).length > count) limit++;
+  return wrapWords(words, limit).join('\n');
+}
+
+/**
+ * Keep the existing line breaks, wrapping only lines longer than the width
+ */
+export function wrapLongLines(text: string, width: number): string[] {
+  return text
+    .split('\n')
+    .flatMap(line => line.length > width ? balanceLines(line, width).split('\n') : [line.trim()])
+    .filter(Boolean);
+}
+
+/**
+ * Slide text in chunks of at most maxLines lines
+ */
+export function splitIntoSlides(text: string, width: number, maxLines: number): string[] {
+  const lines = wrapLongLines(text, width);
+  const chunks: string[] = [];
+  for (let i = 0; i < lines.length; i += maxLines) {
+    chunks.push(lines.slice(i, i + maxLines).join('\n'));
+  }
+  return chunks.length > 0 ? chunks : [''];
+}
+
+function reflowSlide(slide: LyricSlide, options: Required<ReflowOptions>): LyricSlide[] {
+  const reflowText = (text: string): string[] => {
+    switch (options.mode) {
+      case 'paragraph':
+        return [joinLines(text)];
+      case 'balance':
+        return [balanceLines(text, options.width)];
+      default:
+        return splitIntoSlides(text, options.width, options.maxLines);
+    }
+  };
+
+  // Bilingual slides: the first language sets the chunks, the others follow
+  const [primary, ...others] = slide.elements && slide.elements.length > 1 ? slide.elements : [slide.text];
+  const chunks = reflowText(primary);
+  const otherChunks = others.map(other => options.mode === 'split'
+    ? divideLines(wrapLongLines(other, options.width).join('\n'), chunks.length)
+    : reflowText(other));
+  const notes = options.mode === 'split' && slide.notes && chunks.length > 1
+    ? divideLines(slide.notes, chunks.length)
+    : null;
+  // Runs follow the elements in order, so each element is restyled on its own
+  const visibleChars = (text: string) => text.replace(/\s/g, '').length;
+  const styles = slide.runs
+    ? (others.length > 0 ? partitionRuns(slide.runs, [primary, ...others].map(visibleChars)) : [slide.runs]).map(restyler)
+    : null;
+
+  return chunks.map((chunk, index) => {
+    const elements = others.length > 0 ? [chunk, ...otherChunks.map(parts => parts[index] || '')] : undefined;
+    const text = elements ? elements.join('\n') : chunk;
+    const styled = styles ? (elements ?? [chunk]).map((part, n) => styles[n](part)) : null;
+    const runs: TextRun[] | undefined = styled && styled.every(Boolean)
+      ? styled.flatMap((part, n) => n > 0 ? [{ text: '\n' }, ...part!] : part!)
+      : undefined;
+    return {
+      ...slide,
+      text,
+      elements,
+      notes: notes ? notes[index] || undefined : slide.notes,
+      runs,
+    };
+  });
+}
+
+/**
+ * Reflow the lyric slides of each song. Songs are copied; 'none' returns them as they are.
+ */
+export function reflowLyrics(songs: ExtractedLyrics[], options: ReflowOptions): ExtractedLyrics[] {
+  if (options.mode === 'none') {
+    return songs;
+  }
+  const settings: Required<ReflowOptions> = {
+    mode: options.mode,
+    width: options.width ?? DEFAULT_REFLOW_WIDTH,
+    maxLines: options.maxLines ?? DEFAULT_REFLOW_LINES,
+  };
+
+  return songs.map(song => {
+    const sections = song.sections.map(section => ({
+      ...section,
+      slides: section.slides.flatMap(slide => slide.isLyric && slide.text.trim() ? reflowSlide(slide, settings) : [slide]),
+    }));
+    const slides = sections.flatMap(section => section.slides);
+    const lyricTexts = slides.filter(slide => slide.isLyric).map(slide => slide.text);
+    return {
+      ...song,
+      sections,
+      fullText: lyricTexts.join('\n\n'),
+      slideCount: slides.length,
+      lyricSlideCount: lyricTexts.length,
+    };
+  });
+}
@@ -0,0 +1,281 @@
+/**
+ * Lyric Search
+ * Full-text search over song lyrics, to find a song from a line someone
+ * remembers. Lyrics are crawled from the libraries with extractLyrics and
+ * kept as an inverted index (word -> songs) in
+ * ~/.propresenter-words/lyric-index.json. The index is built per library
+ * and rebuilt on request; searching needs no ProPresenter connection.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import type { ProPresenterClient } from '../propresenter-client';
+import { extractLyrics } from '../lyrics-extractor';
+import { crawlLibraries, resolveLibraries } from './library-crawler';
+
+export interface IndexedLine {
+  section: string;
+  text: string;
+}
+
+export interface IndexedSong {
+  uuid: string;
+  title: string;
+  library: string;
+  libraryId: string;
+  lines: IndexedLine[];
+}
+
+export interface LyricIndexLibrary {
+  id: string;
+  name: string;
+  indexedAt: string;
+  songs: number;
+}
+
+interface LyricIndex {
+  libraries: Record<string, LyricIndexLibrary>;
+  songs: Record<string, IndexedSong>;
+  /** Word -> UUIDs of songs containing it */
+  terms: Record<string, string[]>;
+}
+
+export interface LyricSearchResult {
+  uuid: string;
+  title: string;
+  library: string;
+  section: string;
+  /** The best matching line */
+  snippet: string;
+  /** Share of the query's words found in the line (1 = all), plus a bonus for the exact phrase */
+  score: number;
+}
+
+export type LyricIndexProgressEvent =
+  | { type: 'library'; name: string; presentations: number }
+  | { type: 'song'; title: string; index: number; total: number }
+  | { type: 'error'; title: string; message: string };
+
+export interface LyricIndexOptions {
+  /** Library UUIDs or names to index; all libraries when empty */
+  libraries?: string[];
+  onProgress?: (event: LyricIndexProgressEvent) => void;
+}
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const INDEX_FILE = path.join(CONFIG_DIR, 'lyric-index.json');
+
+const DEFAULT_LIMIT = 25;
+const PHRASE_BONUS = 0.5;
+
+function ensureConfigDir(): void {
+  if (!fs.existsSync(CONFIG_DIR)) {
+    fs.mkdirSync(CONFIG_DIR, { recursive: true });
+  }
+}
+
+function emptyIndex(): LyricIndex {
+  return { libraries: {}, songs: {}, terms: {} };
+}
+
+function loadIndex(): LyricIndex {
+  try {
+    if (!fs.existsSync(INDEX_FILE)) {
+      return emptyIndex();
+    }
+    const parsed = JSON.parse(fs.readFileSync(INDEX_FILE, 'utf-8'));
+    return { libraries: parsed.libraries || {}, songs: parsed.songs || {}, terms: parsed.terms || {} };
+  } catch {
+    return emptyIndex();
+  }
+}
+
+function saveIndex(index: LyricIndex): void {
+  ensureConfigDir();
+  fs.writeFileSync(INDEX_FILE, JSON.stringify(index), 'utf-8');
+}
+
+/**
+ * Lowercase, accent-free text with punctuation removed, for matching
+ */
+export function normalizeForSearch(text: string): string {
+  return text
+    .normalize('NFD')
+    .replace(/[\u0300-\u036f]/g, '')
+    .toLowerCase()
+    .replace(/['’`]/g, '')
+    .replace(/[^a-z0-9]+/g, ' ')
+    .trim();
+}
+
+export function tokenize(text: string): string[] {
+  const normalized = normalizeForSearch(text);
+  return normalized ? normalized.split(' ') : [];
+}
+
+function rebuildTerms(index: LyricIndex): void {
+  const terms = new Map<string, Set<string>>();
+  for (const song of Object.values(index.songs)) {
+    const words = new Set([...tokenize(song.title), ...song.lines.flatMap(line => tokenize(line.text))]);
+    for (const word of words) {
+      if (!terms.has(word)) terms.set(word, new Set());
+      terms.get(word)!.add(song.uuid);
+    }
+  }
+  index.terms = Object.fromEntries([...terms].map(([word, uuids]) => [word, [...uuids]]));
+}
+
+/**
+ * Crawl libraries and (re)index their songs' lyrics. Libraries not crawled
+ * keep their existing entries.
+ */
+export async function buildLyricIndex(
+  client: ProPresenterClient,
+  options: LyricIndexOptions = {}
+): Promise<LyricIndexLibrary[]> {
+  const index = loadIndex();
+  const counts = new Map<string, number>();
+
+  const libraries = await crawlLibraries(client, {
+    libraries: options.libraries,
+    onLibrary: (library, presentations) => {
+      options.onProgress?.({ type: 'library', name: library.name, presentations });
+      for (const [uuid, song] of Object.entries(index.songs)) {
+        if (song.libraryId === library.uuid) delete index.songs[uuid];
+      }
+      counts.set(library.uuid, 0);
+    },
+    onError: (title, error) => options.onProgress?.({ type: 'error', title, message: error.message }),
+  }, ({ library, uuid, name, presentation, position, total }) => {
+    options.onProgress?.({ type: 'song', title: name, index: position, total });
+    const lyrics = extractLyrics(presentation);
+    const lines = lyrics.sections.flatMap(section => section.slides
+      .filter(slide => slide.isLyric)
+      .flatMap(slide => slide.text.split('\n'))
+      .map(text => text.trim())
+      .filter(Boolean)
+      .map(text => ({ section: section.name, text })));
+    index.songs[uuid] = { uuid, title: lyrics.title || name, library: library.name, libraryId: library.uuid, lines };
+    counts.set(library.uuid, (counts.get(library.uuid) ?? 0) + 1);
+  });
+
+  const indexedAt = new Date().toISOString();
+  const indexed = libraries.map(library => ({
+    id: library.uuid,
+    name: library.name,
+    indexedAt,
+    songs: counts.get(library.uuid) ?? 0,
+  }));
+  for (const entry of indexed) {
+    index.libraries[entry.id] = entry;
+  }
+
+  rebuildTerms(index);
+  saveIndex(index);
+  return indexed;
+}
+
+/**
+ * Libraries in the index, with when they were last crawled
+ */
+export function listIndexedLibraries(): LyricIndexLibrary[] {
+  return Object.values(loadIndex().libraries);
+}
+
+/**
+ * Find songs containing the words of a remembered line. Songs need every word
+ * of the query; the best line of each song is returned as the snippet.
+ */
+export function searchLyrics(
+  query: string,
+  options: { libraryIds?: string[]; limit?: number } = {}
+): LyricSearchResult[] {
+  const words = [...new Set(tokenize(query))];
+  if (words.length === 0) {
+    return [];
+  }
+
+  const index = loadIndex();
+  const postings = words.map(word => new Set(index.terms[word] || []));
+  const candidates = [...postings[0]].filter(uuid => postings.every(set => set.has(uuid)));
+  const phrase = normalizeForSearch(query);
+  const libraryIds = options.libraryIds?.filter(Boolean);
+
+  const results: LyricSearchResult[] = [];
+  for (const uuid of candidates) {
+    const song = index.songs[uuid];
+    if (!song) continue;
+    if (libraryIds && libraryIds.length > 0 && !libraryIds.includes(song.libraryId)) continue;
+
+    let best: { line: IndexedLine; score: number } | null = null;
+    for (const line of song.lines) {
+      const normalized = normalizeForSearch(line.text);
+      const lineWords = new Set(normalized.split(' '));
+      const found = words.filter(word => lineWords.has(word)).length;
+      const score = found / words.length + (` ${normalized} `.includes(` ${phrase} `) ? PHRASE_BONUS : 0);
+      if (!best || score > best.score) best = { line, score };
+    }
+    if (!best || best.score === 0) {
+      // Only the title matched
+      results.push({ uuid, title: song.title, library: song.library, section: '', snippet: '', score: 0 });
+      continue;
+    }
+    results.push({
+      uuid,
+      title: song.title,
+      library: song.library,
+      section: best.line.section,
+      snippet: best.line.text,
+      score: Math.round(best.score * 100) / 100,
+    });
+  }
+
+  results.sort((a, b) => b.score - a.score || a.title.localeCompare(b.title));
+  return results.slice(0, options.limit ?? DEFAULT_LIMIT);
+}
+
+/**
+ * Words used in at least minSongs indexed songs, normalized as for search.
+ * Gives the linter the library's own vocabulary (Hosanna, Emmanuel, ...).
+ */
+export function indexedVocabulary(minSongs = 1): Set<string> {
+  const { terms } = loadIndex();
+  return new Set(Object.keys(terms).filter(word => terms[word].length >= minSongs));
+}
+
+/**
+ * Library UUIDs for a list of library UUIDs or names, as accepted when
+ * indexing. Indexed libraries resolve from the index; only the rest need
+ * ProPresenter.
+ */
+export async function resolveLibraryIds(client: ProPresenterClient, wanted: string[]): Promise<string[]> {
+  const known = Object.values(loadIndex().libraries);
+  const ids: string[] = [];
+  const unknown: string[] = [];
+  for (const value of wanted.map(entry => entry.trim()).filter(Boolean)) {
+    const key = value.toLowerCase();
+    const match = known.find(library => library.id.toLowerCase() === key || library.name.trim().toLowerCase() === key);
+    if (match) {
+      ids.push(match.id);
+    } else {
+      unknown.push(value);
+    }
+  }
+  if (unknown.length > 0) {
+    ids.push(...(await resolveLibraries(client, unknown)).map(library => library.uuid));
+  }
+  return [...new Set(ids)];
+}
+
+/**
+ * Library UUIDs from the list that have never been indexed
+ */
+export function unindexedLibraries(libraryIds: string[]): string[] {
+  const { libraries } = loadIndex();
+  return libraryIds.filter(id => id && !libraries[id]);
+}
+
+export function getLyricIndexPath(): string {
+  return INDEX_FILE;
+}
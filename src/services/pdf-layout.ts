@@ -0,0 +1,323 @@
+/**
+ * PDF Layout Reader
+ * Rebuilds a service order's reading order from where each piece of text sits
+ * on the page, for bulletins the plain text extraction scrambles. Two-column
+ * layouts are read a column at a time, table rows are read across, and the
+ * second copy on a 2-up sheet (two A5 orders side by side on landscape A4) is
+ * dropped. Bold and larger text are kept as cues for section detection.
+ */
+
+import * as fs from 'fs';
+import type { ServiceOrderLine } from '../types/service-order';
+
+export interface PositionedText {
+  text: string;
+  /** Left edge, in PDF points */
+  x: number;
+  /** Baseline, in PDF points from the bottom of the page */
+  y: number;
+  width: number;
+  /** Font size in points */
+  size: number;
+  bold: boolean;
+}
+
+export interface PdfPageText {
+  /** Page bounds: [x0, y0, x1, y1] in PDF points */
+  view: [number, number, number, number];
+  items: PositionedText[];
+}
+
+interface Row {
+  items: PositionedText[];
+  y: number;
+}
+
+// Text this much larger than the body text is a heading
+const HEADING_SCALE = 1.15;
+// A gap between columns must be at least this wide (points, and share of the region)
+const MIN_GUTTER = 12;
+const MIN_GUTTER_SHARE = 0.03;
+// Right-hand rows sharing a baseline with left-hand text at this rate make a table, not columns
+const TABLE_ROW_SHARE = 0.8;
+// Halves of a 2-up sheet with this share of lines in common are the same order printed twice
+const DUPLICATE_HALF_SHARE = 0.8;
+const MAX_COLUMN_DEPTH = 3;
+
+const BOLD_FONT = /bold|black|heavy|semibold|demibold/i;
+
+/**
+ * pdfjs (used directly and through pdf-parse) expects DOMMatrix, which Node.js
+ * and the Electron main process don't have
+ */
+export function ensureDomMatrix(): void {
+  if (typeof (globalThis as any).DOMMatrix === 'undefined') {
+    (globalThis as any).DOMMatrix = class DOMMatrix {
+      m11 = 1; m12 = 0; m13 = 0; m14 = 0;
+      m21 = 0; m22 = 1; m23 = 0; m24 = 0;
+      m31 = 0; m32 = 0; m33 = 1; m34 = 0;
+      m41 = 0; m42 = 0; m43 = 0; m44 = 1;
+      a = 1; b = 0; c = 0; d = 1; e = 0; f = 0;
+      is2D = true; isIdentity = true;
+      constructor() {}
+      toString() { return 'matrix(1, 0, 0, 1, 0, 0)'; }
+    };
+  }
+}
+
+// pdfjs-dist is published as ES modules only; a real import() keeps TypeScript
+// from compiling it to require()
+const importModule = new Function('specifier', 'return import(specifier)') as (specifier: string) => Promise<any>;
+
+function isBoldFont(page: any, fontName: string): boolean {
+  try {
+    const font = page.commonObjs.has(fontName) ? page.commonObjs.get(fontName) : null;
+    return Boolean(font && (font.bold || font.black || BOLD_FONT.test(font.name || '')));
+  } catch {
+    return BOLD_FONT.test(fontName);
+  }
+}
+
+/**
+ * Read every piece of text of a PDF with its position, size and weight
+ */
+export async function readPdfPages(pdfPath: string): Promise<PdfPageText[]> {
+  ensureDomMatrix();
+  const pdfjs = await importModule('pdfjs-dist/legacy/build/pdf.mjs');
+  const doc = await pdfjs.getDocument({
+    data: new Uint8Array(fs.readFileSync(pdfPath)),
+    isEvalSupported: false,
+    disableFontFace: true,
+    verbosity: 0,
+  }).promise;
+
+  try {
+    const pages: PdfPageText[] = [];
+    for (let number = 1; number <= doc.numPages; number++) {
+      const page = await doc.getPage(number);
+      const content = await page.getTextContent();
+      // Resolves the page's fonts, whose names tell us which are bold
+      await page.getOperatorList();
+
+      const items: PositionedText[] = [];
+      for (const item of content.items) {
+        if (typeof item.str !== 'string' || !item.str.trim()) continue;
+        const [a, b, c, d, x, y] = item.transform;
+        // Rotated text (margin notes, watermarks) is left out
+        if (Math.abs(b) > Math.abs(a)) continue;
+        items.push({
+          text: item.str,
+          x,
+          y,
+          width: item.width,
+          size: Math.hypot(c, d) || item.height || 1,
+          bold: isBoldFont(page, item.fontName),
+        });
+      }
+      pages.push({ view: page.view, items });
+      page.cleanup();
+    }
+    return pages;
+  } finally {
+    await doc.destroy();
+  }
+}
+
+/**
+ * Lines of a PDF in reading order, with bold and heading cues
+ */
+export async function readPdfLayout(pdfPath: string): Promise<ServiceOrderLine[]> {
+  return layoutPages(await readPdfPages(pdfPath));
+}
+
+/**
+ * Put the text of each page in reading order and turn it into lines
+ */
+export function layoutPages(pages: PdfPageText[]): ServiceOrderLine[] {
+  const bodySize = medianSize(pages.flatMap(page => page.items));
+  const lines: ServiceOrderLine[] = [];
+
+  for (const page of pages) {
+    for (const half of pageHalves(page)) {
+      for (const row of readRegion(half.items, half.left, half.right, 0)) {
+        const line = rowToLine(row, bodySize);
+        if (line.text) lines.push(line);
+      }
+    }
+  }
+  return lines;
+}
+
+/**
+ * Font size of most of the text, weighted by length
+ */
+function medianSize(items: PositionedText[]): number {
+  const sizes = items
+    .map(item => ({ size: item.size, weight: item.text.trim().length }))
+    .sort((a, b) => a.size - b.size);
+  const total = sizes.reduce((sum, entry) => sum + entry.weight, 0);
+  let seen = 0;
+  for (const entry of sizes) {
+    seen += entry.weight;
+    if (seen >= total / 2) return entry.size;
+  }
+  return 12;
+}
+
+/**
+ * Split a landscape sheet printed 2-up into its halves. When both halves hold
+ * the same order only the first is kept.
+ */
+function pageHalves(page: PdfPageText): Array<{ items: PositionedText[]; left: number; right: number }> {
+  const [x0, y0, x1, y1] = page.view;
+  const whole = [{ items: page.items, left: x0, right: x1 }];
+  const width = x1 - x0;
+  if (width <= (y1 - y0) * 1.2 || page.items.length < 10) return whole;
+
+  const middle = x0 + width / 2;
+  const crossing = page.items.filter(item => item.x < middle - 2 && item.x + item.width > middle + 2);
+  if (crossing.length > page.items.length * 0.02) return whole;
+
+  const leftItems = page.items.filter(item => item.x + item.width / 2 < middle);
+  const rightItems = page.items.filter(item => item.x + item.width / 2 >= middle);
+  if (leftItems.length === 0 || rightItems.length === 0) return whole;
+
+  const leftText = new Set(groupRows(leftItems).map(row => rowText(row).toLowerCase()));
+  const rightText = groupRows(rightItems).map(row => rowText(row).toLowerCase());
+  const shared = rightText.filter(text => leftText.has(text)).length;
+  if (shared >= Math.max(leftText.size, rightText.length) * DUPLICATE_HALF_SHARE) {
+    return [{ items: leftItems, left: x0, right: middle }];
+  }
+  return [
+    { items: leftItems, left: x0, right: middle },
+    { items: rightItems, left: middle, right: x1 },
+  ];
+}
+
+/**
+ * Rows of a region in reading order. A region split by a column gutter is read
+ * one column at a time between the lines that run across both columns.
+ */
+function readRegion(items: PositionedText[], left: number, right: number, depth: number): Row[] {
+  const rows = groupRows(items);
+  const gutter = depth < MAX_COLUMN_DEPTH ? findColumnGutter(items, rows, left, right) : null;
+  if (gutter === null) return rows;
+
+  const ordered: Row[] = [];
+  let leftColumn: PositionedText[] = [];
+  let rightColumn: PositionedText[] = [];
+  const flush = () => {
+    ordered.push(...readRegion(leftColumn, left, gutter, depth + 1), ...readRegion(rightColumn, gutter, right, depth + 1));
+    leftColumn = [];
+    rightColumn = [];
+  };
+
+  for (const row of rows) {
+    if (row.items.some(item => item.x < gutter && item.x + item.width > gutter)) {
+      // A heading or line across both columns ends the columns above it
+      flush();
+      ordered.push(row);
+      continue;
+    }
+    for (const item of row.items) {
+      (item.x < gutter ? leftColumn : rightColumn).push(item);
+    }
+  }
+  flush();
+  return ordered;
+}
+
+/**
+ * The x position of the widest clear vertical gap between two columns of text,
+ * or null when the region is one column or a table (whose rows read across)
+ */
+function findColumnGutter(items: PositionedText[], rows: Row[], left: number, right: number): number | null {
+  const width = right - left;
+  // Lines across most of the region don't say where the columns are
+  const intervals = items
+    .filter(item => item.width < width * 0.45)
+    .map(item => [item.x, item.x + item.width] as [number, number])
+    .sort((a, b) => a[0] - b[0]);
+  if (intervals.length < 4) return null;
+
+  let best: { x: number; gap: number } | null = null;
+  let end = intervals[0][1];
+  for (const [start, finish] of intervals.slice(1)) {
+    const gap = start - end;
+    const x = end + gap / 2;
+    if (gap >= Math.max(MIN_GUTTER, width * MIN_GUTTER_SHARE)
+      && x > left + width * 0.1 && x < right - width * 0.1
+      && (!best || gap > best.gap)) {
+      best = { x, gap };
+    }
+    end = Math.max(end, finish);
+  }
+  if (!best) return null;
+
+  // In a table nearly every right-hand cell sits on the same baseline as a left-hand one
+  const gutter = best.x;
+  const withRight = rows.filter(row => row.items.some(item => item.x >= gutter));
+  const paired = withRight.filter(row => row.items.some(item => item.x + item.width <= gutter));
+  if (withRight.length > 0 && paired.length >= withRight.length * TABLE_ROW_SHARE) {
+    return null;
+  }
+  return gutter;
+}
+
+/**
+ * Group text sharing a baseline into rows, top of the page first
+ */
+function groupRows(items: PositionedText[]): Row[] {
+  const sorted = [...items].sort((a, b) => b.y - a.y || a.x - b.x);
+  const rows: Row[] = [];
+  for (const item of sorted) {
+    const row = rows[rows.length - 1];
+    if (row && Math.abs(row.y - item.y) <= Math.max(item.size, ...row.items.map(i => i.size)) * 0.4) {
+      row.items.push(item);
+    } else {
+      rows.push({ items: [item], y: item.y });
+    }
+  }
+  for (const row of rows) {
+    row.items.sort((a, b) => a.x - b.x);
+  }
+  return rows;
+}
+
+/**
+ * Join a row's pieces: a space for word gaps, a tab between table cells
+ */
+function rowText(row: Row): string {
+  let text = '';
+  let previous: PositionedText | null = null;
+  for (const item of row.items) {
+    if (previous) {
+      const gap = item.x - (previous.x + previous.width);
+      const size = Math.max(item.size, previous.size);
+      if (gap > size * 1.5) {
+        text = text.trimEnd() + '\t';
+      } else if (gap > size * 0.15 && !/\s$/.test(text) && !/^\s/.test(item.text)) {
+        text += ' ';
+      }
+    }
+    text += item.text;
+    previous = item;
+  }
+  return text.replace(/ {2,}/g, ' ').replace(/ *\t */g, '\t').trim();
+}
+
+function rowToLine(row: Row, bodySize: number): ServiceOrderLine {
+  const text = rowText(row);
+  let boldChars = 0;
+  let chars = 0;
+  for (const item of row.items) {
+    const length = item.text.replace(/\s/g, '').length;
+    chars += length;
+    if (item.bold) boldChars += length;
+  }
+  const size = Math.max(...row.items.map(item => item.size));
+  const line: ServiceOrderLine = { text };
+  if (chars > 0 && boldChars * 2 >= chars) line.bold = true;
+  if (size >= bodySize * HEADING_SCALE) line.heading = true;
+  return line;
+}
@@ -115,7 +115,9 @@ export async function collectPlaylistLyrics(
         continue;
       }
 
-      const lyrics = extractLyrics(presentation);
+      const lyrics = extractLyrics(presentation, {
+        arrangement: { uuid: item.arrangementUuid, name: item.arrangementName },
+      });
       songs.push({ item, lyrics });
       onProgress?.({ type: 'playlist:item:success', item });
     } catch (error: any) {
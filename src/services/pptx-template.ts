@@ -0,0 +1,476 @@
+/**
+ * PPTX Template Export - Build lyric slides on an existing PowerPoint master
+ *
+ * pptxgenjs can't open an existing presentation, so this works on the
+ * reference .pptx package directly: its slide masters, layouts, theme,
+ * fonts and media are kept as they are, its own slides are dropped, and
+ * one slide per lyric is added on the chosen layout. Lyrics go into the
+ * layout's body placeholder and song titles into its title placeholder, so
+ * every bit of styling comes from the master.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { ZipReader } from '../utils/zip-reader';
+import { createZip } from '../utils/zip-writer';
+import { ExtractedLyrics, TranslationSource, formatSongCredits, lastLyricSlide, splitTranslation } from '../lyrics-extractor';
+import type { TextRun } from '../propresenter-client';
+import type { LabelDisplay, SlideFitEvent } from '../pptx-exporter';
+import { runsForText } from '../utils/text-runs';
+import { TextBox, TextFitResult, divideLines, fitText } from './text-fit';
+
+export interface TemplateLayout {
+  /** Package path, e.g. ppt/slideLayouts/slideLayout2.xml */
+  file: string;
+  name: string;
+  /** The layout's type attribute (title, obj, txOnly, ...) */
+  type: string;
+  /** Raw <p:ph/> elements of the layout's title and body placeholders */
+  titlePlaceholder: string | null;
+  bodyPlaceholder: string | null;
+  footerPlaceholder: string | null;
+}
+
+export interface PptxTemplateOptions {
+  templatePath: string;
+  outputPath: string;
+  includeSongTitles?: boolean;
+  /** Layout names to use; matched case-insensitively */
+  lyricLayout?: string;
+  titleLayout?: string;
+  translationSource?: TranslationSource;
+  translationColor?: string;
+  translationFontSize?: number;
+  translationItalic?: boolean;
+  /** Song credits on the last slide of each song */
+  copyrightFooter?: boolean;
+  ccliLicence?: string;
+  /**
+   * Slide labels as a caption line above the lyrics, or in the speaker
+   * notes. Notes need a notes master in the template.
+   */
+  labelDisplay?: LabelDisplay;
+  /**
+   * Shrink long slides, then split them, to fit the body placeholder. The
+   * master's body font size is the starting point when it sets one.
+   */
+  autoFit?: boolean;
+  fontSize?: number;
+  minFontSize?: number;
+  maxFontSize?: number;
+  onSlideFit?: (event: SlideFitEvent) => void;
+}
+
+const NS_A = 'http://schemas.openxmlformats.org/drawingml/2006/main';
+const NS_R = 'http://schemas.openxmlformats.org/officeDocument/2006/relationships';
+const NS_P = 'http://schemas.openxmlformats.org/presentationml/2006/main';
+const REL_SLIDE = 'http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide';
+const REL_LAYOUT = 'http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout';
+const REL_NOTES_SLIDE = 'http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesSlide';
+const REL_NOTES_MASTER = 'http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesMaster';
+const SLIDE_CONTENT_TYPE = 'application/vnd.openxmlformats-officedocument.presentationml.slide+xml';
+const NOTES_CONTENT_TYPE = 'application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml';
+const EMU_PER_INCH = 914400;
+// Share of the body placeholder given to the primary language when a translation follows
+const PRIMARY_SHARE = 0.6;
+// PowerPoint's section list extension references slide ids we remove
+const SECTION_EXT = /<p:ext uri="\{521415D9-36F7-43E2-AB2F-B90AF26B5E84\}">[\s\S]*?<\/p:ext>/g;
+
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+function attr(xml: string, name: string): string | null {
+  const match = xml.match(new RegExp(`\\b${name}="([^"]*)"`));
+  return match ? match[1] : null;
+}
+
+/**
+ * The <p:sp> whose placeholder matches, e.g. /type="body"/
+ */
+function placeholderShapeXml(xml: string, placeholder: RegExp): string | null {
+  const shapes = xml.match(/<p:sp\b[^>]*>[\s\S]*?<\/p:sp>/g) ?? [];
+  return shapes.find(sp => placeholder.test(sp.match(/<p:ph\b[^>]*>/)?.[0] ?? '')) ?? null;
+}
+
+function shapeBox(sp: string | null): TextBox | null {
+  const off = sp?.match(/<a:off x="(-?\d+)" y="(-?\d+)"\/>/);
+  const ext = sp?.match(/<a:ext cx="(\d+)" cy="(\d+)"\/>/);
+  if (!off || !ext) return null;
+  return { w: parseInt(ext[1], 10) / EMU_PER_INCH, h: parseInt(ext[2], 10) / EMU_PER_INCH };
+}
+
+/**
+ * First-level font size in points set by a shape's list style or a master's body style
+ */
+function levelOneFontSize(xml: string | null | undefined): number | null {
+  const sz = xml?.match(/<a:lvl1pPr\b[^>]*>(?:(?!<\/a:lvl1pPr>)[\s\S])*?<a:defRPr\b[^>]*\bsz="(\d+)"/)?.[1];
+  return sz ? parseInt(sz, 10) / 100 : null;
+}
+
+function layoutNumber(file: string): number {
+  const match = file.match(/(\d+)\.xml$/);
+  return match ? parseInt(match[1], 10) : 0;
+}
+
+function readLayout(file: string, xml: string): TemplateLayout {
+  const root = xml.match(/<p:sldLayout\b[^>]*>/)?.[0] ?? '';
+  const name = xml.match(/<p:cSld\b[^>]*\bname="([^"]*)"/)?.[1] ?? path.basename(file, '.xml');
+  const placeholders = xml.match(/<p:ph\b[^>]*\/?>/g) ?? [];
+
+  const titlePlaceholder = placeholders.find(ph => /type="(title|ctrTitle)"/.test(ph)) ?? null;
+  // Body placeholders are type="body" or untyped content placeholders with an idx
+  const bodyPlaceholder = placeholders.find(ph => /type="body"/.test(ph))
+    ?? placeholders.find(ph => !/\btype="/.test(ph) && /\bidx="/.test(ph))
+    ?? null;
+  const footerPlaceholder = placeholders.find(ph => /type="ftr"/.test(ph)) ?? null;
+
+  return {
+    file,
+    name,
+    type: attr(root, 'type') ?? 'cust',
+    titlePlaceholder: titlePlaceholder && titlePlaceholder.replace(/\/?>$/, '/>'),
+    bodyPlaceholder: bodyPlaceholder && bodyPlaceholder.replace(/\/?>$/, '/>'),
+    footerPlaceholder: footerPlaceholder && footerPlaceholder.replace(/\/?>$/, '/>'),
+  };
+}
+
+/**
+ * List the slide layouts of a reference .pptx
+ */
+export function listTemplateLayouts(templatePath: string): TemplateLayout[] {
+  const zip = new ZipReader(fs.readFileSync(templatePath));
+  return zip.entries
+    .filter(entry => /^ppt\/slideLayouts\/slideLayout\d+\.xml$/.test(entry.name))
+    .sort((a, b) => layoutNumber(a.name) - layoutNumber(b.name))
+    .map(entry => readLayout(entry.name, zip.read(entry).toString('utf-8')));
+}
+
+function pickLayout(
+  layouts: TemplateLayout[],
+  wanted: string | undefined,
+  role: 'lyric' | 'title'
+): TemplateLayout {
+  if (wanted) {
+    const match = layouts.find(layout => layout.name.toLowerCase() === wanted.toLowerCase());
+    if (!match) {
+      throw new Error(`Template has no layout named "${wanted}". Layouts: ${layouts.map(l => l.name).join(', ')}`);
+    }
+    return match;
+  }
+
+  const usable = layouts.filter(layout => role === 'lyric' ? layout.bodyPlaceholder : layout.titlePlaceholder);
+  const preferred = role === 'lyric'
+    ? usable.find(layout => /lyric/i.test(layout.name))
+      ?? usable.find(layout => layout.type === 'txOnly')
+      ?? usable.find(layout => layout.type === 'obj')
+    : usable.find(layout => /title/i.test(layout.name) && layout.type === 'title')
+      ?? usable.find(layout => layout.type === 'title');
+
+  const layout = preferred ?? usable[0];
+  if (!layout) {
+    throw new Error(`Template has no layout with a ${role === 'lyric' ? 'body' : 'title'} placeholder`);
+  }
+  return layout;
+}
+
+function runXml(text: string, runProps: string, color?: string): string {
+  const rPr = color
+    ? `<a:rPr lang="en-GB" dirty="0"${runProps}><a:solidFill><a:srgbClr val="${escapeXml(color)}"/></a:solidFill></a:rPr>`
+    : `<a:rPr lang="en-GB" dirty="0"${runProps}/>`;
+  return `<a:r>${rPr}<a:t>${escapeXml(text)}</a:t></a:r>`;
+}
+
+/**
+ * Formatting attributes of a text run, for slides without other run properties
+ */
+function textRunProps(run: TextRun): string {
+  let props = '';
+  if (run.bold !== undefined) props += ` b="${run.bold ? 1 : 0}"`;
+  if (run.italic !== undefined) props += ` i="${run.italic ? 1 : 0}"`;
+  if (run.underline) props += ' u="sng"';
+  return props;
+}
+
+function paragraphs(text: string, runProps = '', color?: string, runs?: TextRun[]): string {
+  const formatted = runs && !runProps && !color ? runsForText(runs, text) : null;
+  return text.split('\n').map((line, index) => {
+    const content = formatted
+      ? formatted[index].map(run => runXml(run.text, textRunProps(run), run.color)).join('')
+      : line ? runXml(line, runProps, color) : '';
+    // Lyrics aren't bullet points, whatever the master's body style says
+    return `<a:p><a:pPr marL="0" indent="0"><a:buNone/></a:pPr>${content}</a:p>`;
+  }).join('');
+}
+
+function placeholderShape(id: number, name: string, placeholder: string, body: string, bodyPr = '<a:bodyPr/>'): string {
+  return `<p:sp><p:nvSpPr><p:cNvPr id="${id}" name="${name}"/><p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr>`
+    + `<p:nvPr>${placeholder}</p:nvPr></p:nvSpPr><p:spPr/>`
+    + `<p:txBody>${bodyPr}<a:lstStyle/>${body}</p:txBody></p:sp>`;
+}
+
+/**
+ * Plain text box, for layouts without a footer placeholder. Position in EMU.
+ */
+function textBoxShape(id: number, name: string, box: { x: number; y: number; w: number; h: number }, body: string): string {
+  return `<p:sp><p:nvSpPr><p:cNvPr id="${id}" name="${name}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr>`
+    + `<p:spPr><a:xfrm><a:off x="${box.x}" y="${box.y}"/><a:ext cx="${box.w}" cy="${box.h}"/></a:xfrm>`
+    + '<a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:noFill/></p:spPr>'
+    + `<p:txBody><a:bodyPr wrap="square" anchor="b"><a:normAutofit/></a:bodyPr><a:lstStyle/>${body}</p:txBody></p:sp>`;
+}
+
+function slideXml(shapes: string[]): string {
+  return '<?xml version="1.0" encoding="UTF-8" standalone="yes"?>\n'
+    + `<p:sld xmlns:a="${NS_A}" xmlns:r="${NS_R}" xmlns:p="${NS_P}"><p:cSld><p:spTree>`
+    + '<p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/>'
+    + shapes.join('')
+    + '</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>';
+}
+
+function notesSlideXml(notes: string): string {
+  return '<?xml version="1.0" encoding="UTF-8" standalone="yes"?>\n'
+    + `<p:notes xmlns:a="${NS_A}" xmlns:r="${NS_R}" xmlns:p="${NS_P}"><p:cSld><p:spTree>`
+    + '<p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/>'
+    + '<p:sp><p:nvSpPr><p:cNvPr id="2" name="Slide Image 1"/><p:cNvSpPr><a:spLocks noGrp="1" noRot="1" noChangeAspect="1"/></p:cNvSpPr>'
+    + '<p:nvPr><p:ph type="sldImg"/></p:nvPr></p:nvSpPr><p:spPr/></p:sp>'
+    + placeholderShape(3, 'Notes Placeholder 2', '<p:ph type="body" idx="1"/>', paragraphs(notes))
+    + '</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:notes>';
+}
+
+function slideRelsXml(layout: TemplateLayout, notesFile?: string): string {
+  return '<?xml version="1.0" encoding="UTF-8" standalone="yes"?>\n'
+    + '<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">'
+    + `<Relationship Id="rId1" Type="${REL_LAYOUT}" Target="../slideLayouts/${path.posix.basename(layout.file)}"/>`
+    + (notesFile ? `<Relationship Id="rId2" Type="${REL_NOTES_SLIDE}" Target="../notesSlides/${notesFile}"/>` : '')
+    + '</Relationships>';
+}
+
+function notesRelsXml(notesMaster: string, slideFile: string): string {
+  return '<?xml version="1.0" encoding="UTF-8" standalone="yes"?>\n'
+    + '<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">'
+    + `<Relationship Id="rId1" Type="${REL_NOTES_MASTER}" Target="../notesMasters/${notesMaster}"/>`
+    + `<Relationship Id="rId2" Type="${REL_SLIDE}" Target="../slides/${slideFile}"/>`
+    + '</Relationships>';
+}
+
+/**
+ * Export songs onto the slide master of a reference .pptx
+ */
+export async function exportWithPptxTemplate(
+  songs: ExtractedLyrics[],
+  options: PptxTemplateOptions
+): Promise<string> {
+  if (!fs.existsSync(options.templatePath)) {
+    throw new Error(`PPTX template not found: ${options.templatePath}`);
+  }
+
+  const zip = new ZipReader(fs.readFileSync(options.templatePath));
+  const files = new Map<string, Buffer>();
+  for (const entry of zip.entries) {
+    if (entry.name.endsWith('/')) continue;
+    // Drop the template's own slides and their notes
+    if (/^ppt\/(slides|notesSlides)\//.test(entry.name)) continue;
+    files.set(entry.name, zip.read(entry));
+  }
+
+  const text = (name: string): string => {
+    const buf = files.get(name);
+    if (!buf) {
+      throw new Error(`${path.basename(options.templatePath)} is not a PowerPoint file (missing ${name})`);
+    }
+    return buf.toString('utf-8');
+  };
+
+  const layouts = listTemplateLayouts(options.templatePath);
+  const lyricLayout = pickLayout(layouts, options.lyricLayout, 'lyric');
+  const titleLayout = options.includeSongTitles ? pickLayout(layouts, options.titleLayout, 'title') : null;
+
+  const translationSource = options.translationSource ?? 'none';
+  const translationProps = [
+    options.translationItalic !== false ? ' i="1"' : '',
+    options.translationFontSize ? ` sz="${Math.round(options.translationFontSize * 100)}"` : '',
+  ].join('');
+  const translationColor = options.translationColor?.replace('#', '') || undefined;
+
+  // Footer box along the bottom of the slide, for layouts without a footer placeholder
+  const slideSize = text('ppt/presentation.xml').match(/<p:sldSz\b[^>]*>/)?.[0] ?? '';
+  const slideW = parseInt(attr(slideSize, 'cx') ?? '12192000', 10);
+  const slideH = parseInt(attr(slideSize, 'cy') ?? '6858000', 10);
+  const footerBox = {
+    x: Math.round(slideW * 0.03),
+    y: Math.round(slideH * 0.92),
+    w: Math.round(slideW * 0.94),
+    h: Math.round(slideH * 0.06),
+  };
+
+  // Notes slides hang off the template's notes master; without one there are no notes
+  const labelDisplay = options.labelDisplay ?? 'none';
+  const notesMaster = text('ppt/_rels/presentation.xml.rels')
+    .match(/<Relationship\b[^>]*Type="[^"]*\/relationships\/notesMaster"[^>]*\/>/)?.[0]
+    .match(/Target="notesMasters\/([^"]+)"/)?.[1] ?? null;
+  if (labelDisplay === 'notes' && !notesMaster
+    && songs.some(song => song.sections.some(section => section.slides.some(slide => slide.isLyric && slide.label)))) {
+    throw new Error(`${path.basename(options.templatePath)} has no notes master, so slide labels can't go in the notes. Use labelDisplay=caption or none.`);
+  }
+
+  // Body placeholder size and font size, from the layout or else its master
+  const layoutXml = text(lyricLayout.file);
+  const layoutRels = files.get(lyricLayout.file.replace(/([^/]+)$/, '_rels/$1.rels'))?.toString('utf-8') ?? '';
+  const masterTarget = layoutRels.match(/Target="\.\.\/slideMasters\/([^"]+)"/)?.[1];
+  const masterXml = masterTarget ? files.get(`ppt/slideMasters/${masterTarget}`)?.toString('utf-8') ?? null : null;
+  const idx = attr(lyricLayout.bodyPlaceholder!, 'idx');
+  const layoutBody = placeholderShapeXml(layoutXml, idx ? new RegExp(`\\bidx="${idx}"`) : /type="body"/);
+  const masterBody = masterXml ? placeholderShapeXml(masterXml, /type="body"/) : null;
+  const bodyBox = shapeBox(layoutBody) ?? shapeBox(masterBody)
+    ?? { w: (slideW * 0.9) / EMU_PER_INCH, h: (slideH * 0.7) / EMU_PER_INCH };
+  const bodyFontSize = levelOneFontSize(layoutBody) ?? levelOneFontSize(masterBody)
+    ?? levelOneFontSize(masterXml?.match(/<p:bodyStyle>[\s\S]*?<\/p:bodyStyle>/)?.[0])
+    ?? options.fontSize ?? 44;
+
+  // Build the new slides
+  const slides: Array<{ xml: string; layout: TemplateLayout; notes?: string }> = [];
+  for (const song of songs) {
+    const credits = options.copyrightFooter ? formatSongCredits(song, options.ccliLicence) : null;
+    const lastSlide = lastLyricSlide(song);
+
+    if (titleLayout) {
+      slides.push({
+        layout: titleLayout,
+        xml: slideXml([placeholderShape(2, 'Title 1', titleLayout.titlePlaceholder!, paragraphs(song.title))]),
+      });
+    }
+
+    for (const section of song.sections) {
+      for (const slideData of section.slides) {
+        if (!slideData.isLyric || !slideData.text || slideData.text.trim() === '') {
+          continue;
+        }
+
+        const { primary, translation } = splitTranslation(slideData, translationSource);
+
+        // Shrink, then split, long slides so they stay inside the body placeholder.
+        // Text is never grown past the master's size.
+        const fit: TextFitResult = options.autoFit
+          ? fitText(primary, { w: bodyBox.w, h: translation ? bodyBox.h * PRIMARY_SHARE : bodyBox.h }, {
+            fontSize: bodyFontSize,
+            minFontSize: Math.min(options.minFontSize ?? bodyFontSize, bodyFontSize),
+            maxFontSize: Math.min(options.maxFontSize ?? bodyFontSize, bodyFontSize),
+          })
+          : { action: 'fit', chunks: [{ text: primary, fontSize: bodyFontSize, fits: true }] };
+        const translations = translation ? divideLines(translation, fit.chunks.length) : [];
+
+        options.onSlideFit?.({
+          song: song.title,
+          section: section.name,
+          action: fit.action,
+          fontSizes: fit.chunks.map(chunk => chunk.fontSize),
+          overflow: fit.chunks.some(chunk => !chunk.fits),
+        });
+
+        fit.chunks.forEach((chunk, index) => {
+          let body = slideData.label && labelDisplay === 'caption'
+            ? paragraphs(slideData.label, ' i="1" sz="1800"', translationColor)
+            : '';
+          body += paragraphs(chunk.text, '', undefined, slideData.runs);
+          if (translations[index]) {
+            body += paragraphs(translations[index], translationProps, translationColor);
+          }
+          // fontScale is in thousandths of a percent of the master's sizes
+          const bodyPr = chunk.fontSize < bodyFontSize
+            ? `<a:bodyPr><a:normAutofit fontScale="${Math.round((chunk.fontSize / bodyFontSize) * 100000)}"/></a:bodyPr>`
+            : undefined;
+          const shapes = [placeholderShape(2, 'Lyrics 1', lyricLayout.bodyPlaceholder!, body, bodyPr)];
+          if (credits && slideData === lastSlide && index === fit.chunks.length - 1) {
+            shapes.push(lyricLayout.footerPlaceholder
+              ? placeholderShape(3, 'Footer 2', lyricLayout.footerPlaceholder, paragraphs(credits))
+              : textBoxShape(3, 'Copyright 2', footerBox, paragraphs(credits, ' sz="1100"')));
+          }
+
+          // Section name (and label) as notes, as in the generated layout
+          const notes: string[] = [];
+          if (section.name) {
+            const part = fit.chunks.length > 1 ? ` (${index + 1}/${fit.chunks.length})` : '';
+            notes.push(`Section: ${section.name}${part}`);
+          }
+          if (slideData.label && labelDisplay === 'notes') {
+            notes.push(`Label: ${slideData.label}`);
+          }
+          slides.push({
+            layout: lyricLayout,
+            xml: slideXml(shapes),
+            notes: notesMaster && notes.length > 0 ? notes.join('\n') : undefined,
+          });
+        });
+      }
+    }
+  }
+
+  // Point the presentation at the new slides
+  let presentationRels = text('ppt/_rels/presentation.xml.rels')
+    .replace(/<Relationship\b[^>]*Type="[^"]*\/relationships\/slide"[^>]*\/>/g, '');
+  const usedIds = (presentationRels.match(/Id="rId(\d+)"/g) ?? []).map(id => parseInt(id.replace(/\D/g, ''), 10));
+  let nextRelId = Math.max(0, ...usedIds) + 1;
+
+  let contentTypes = text('[Content_Types].xml')
+    .replace(/<Override\b[^>]*PartName="\/ppt\/(slides|notesSlides)\/[^"]*"[^>]*\/>/g, '');
+
+  const slideIdEntries: string[] = [];
+  const newRels: string[] = [];
+  const newOverrides: string[] = [];
+  slides.forEach((slide, index) => {
+    const number = index + 1;
+    const relId = `rId${nextRelId++}`;
+    files.set(`ppt/slides/slide${number}.xml`, Buffer.from(slide.xml, 'utf-8'));
+    const notesFile = slide.notes ? `notesSlide${number}.xml` : undefined;
+    files.set(`ppt/slides/_rels/slide${number}.xml.rels`, Buffer.from(slideRelsXml(slide.layout, notesFile), 'utf-8'));
+    if (slide.notes && notesMaster) {
+      files.set(`ppt/notesSlides/${notesFile}`, Buffer.from(notesSlideXml(slide.notes), 'utf-8'));
+      files.set(`ppt/notesSlides/_rels/${notesFile}.rels`, Buffer.from(notesRelsXml(notesMaster, `slide${number}.xml`), 'utf-8'));
+      newOverrides.push(`<Override PartName="/ppt/notesSlides/${notesFile}" ContentType="${NOTES_CONTENT_TYPE}"/>`);
+    }
+    slideIdEntries.push(`<p:sldId id="${256 + index}" r:id="${relId}"/>`);
+    newRels.push(`<Relationship Id="${relId}" Type="${REL_SLIDE}" Target="slides/slide${number}.xml"/>`);
+    newOverrides.push(`<Override PartName="/ppt/slides/slide${number}.xml" ContentType="${SLIDE_CONTENT_TYPE}"/>`);
+  });
+
+  presentationRels = presentationRels.replace('</Relationships>', `${newRels.join('')}</Relationships>`);
+  contentTypes = contentTypes.replace('</Types>', `${newOverrides.join('')}</Types>`);
+
+  const slideIdList = `<p:sldIdLst>${slideIdEntries.join('')}</p:sldIdLst>`;
+  let presentation = text('ppt/presentation.xml')
+    .replace(/<p:sldIdLst>[\s\S]*?<\/p:sldIdLst>|<p:sldIdLst\/>/, '')
+    .replace(/<p:custShowLst>[\s\S]*?<\/p:custShowLst>/, '')
+    .replace(SECTION_EXT, '');
+  // sldIdLst follows the master and notes/handout master id lists
+  const anchor = presentation.match(/<\/p:handoutMasterIdLst>|<\/p:notesMasterIdLst>|<\/p:sldMasterIdLst>/g);
+  const insertAfter = anchor ? anchor[anchor.length - 1] : null;
+  if (!insertAfter) {
+    throw new Error('Template presentation.xml has no slide master list');
+  }
+  presentation = presentation.replace(insertAfter, `${insertAfter}${slideIdList}`);
+
+  files.set('ppt/presentation.xml', Buffer.from(presentation, 'utf-8'));
+  files.set('ppt/_rels/presentation.xml.rels', Buffer.from(presentationRels, 'utf-8'));
+  files.set('[Content_Types].xml', Buffer.from(contentTypes, 'utf-8'));
+
+  const appXml = files.get('docProps/app.xml');
+  if (appXml) {
+    const updated = appXml.toString('utf-8').replace(/<Slides>\d+<\/Slides>/, `<Slides>${slides.length}</Slides>`);
+    files.set('docProps/app.xml', Buffer.from(updated, 'utf-8'));
+  }
+
+  let outputFile = options.outputPath;
+  if (!outputFile.endsWith('.pptx')) {
+    outputFile = `${outputFile}.pptx`;
+  }
+
+  // [Content_Types].xml conventionally comes first in the package
+  const ordered = ['[Content_Types].xml', ...[...files.keys()].filter(name => name !== '[Content_Types].xml')];
+  fs.writeFileSync(outputFile, createZip(ordered.map(name => ({ name, data: files.get(name)! }))));
+
+  return outputFile;
+}
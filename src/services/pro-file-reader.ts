@@ -0,0 +1,378 @@
+/**
+ * ProPresenter File Reader - Offline import of .pro and .proplaylist files
+ *
+ * Reads ProPresenter 7 presentation documents straight from disk so lyrics can
+ * be extracted and exported without a running ProPresenter instance. Output
+ * uses the same PresentationInfo shape as the Network API client, so the
+ * lyrics extractor and exporters work unchanged.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { compactMetadata } from '../propresenter-client';
+import type { ArrangementInfo, GroupInfo, PresentationInfo, SlideInfo, SongMetadata, TextRun } from '../propresenter-client';
+import {
+  ProtoField,
+  getFloat,
+  getMessage,
+  getMessages,
+  getPath,
+  getString,
+  getVarint,
+  tryDecodeMessage,
+} from '../utils/protobuf';
+import { ZipReader } from '../utils/zip-reader';
+import { rtfBlobsToRuns, rtfToPlainText } from '../utils/rtf';
+
+// rv.data.Presentation field numbers
+const PRESENTATION = {
+  uuid: 2,
+  name: 3,
+  category: 6,
+  selectedArrangement: 10,
+  arrangements: 11,
+  cueGroups: 12,
+  cues: 13,
+  ccli: 14,
+};
+
+const CCLI = { author: 1, artistCredits: 2, publisher: 4, copyrightYear: 5, songNumber: 6, album: 8 };
+
+const ARRANGEMENT = { uuid: 1, name: 2, groupIdentifiers: 3 };
+const CUE_GROUP = { group: 1, cueIdentifiers: 2 };
+const GROUP = { uuid: 1, name: 2, color: 3 };
+const CUE = { uuid: 1, name: 2, actions: 10, isEnabled: 12 };
+const ACTION = { label: 3, slide: 23 };
+const LABEL_TEXT = 1;
+const UUID_STRING = 1;
+
+// Action.slide -> SlideType.presentation -> PresentationSlide.base_slide
+const BASE_SLIDE_PATH = [ACTION.slide, 2, 1];
+// Action.slide -> SlideType.presentation -> PresentationSlide.notes
+const NOTES_PATH = [ACTION.slide, 2, 2];
+
+// rv.data.PlaylistDocument / Playlist / PlaylistItem field numbers
+const PLAYLIST_DOCUMENT = { rootNode: 3 };
+const PLAYLIST = { name: 2, playlists: 12, items: 13 };
+const PLAYLIST_CHILDREN = 1;
+const PLAYLIST_ITEM = { presentation: 5 };
+const PLAYLIST_PRESENTATION = { documentPath: 1 };
+
+const RTF_PREFIX = Buffer.from('{\\rtf');
+
+export interface OfflinePlaylist {
+  name: string;
+  presentations: PresentationInfo[];
+}
+
+function readUuid(fields: ProtoField[], number: number): string {
+  const message = getMessage(fields, number);
+  return (message && getString(message, UUID_STRING)) || '';
+}
+
+function colorToHex(fields: ProtoField[] | undefined): string {
+  if (!fields) return '';
+  const channel = (n: number) => {
+    const value = Math.max(0, Math.min(1, getFloat(fields, n) ?? 0));
+    return Math.round(value * 255).toString(16).padStart(2, '0');
+  };
+  return `#${channel(1)}${channel(2)}${channel(3)}`.toUpperCase();
+}
+
+/**
+ * Collect every RTF blob in a message subtree, in document order
+ */
+function collectRtf(fields: ProtoField[], out: string[] = [], depth = 0): string[] {
+  if (depth > 12) return out;
+
+  for (const field of fields) {
+    if (!Buffer.isBuffer(field.value) || field.wireType !== 2) continue;
+    const bytes = field.value;
+
+    if (bytes.subarray(0, RTF_PREFIX.length).equals(RTF_PREFIX)) {
+      out.push(bytes.toString('utf-8'));
+      continue;
+    }
+
+    const nested = tryDecodeMessage(bytes);
+    if (nested && nested.length > 0) {
+      collectRtf(nested, out, depth + 1);
+    }
+  }
+
+  return out;
+}
+
+/**
+ * Every length-delimited value in a message subtree, read as text
+ */
+function collectStrings(fields: ProtoField[], out: string[] = [], depth = 0): string[] {
+  if (depth > 6) return out;
+
+  for (const field of fields) {
+    if (!Buffer.isBuffer(field.value) || field.wireType !== 2) continue;
+    out.push(field.value.toString('utf-8'));
+    const nested = tryDecodeMessage(field.value);
+    if (nested && nested.length > 0) {
+      collectStrings(nested, out, depth + 1);
+    }
+  }
+
+  return out;
+}
+
+/**
+ * File name at the end of a path or file URL, percent-decoded and lowercased
+ */
+function documentBaseName(value: string): string {
+  let decoded = value;
+  try {
+    decoded = decodeURIComponent(value);
+  } catch {
+    // Not percent-encoded
+  }
+  return path.posix.basename(decoded.replace(/\\/g, '/')).toLowerCase();
+}
+
+function rtfBlobsToElements(blobs: string[]): string[] {
+  return blobs
+    .map(rtfToPlainText)
+    .filter(text => text.trim().length > 0);
+}
+
+function rtfBlobsToText(blobs: string[]): string {
+  return rtfBlobsToElements(blobs).join('\n');
+}
+
+function parseCue(cue: ProtoField[], index: number, enabledFlagInUse: boolean): SlideInfo {
+  let elements: string[] = [];
+  let runs: TextRun[] | undefined;
+  let notes = '';
+  let label = '';
+
+  for (const action of getMessages(cue, CUE.actions)) {
+    const baseSlide = getPath(action, BASE_SLIDE_PATH);
+    const slideNotes = getPath(action, NOTES_PATH);
+
+    if (elements.length === 0) {
+      // Prefer the slide's own elements; fall back to scanning the whole action
+      const blobs = collectRtf(baseSlide ?? action);
+      elements = rtfBlobsToElements(blobs);
+      runs = rtfBlobsToRuns(blobs);
+    }
+    if (!notes && slideNotes) {
+      notes = rtfBlobsToText(collectRtf(slideNotes));
+    }
+    if (!label) {
+      const labelMessage = getMessage(action, ACTION.label);
+      label = (labelMessage && getString(labelMessage, LABEL_TEXT)) || '';
+    }
+  }
+
+  // proto3 omits false booleans, so only trust a missing flag once we know
+  // this file writes it for other cues.
+  const enabledFlag = getVarint(cue, CUE.isEnabled);
+  const enabled = enabledFlag !== undefined ? enabledFlag !== 0 : !enabledFlagInUse;
+
+  return {
+    index,
+    text: elements.join('\n'),
+    elements: elements.length > 1 ? elements : undefined,
+    runs,
+    notes,
+    label: label || getString(cue, CUE.name) || '',
+    enabled,
+  };
+}
+
+function parseMetadata(fields: ProtoField[]): SongMetadata | undefined {
+  const ccli = getMessage(fields, PRESENTATION.ccli);
+  if (!ccli) return undefined;
+  const songNumber = getVarint(ccli, CCLI.songNumber);
+  const year = getVarint(ccli, CCLI.copyrightYear);
+  return compactMetadata({
+    ccliNumber: songNumber ? String(songNumber) : undefined,
+    author: getString(ccli, CCLI.author)?.trim() || undefined,
+    artist: getString(ccli, CCLI.artistCredits)?.trim() || undefined,
+    publisher: getString(ccli, CCLI.publisher)?.trim() || undefined,
+    copyrightYear: year || undefined,
+    album: getString(ccli, CCLI.album)?.trim() || undefined,
+  });
+}
+
+function parseArrangements(fields: ProtoField[]): ArrangementInfo[] | undefined {
+  const arrangements = getMessages(fields, PRESENTATION.arrangements).map(arrangement => ({
+    uuid: readUuid(arrangement, ARRANGEMENT.uuid),
+    name: getString(arrangement, ARRANGEMENT.name) || 'Unnamed Arrangement',
+    groups: getMessages(arrangement, ARRANGEMENT.groupIdentifiers)
+      .map(id => getString(id, UUID_STRING) || '')
+      .filter(Boolean),
+  }));
+  return arrangements.length > 0 ? arrangements : undefined;
+}
+
+/**
+ * Decode a .pro document buffer into a PresentationInfo
+ */
+export function parseProPresentation(buf: Buffer, filePath?: string): PresentationInfo {
+  const fields = tryDecodeMessage(buf);
+  if (!fields) {
+    throw new Error(`Not a ProPresenter 7 presentation${filePath ? `: ${filePath}` : ''}`);
+  }
+
+  const cueMessages = getMessages(fields, PRESENTATION.cues);
+  const enabledFlagInUse = cueMessages.some(cue => getVarint(cue, CUE.isEnabled) !== undefined);
+  const cuesByUuid = new Map<string, ProtoField[]>();
+  for (const cue of cueMessages) {
+    cuesByUuid.set(readUuid(cue, CUE.uuid), cue);
+  }
+
+  const groups: GroupInfo[] = [];
+  for (const cueGroup of getMessages(fields, PRESENTATION.cueGroups)) {
+    const group = getMessage(cueGroup, CUE_GROUP.group) ?? [];
+    const cueIds = getMessages(cueGroup, CUE_GROUP.cueIdentifiers)
+      .map(id => getString(id, UUID_STRING) || '');
+
+    let slideIndex = 0;
+    const slides: SlideInfo[] = [];
+    for (const cueId of cueIds) {
+      const cue = cuesByUuid.get(cueId);
+      if (cue) {
+        slides.push(parseCue(cue, slideIndex++, enabledFlagInUse));
+      }
+    }
+
+    groups.push({
+      uuid: readUuid(group, GROUP.uuid) || undefined,
+      name: getString(group, GROUP.name) || 'Unnamed Group',
+      color: colorToHex(getMessage(group, GROUP.color)),
+      slides,
+    });
+  }
+
+  // Presentations without groups still carry their cues; keep them in one group
+  if (groups.length === 0 && cueMessages.length > 0) {
+    groups.push({
+      name: 'Unnamed Group',
+      color: '',
+      slides: cueMessages.map((cue, i) => parseCue(cue, i, enabledFlagInUse)),
+    });
+  }
+
+  const fallbackName = filePath ? path.basename(filePath, path.extname(filePath)) : 'Unnamed';
+
+  return {
+    uuid: readUuid(fields, PRESENTATION.uuid),
+    name: getString(fields, PRESENTATION.name) || fallbackName,
+    path: filePath,
+    hasTimeline: false,
+    destination: 'presentation',
+    groups,
+    metadata: parseMetadata(fields),
+    arrangements: parseArrangements(fields),
+    selectedArrangementUuid: readUuid(fields, PRESENTATION.selectedArrangement) || undefined,
+  };
+}
+
+/**
+ * Read a single .pro file from disk
+ */
+export function readProFile(filePath: string): PresentationInfo {
+  return parseProPresentation(fs.readFileSync(filePath), filePath);
+}
+
+/**
+ * Read a .proplaylist bundle. Presentations are returned in the order of the
+ * playlist's items; files the items don't reference follow in archive order.
+ */
+export function readProPlaylist(filePath: string): OfflinePlaylist {
+  const zip = new ZipReader(fs.readFileSync(filePath));
+  const proEntries = zip.entries.filter(entry => entry.name.toLowerCase().endsWith('.pro'));
+  const dataEntry = zip.find(entry => entry.name === 'data' || entry.name.endsWith('/data'));
+
+  let name = path.basename(filePath, path.extname(filePath));
+  let order: Map<string, number> | null = null;
+
+  if (dataEntry) {
+    const data = zip.read(dataEntry);
+    const document = tryDecodeMessage(data);
+    // PlaylistDocument.root_node -> child playlists -> Playlist.name
+    const rootNode = document ? getMessage(document, PLAYLIST_DOCUMENT.rootNode) : undefined;
+    const playlists = rootNode ? getMessages(getMessage(rootNode, PLAYLIST.playlists) ?? [], PLAYLIST_CHILDREN) : [];
+    const playlistName = playlists.length === 1 ? getString(playlists[0], PLAYLIST.name) : undefined;
+    if (playlistName) {
+      name = playlistName;
+    }
+
+    // Presentation items point at their document by path; the bundle stores
+    // each document under its file name.
+    const entriesByName = new Map(proEntries.map(entry => [documentBaseName(entry.name), entry.name]));
+    order = new Map();
+    const items = playlists.flatMap(playlist =>
+      getMessages(getMessage(playlist, PLAYLIST.items) ?? [], PLAYLIST_CHILDREN));
+    for (const item of items) {
+      const documentPath = getPath(item, [PLAYLIST_ITEM.presentation, PLAYLIST_PRESENTATION.documentPath]);
+      if (!documentPath) continue;
+      const entryName = collectStrings(documentPath)
+        .map(value => entriesByName.get(documentBaseName(value)))
+        .find(Boolean);
+      if (entryName && !order.has(entryName)) {
+        order.set(entryName, order.size);
+      }
+    }
+  }
+
+  const position = (entry: { name: string }) => order?.get(entry.name) ?? Number.MAX_SAFE_INTEGER;
+  const sorted = [...proEntries].sort((a, b) => position(a) - position(b));
+
+  const presentations = sorted.map(entry =>
+    parseProPresentation(zip.read(entry), entry.name)
+  );
+
+  return { name, presentations };
+}
+
+/**
+ * Load presentations from a .pro file, a .proplaylist bundle, or a directory
+ * of .pro files (e.g. a ProPresenter library folder).
+ */
+export function loadPresentationsFromPath(inputPath: string): OfflinePlaylist {
+  if (!fs.existsSync(inputPath)) {
+    throw new Error(`Path not found: ${inputPath}`);
+  }
+
+  const stat = fs.statSync(inputPath);
+  if (stat.isDirectory()) {
+    const files = fs.readdirSync(inputPath)
+      .filter(file => file.toLowerCase().endsWith('.pro'))
+      .sort((a, b) => a.localeCompare(b));
+    return {
+      name: path.basename(inputPath),
+      presentations: files.map(file => readProFile(path.join(inputPath, file))),
+    };
+  }
+
+  const ext = path.extname(inputPath).toLowerCase();
+  if (ext === '.proplaylist') {
+    return readProPlaylist(inputPath);
+  }
+  if (ext === '.pro') {
+    const presentation = readProFile(inputPath);
+    return { name: presentation.name, presentations: [presentation] };
+  }
+
+  throw new Error(`Unsupported file type "${ext}". Expected .pro, .proplaylist or a folder of .pro files.`);
+}
+
+/**
+ * True when a CLI argument looks like an offline source rather than a playlist UUID
+ */
+export function isOfflineSource(arg: string): boolean {
+  const ext = path.extname(arg).toLowerCase();
+  if (ext === '.pro' || ext === '.proplaylist') return true;
+  try {
+    return fs.statSync(arg).isDirectory();
+  } catch {
+    return false;
+  }
+}
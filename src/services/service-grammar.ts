@@ -0,0 +1,395 @@
+/**
+ * Service Order Grammar
+ *
+ * Describes how a church's service order PDF is laid out so the parser can
+ * read it without code changes: which lines are songs, videos and readings,
+ * which lines move songs to the next praise slot, where the leader and notes
+ * are, and how special services are recognised. With the positional PDF
+ * layout, rules can also depend on a line being bold or a heading, and on the
+ * heading it sits under.
+ *
+ * The built-in "st-andrews" grammar is the layout the parser was written for.
+ * Other grammars are JSON files in ~/.propresenter-words/service-grammars/<id>.json;
+ * anything a file leaves out is taken from the built-in grammar.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import type { PraiseSlot, SpecialServiceType } from '../types/service-order';
+import { parsePattern } from './classification-rules';
+
+export type GrammarItemType = 'song' | 'video' | 'bible';
+
+/**
+ * How a line is printed, from the positional PDF layout: "heading" is larger
+ * than the body text, "bold" is mostly bold, "plain" is neither
+ */
+export type GrammarLineStyle = 'heading' | 'bold' | 'plain';
+
+export type GrammarPdfLayout = 'text' | 'positional';
+
+/**
+ * Patterns are case-insensitive regexes, or "/pattern/flags" for other flags
+ * (e.g. "/Room 1/" to match case).
+ */
+export interface GrammarItemRule {
+  id: string;
+  description?: string;
+  /** Start of an item line; the rest of the line is the item */
+  pattern: string;
+  type: GrammarItemType;
+  /** Removed from the start of the item, e.g. hymn book numbers like "MP 59" */
+  stripPrefix?: string;
+  /** Marks a song as a video; removed from the title */
+  videoMarker?: string;
+  /** Read the leader from the item (default true) */
+  leader?: boolean;
+  /** Remove quotes around the title */
+  stripQuotes?: boolean;
+  /** Split a song title on this text into several songs */
+  split?: string;
+  /** Only match lines printed this way */
+  style?: GrammarLineStyle;
+  /**
+   * Only match under a heading or bold line matching this pattern, e.g. a
+   * plain list of songs under "Worship". Such rules match plain lines unless
+   * style says otherwise.
+   */
+  section?: string;
+}
+
+export interface GrammarSlotMarker {
+  pattern: string;
+  slot: PraiseSlot;
+  /** Only match lines printed this way */
+  style?: GrammarLineStyle;
+}
+
+export interface GrammarSpecialService {
+  type: Exclude<SpecialServiceType, null>;
+  pattern: string;
+}
+
+export interface ServiceGrammar {
+  id: string;
+  name: string;
+  description?: string;
+  /**
+   * How PDFs are read: "text" takes pdf-parse's text as it comes; "positional"
+   * rebuilds columns, tables and 2-up pages from where the text sits, and
+   * keeps bold and heading cues for style and section rules
+   */
+  pdfLayout: GrammarPdfLayout;
+  /** Drop the second copy of orders printed twice on a page (2-up layouts) */
+  dedupeRepeatedPages: boolean;
+  /** Finds the date in the first line; group 1 when the pattern has one */
+  datePattern: string;
+  /** Lines that are never items or markers (times, room notes, ...) */
+  ignore: string[];
+  /** Praise slot of songs before the first marker */
+  initialSlot: PraiseSlot;
+  /** Lines that put the songs after them in another praise slot; first match wins */
+  slotMarkers: GrammarSlotMarker[];
+  /** Lines that are items; first match wins, other lines are skipped */
+  items: GrammarItemRule[];
+  metadata: {
+    /** At the end of an item; group 1 is the leader, e.g. "(Praise Team)" */
+    leader?: string;
+    /** A line straight after an item holding its notes; group 1 is the text */
+    notesLine?: string;
+  };
+  kids: {
+    /** Marks a video as kids content */
+    pattern: string;
+    /** In the lines after a video, means it's the kids video (e.g. "children leave") */
+    contextPattern?: string;
+    /** Stops looking further ahead */
+    contextStop?: string;
+    /** How many lines to look ahead */
+    lookahead: number;
+  };
+  /** Checked against the first headerLines lines; first match wins */
+  specialServices: GrammarSpecialService[];
+  headerLines: number;
+}
+
+export interface ServiceGrammarSummary extends ServiceGrammar {
+  builtIn: boolean;
+  /** Set for grammars loaded from the grammars folder */
+  filePath?: string;
+}
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const GRAMMARS_DIR = path.join(CONFIG_DIR, 'service-grammars');
+
+const GRAMMAR_ID_PATTERN = /^[a-z0-9][a-z0-9-]*$/;
+
+const ITEM_TYPES: GrammarItemType[] = ['song', 'video', 'bible'];
+const LINE_STYLES: GrammarLineStyle[] = ['heading', 'bold', 'plain'];
+const PDF_LAYOUTS: GrammarPdfLayout[] = ['text', 'positional'];
+const SLOTS: PraiseSlot[] = ['praise1', 'praise2', 'praise3', 'kids'];
+const SPECIAL_SERVICES: Array<GrammarSpecialService['type']> = [
+  'remembrance', 'christmas', 'easter', 'carol', 'communion', 'good-friday', 'nativity',
+];
+
+export const DEFAULT_SERVICE_GRAMMAR: ServiceGrammar = {
+  id: 'st-andrews',
+  name: "St Andrew's",
+  description: 'PRAISE:, BIBLE READING: and VIDEO: lines; slots follow Call to Worship, Prayers for Others and Prayerful Reflection',
+  pdfLayout: 'text',
+  dedupeRepeatedPages: true,
+  datePattern: '(\\d{1,2}(?:st|nd|rd|th)?\\s+\\w+\\s+\\d{4})',
+  ignore: [
+    '^\\d{1,2}:\\d{2}(am|pm)',
+    '^\\[.*\\]$',
+    '/Live Streamed|Room 1|leave for/',
+  ],
+  initialSlot: 'praise1',
+  slotMarkers: [
+    { pattern: 'call to worship|opening prayer', slot: 'praise1' },
+    { pattern: 'praying for others|prayers for others', slot: 'praise2' },
+    // Communion services have no Prayers for Others; the song before communion follows the sermon
+    { pattern: '^sermon(:|$)', slot: 'praise2' },
+    // Good Friday
+    { pattern: '^reflection(:|$)', slot: 'praise2' },
+    { pattern: 'prayerful reflection|reflection and response', slot: 'praise3' },
+    { pattern: 'act of communion|sacrament of communion|communion:', slot: 'praise3' },
+    // Nativity and Kids Ministry services
+    { pattern: '^epilogue', slot: 'praise3' },
+  ],
+  items: [
+    {
+      id: 'praise',
+      description: 'PRAISE: Song, PRAISE \'Song\' or PRAISE MP 59 \'Song\'',
+      pattern: '^PRAISE(:\\s*|\\s+(?!&))',
+      type: 'song',
+      stripPrefix: '^MP\\s*\\d+\\s*',
+      videoMarker: '\\(Video\\)',
+      stripQuotes: true,
+      split: ' / ',
+    },
+    {
+      id: 'community-singing',
+      description: 'Funerals and special services',
+      pattern: '^COMMUNITY SINGING:\\s*',
+      type: 'song',
+      leader: false,
+      stripQuotes: true,
+      split: ' / ',
+    },
+    {
+      id: 'praise-and-play',
+      description: 'Nativity all-together services',
+      pattern: '^PRAISE\\s*&\\s*PLAY:\\s*',
+      type: 'song',
+      videoMarker: '\\(Video\\)',
+      split: ' / ',
+    },
+    {
+      id: 'video',
+      description: 'Standalone videos (Remembrance, closing videos)',
+      pattern: '^VIDEO:\\s*',
+      type: 'video',
+    },
+    { id: 'bible-reading', pattern: '^BIBLE READING:\\s*', type: 'bible' },
+    { id: 'scripture', description: 'Good Friday', pattern: '^SCRIPTURE:\\s*', type: 'bible' },
+  ],
+  metadata: {
+    leader: '\\(([^)]+)\\)$',
+  },
+  kids: {
+    pattern: "\\b(kids?|children's?|children)\\b",
+    contextPattern: 'children leave|leave for',
+    contextStop: '^(praise|bible|sermon|prayer|family news|time of)',
+    lookahead: 3,
+  },
+  specialServices: [
+    { type: 'good-friday', pattern: 'good friday' },
+    { type: 'remembrance', pattern: 'remembrance' },
+    { type: 'christmas', pattern: 'christmas|christingle' },
+    { type: 'nativity', pattern: 'nativity|christmas eve' },
+    { type: 'carol', pattern: 'carol' },
+    { type: 'easter', pattern: 'easter|palm sunday' },
+    { type: 'communion', pattern: 'communion|new members|welcoming' },
+  ],
+  headerLines: 10,
+};
+
+function ensureGrammarsDir(): void {
+  if (!fs.existsSync(GRAMMARS_DIR)) {
+    fs.mkdirSync(GRAMMARS_DIR, { recursive: true });
+  }
+}
+
+/**
+ * Fill any parts a (possibly hand-written) grammar leaves out from the built-in one
+ */
+export function normalizeServiceGrammar(raw: Partial<ServiceGrammar> & { id: string }): ServiceGrammar {
+  const base = DEFAULT_SERVICE_GRAMMAR;
+  return {
+    ...base,
+    ...raw,
+    name: raw.name || raw.id,
+    description: raw.description,
+    metadata: { ...base.metadata, ...raw.metadata },
+    kids: { ...base.kids, ...raw.kids },
+  };
+}
+
+/**
+ * Check a grammar's shape and patterns. Returns a list of problems.
+ */
+export function validateServiceGrammar(grammar: ServiceGrammar): string[] {
+  const errors: string[] = [];
+  const checkPattern = (where: string, pattern: unknown) => {
+    if (pattern === undefined || pattern === '') return;
+    try {
+      parsePattern(String(pattern));
+    } catch (error: any) {
+      errors.push(`${where}: invalid pattern (${error.message})`);
+    }
+  };
+  const checkList = (key: 'ignore' | 'slotMarkers' | 'items' | 'specialServices') => {
+    if (Array.isArray(grammar[key])) return true;
+    errors.push(`${key} must be a list`);
+    return false;
+  };
+
+  const checkStyle = (where: string, style: unknown) => {
+    if (style !== undefined && !LINE_STYLES.includes(style as GrammarLineStyle)) {
+      errors.push(`${where}: style must be one of ${LINE_STYLES.join(', ')}`);
+    }
+  };
+
+  if (!PDF_LAYOUTS.includes(grammar.pdfLayout)) {
+    errors.push(`pdfLayout must be one of ${PDF_LAYOUTS.join(', ')}`);
+  }
+  checkPattern('datePattern', grammar.datePattern);
+  if (!SLOTS.includes(grammar.initialSlot)) {
+    errors.push(`initialSlot must be one of ${SLOTS.join(', ')}`);
+  }
+  if (checkList('ignore')) {
+    grammar.ignore.forEach((pattern, index) => checkPattern(`ignore #${index + 1}`, pattern));
+  }
+  if (checkList('slotMarkers')) {
+    grammar.slotMarkers.forEach((marker, index) => {
+      const where = `Slot marker #${index + 1}`;
+      if (!marker?.pattern) errors.push(`${where} needs a pattern`);
+      checkPattern(where, marker?.pattern);
+      if (!SLOTS.includes(marker?.slot)) errors.push(`${where}: slot must be one of ${SLOTS.join(', ')}`);
+      checkStyle(where, marker?.style);
+    });
+  }
+  if (checkList('items')) {
+    const ids = new Set<string>();
+    grammar.items.forEach((item, index) => {
+      const where = `Item ${item?.id ? `"${item.id}"` : `#${index + 1}`}`;
+      if (!item?.id) errors.push(`${where} needs an id`);
+      if (item?.id && ids.has(item.id)) errors.push(`${where} has a duplicate id`);
+      ids.add(item?.id);
+      if (!item?.pattern) errors.push(`${where} needs a pattern`);
+      if (!ITEM_TYPES.includes(item?.type)) errors.push(`${where}: type must be one of ${ITEM_TYPES.join(', ')}`);
+      checkPattern(where, item?.pattern);
+      checkPattern(`${where} stripPrefix`, item?.stripPrefix);
+      checkPattern(`${where} videoMarker`, item?.videoMarker);
+      checkPattern(`${where} section`, item?.section);
+      checkStyle(where, item?.style);
+    });
+  }
+  checkPattern('metadata.leader', grammar.metadata?.leader);
+  checkPattern('metadata.notesLine', grammar.metadata?.notesLine);
+  checkPattern('kids.pattern', grammar.kids?.pattern);
+  checkPattern('kids.contextPattern', grammar.kids?.contextPattern);
+  checkPattern('kids.contextStop', grammar.kids?.contextStop);
+  if (checkList('specialServices')) {
+    grammar.specialServices.forEach((special, index) => {
+      const where = `Special service #${index + 1}`;
+      if (!SPECIAL_SERVICES.includes(special?.type)) {
+        errors.push(`${where}: type must be one of ${SPECIAL_SERVICES.join(', ')}`);
+      }
+      checkPattern(where, special?.pattern);
+    });
+  }
+
+  return errors;
+}
+
+function loadUserGrammars(): ServiceGrammarSummary[] {
+  try {
+    if (!fs.existsSync(GRAMMARS_DIR)) {
+      return [];
+    }
+    const grammars: ServiceGrammarSummary[] = [];
+    for (const file of fs.readdirSync(GRAMMARS_DIR)) {
+      if (!file.toLowerCase().endsWith('.json')) continue;
+      const filePath = path.join(GRAMMARS_DIR, file);
+      try {
+        const raw = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+        const id = path.basename(file, path.extname(file)).toLowerCase();
+        const grammar = normalizeServiceGrammar({ ...raw, id });
+        const errors = validateServiceGrammar(grammar);
+        if (errors.length > 0) {
+          console.warn(`Ignoring ${filePath}: ${errors[0]}`);
+          continue;
+        }
+        grammars.push({ ...grammar, builtIn: false, filePath });
+      } catch (error: any) {
+        console.warn(`Ignoring ${filePath}: ${error.message}`);
+      }
+    }
+    return grammars;
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * All grammars, built-in first. A file with the built-in id replaces it.
+ */
+export function listServiceGrammars(): ServiceGrammarSummary[] {
+  const userGrammars = loadUserGrammars();
+  const builtIn = userGrammars.some(grammar => grammar.id === DEFAULT_SERVICE_GRAMMAR.id)
+    ? []
+    : [{ ...DEFAULT_SERVICE_GRAMMAR, builtIn: true }];
+  return [...builtIn, ...userGrammars.sort((a, b) => a.name.localeCompare(b.name))];
+}
+
+export function getServiceGrammar(id: string): ServiceGrammarSummary | null {
+  const wanted = id.trim().toLowerCase();
+  return listServiceGrammars().find(grammar => grammar.id === wanted) ?? null;
+}
+
+/**
+ * Look up a grammar (the built-in one when no id is given), throwing with the
+ * available ids when it doesn't exist
+ */
+export function requireServiceGrammar(id?: string | null): ServiceGrammar {
+  const grammar = getServiceGrammar(id || DEFAULT_SERVICE_GRAMMAR.id);
+  if (!grammar) {
+    const available = listServiceGrammars().map(g => g.id).join(', ');
+    throw new Error(`Unknown service grammar "${id}". Available: ${available}`);
+  }
+  return grammar;
+}
+
+/**
+ * Copy a grammar to a new file in the grammars folder for editing
+ */
+export function copyServiceGrammar(fromId: string, newId: string, name?: string): ServiceGrammarSummary {
+  const id = newId.trim().toLowerCase();
+  if (!GRAMMAR_ID_PATTERN.test(id)) {
+    throw new Error('Grammar id may only contain lowercase letters, numbers and dashes');
+  }
+  const { builtIn, filePath: sourcePath, ...source } = requireServiceGrammar(fromId) as ServiceGrammarSummary;
+  const grammar = normalizeServiceGrammar({ ...source, id, name: name || id });
+
+  ensureGrammarsDir();
+  const filePath = path.join(GRAMMARS_DIR, `${id}.json`);
+  fs.writeFileSync(filePath, JSON.stringify(grammar, null, 2), 'utf-8');
+  return { ...grammar, builtIn: false, filePath };
+}
+
+export function getServiceGrammarsDir(): string {
+  return GRAMMARS_DIR;
+}
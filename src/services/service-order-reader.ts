@@ -0,0 +1,173 @@
+/**
+ * Service Order Reader
+ * Gets the text of a service order for the parser, one item per line, from a
+ * PDF, a Word document (.docx), plain text or Markdown. Word and Markdown
+ * table rows become one line with the cells separated by tabs, so
+ * "PRAISE: | Song Title" reads like the PDF line "PRAISE:	Song Title".
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { ZipReader } from '../utils/zip-reader';
+import { ensureDomMatrix, readPdfLayout } from './pdf-layout';
+import type { ServiceOrderLine } from '../types/service-order';
+
+export const SERVICE_ORDER_EXTENSIONS = ['.pdf', '.docx', '.txt', '.md'];
+
+/**
+ * Whether a file name has a service order extension we can read
+ */
+export function isServiceOrderFile(fileName: string): boolean {
+  return SERVICE_ORDER_EXTENSIONS.includes(path.extname(fileName).toLowerCase());
+}
+
+/**
+ * Read a service order's text. The format comes from the extension, which can
+ * be given separately for uploads stored without one.
+ */
+export async function readServiceOrderText(filePath: string, extension = path.extname(filePath)): Promise<string> {
+  switch (extension.toLowerCase()) {
+    case '.pdf':
+      return readPdfText(filePath);
+    case '.docx':
+      return docxToText(fs.readFileSync(filePath));
+    case '.md':
+      return markdownToText(fs.readFileSync(filePath, 'utf-8'));
+    case '.txt':
+      return fs.readFileSync(filePath, 'utf-8').replace(/^\uFEFF/, '');
+    default:
+      throw new Error(`Unsupported service order format "${extension || path.basename(filePath)}". Use ${SERVICE_ORDER_EXTENSIONS.join(', ')}`);
+  }
+}
+
+/**
+ * Read a service order as lines. With the positional layout PDFs are read by
+ * where their text sits on the page, keeping bold and heading cues; everything
+ * else is read as text.
+ */
+export async function readServiceOrderLines(
+  filePath: string,
+  options: { extension?: string; pdfLayout?: 'text' | 'positional' } = {}
+): Promise<ServiceOrderLine[]> {
+  const extension = options.extension || path.extname(filePath);
+  if (options.pdfLayout === 'positional' && extension.toLowerCase() === '.pdf') {
+    return readPdfLayout(filePath);
+  }
+  const text = await readServiceOrderText(filePath, extension);
+  return text.split('\n').map(line => ({ text: line }));
+}
+
+/**
+ * Extract the text of a PDF with pdf-parse
+ */
+export async function readPdfText(pdfPath: string): Promise<string> {
+  ensureDomMatrix();
+
+  // Lazy-load pdf-parse to avoid DOMMatrix error at app startup
+  const { PDFParse } = require('pdf-parse');
+
+  // Read and parse PDF - convert Buffer to Uint8Array as required by pdf-parse v2
+  const nodeBuffer = fs.readFileSync(pdfPath);
+  const uint8Array = new Uint8Array(nodeBuffer.buffer, nodeBuffer.byteOffset, nodeBuffer.byteLength);
+
+  // pdf-parse v2 requires options object with data property
+  const parser = new PDFParse({ data: uint8Array });
+  const data = await parser.getText();
+  await parser.destroy(); // Free memory
+  return data.text;
+}
+
+function decodeXmlText(text: string): string {
+  return text
+    .replace(/&lt;/g, '<')
+    .replace(/&gt;/g, '>')
+    .replace(/&quot;/g, '"')
+    .replace(/&apos;/g, "'")
+    .replace(/&#x([0-9a-f]+);/gi, (_, hex) => String.fromCodePoint(parseInt(hex, 16)))
+    .replace(/&#(\d+);/g, (_, dec) => String.fromCodePoint(parseInt(dec, 10)))
+    .replace(/&amp;/g, '&');
+}
+
+/**
+ * Text of a run of WordprocessingML: text, tabs and line breaks
+ */
+function wordRunsToText(xml: string): string {
+  let text = '';
+  const tokens = /<w:t(?:\s[^>]*)?>([\s\S]*?)<\/w:t>|<w:tab\/>|<w:(?:br|cr)(?:\s[^>]*)?\/>/g;
+  let match: RegExpExecArray | null;
+  while ((match = tokens.exec(xml)) !== null) {
+    if (match[1] !== undefined) {
+      text += decodeXmlText(match[1]);
+    } else {
+      text += match[0].startsWith('<w:tab') ? '\t' : '\n';
+    }
+  }
+  return text;
+}
+
+function wordParagraphs(xml: string): string[] {
+  return (xml.match(/<w:p[\s>][\s\S]*?<\/w:p>/g) || []).map(wordRunsToText);
+}
+
+/**
+ * Text of a Word document: one line per paragraph, one line per table row
+ */
+export function docxToText(buffer: Buffer): string {
+  let zip: ZipReader;
+  try {
+    zip = new ZipReader(buffer);
+  } catch {
+    throw new Error('Not a Word document (.docx). Older .doc files need saving as .docx first.');
+  }
+  const entry = zip.find(item => item.name === 'word/document.xml');
+  if (!entry) {
+    throw new Error('Not a Word document (.docx): word/document.xml is missing');
+  }
+  const xml = zip.read(entry).toString('utf-8');
+  const body = xml.match(/<w:body>([\s\S]*)<\/w:body>/)?.[1] ?? xml;
+
+  const lines: string[] = [];
+  // Tables and paragraphs in document order
+  const blocks = /<w:tbl[\s>][\s\S]*?<\/w:tbl>|<w:p[\s>][\s\S]*?<\/w:p>/g;
+  let block: RegExpExecArray | null;
+  while ((block = blocks.exec(body)) !== null) {
+    if (!block[0].startsWith('<w:tbl')) {
+      lines.push(wordRunsToText(block[0]));
+      continue;
+    }
+    for (const row of block[0].match(/<w:tr[\s>][\s\S]*?<\/w:tr>/g) || []) {
+      const cells = (row.match(/<w:tc[\s>][\s\S]*?<\/w:tc>/g) || [])
+        .map(cell => wordParagraphs(cell).join(' ').trim())
+        .filter(cell => cell.length > 0);
+      lines.push(cells.join('\t'));
+    }
+  }
+  return lines.join('\n');
+}
+
+/**
+ * Plain text of a Markdown service order: headings, list markers, emphasis,
+ * links and table pipes removed
+ */
+export function markdownToText(markdown: string): string {
+  return markdown
+    .replace(/^\uFEFF/, '')
+    .split(/\r?\n/)
+    .filter(line => !/^\s*\|?\s*:?-{3,}:?\s*(\|\s*:?-{3,}:?\s*)*\|?\s*$/.test(line)) // table separators
+    .filter(line => !/^\s*(```|~~~|([-*_])(\s*\2){2,}\s*$)/.test(line))         // fences, rules
+    .map(line => {
+      let text = line
+        .replace(/^\s{0,3}#{1,6}\s+/, '')          // headings
+        .replace(/^\s*>\s?/, '')                   // quotes
+        .replace(/^\s*(?:[-*+]|\d+[.)])\s+/, '')   // list markers
+        .replace(/!?\[([^\]]*)\]\([^)]*\)/g, '$1') // links and images
+        .replace(/(\*\*|__)(.+?)\1/g, '$2')         // bold
+        .replace(/(^|[^\w*])[*_]([^*_]+)[*_](?=[^\w*]|$)/g, '$1$2')  // italics
+        .replace(/`([^`]*)`/g, '$1');
+      if (/^\s*\|.*\|\s*$/.test(text)) {
+        text = text.trim().slice(1, -1).split('|').map(cell => cell.trim()).filter(Boolean).join('\t');
+      }
+      return text;
+    })
+    .join('\n');
+}
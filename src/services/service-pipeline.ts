@@ -0,0 +1,480 @@
+/**
+ * Service Pipeline
+ * The steps of the Service Generator after a service order is parsed: match
+ * its songs and videos to library presentations, match its Bible readings to
+ * verse presentations, and insert the chosen presentations under the matching
+ * headers of a playlist made from a template. Shared by the web routes, the
+ * desktop app and the headless `service build` command.
+ */
+
+import type { ProPresenterClient } from '../propresenter-client';
+import type { ParsedService } from '../types/service-order';
+import type { LibraryPresentation } from '../types/song-match';
+import { SongMatcher } from './song-matcher';
+import { loadAliases, aliasesToCustomMappings } from './alias-store';
+import { recordServiceUsage } from './usage-store';
+
+export interface ServiceLibraryIds {
+  /** Worship libraries; the kids and service content libraries are skipped if listed */
+  libraryIds: string[];
+  kidsLibraryId?: string | null;
+  serviceContentLibraryId?: string | null;
+}
+
+export interface ServiceLibraries {
+  worship: LibraryPresentation[];
+  kids: LibraryPresentation[];
+  serviceContent: LibraryPresentation[];
+}
+
+export interface SongItemToMatch {
+  text: string;
+  isKidsVideo?: boolean;
+  praiseSlot?: string;
+  specialServiceType?: string | null;
+}
+
+/** A presentation offered for a song or reading; confidence is a percentage */
+export interface MatchedPresentation {
+  uuid: string;
+  name: string;
+  library?: string;
+  confidence: number;
+}
+
+export interface SongMatchResult {
+  songName: string;
+  praiseSlot?: string;
+  isKidsVideo: boolean;
+  matches: MatchedPresentation[];
+  bestMatch?: MatchedPresentation;
+  requiresReview: boolean;
+  /** Set when the best match is confident enough to use without review */
+  selectedMatch?: { uuid: string; name: string };
+  /** UUID of the presentation the user picked by hand, if they overrode the match */
+  manualSelection?: string;
+}
+
+export interface VerseMatchResult {
+  reference: string;
+  matches: MatchedPresentation[];
+  bestMatch?: MatchedPresentation;
+  requiresReview: boolean;
+  selectedMatch?: { uuid: string; name: string };
+}
+
+/** A presentation to insert, under the header of its slot */
+export interface ServicePlaylistItem {
+  type: string;
+  uuid: string;
+  name: string;
+  /** praise1, praise2, praise3, kids or reading */
+  praiseSlot?: string;
+}
+
+/** Song confidence (0-1) below which a match is left for review */
+export const DEFAULT_SONG_THRESHOLD = 0.7;
+/** Verse confidence (percent) below which a match is left for review */
+export const DEFAULT_VERSE_THRESHOLD = 85;
+
+/**
+ * Template playlist headers (lowercase) and the slot whose items go under them
+ */
+export const SERVICE_HEADER_SLOTS: Record<string, string> = {
+  'praise 1': 'praise1', 'praise1': 'praise1',
+  'praise 2': 'praise2', 'praise2': 'praise2',
+  'praise 3': 'praise3', 'praise3': 'praise3',
+  'kids talk': 'kids', 'kids': 'kids', 'kids song': 'kids', 'kids video': 'kids',
+  'reading': 'reading', 'bible': 'reading',
+};
+
+// Bible verse presentations have the translation in their name
+const BIBLE_TRANSLATION = /\b(niv|esv|nlt|kjv|nkjv|nasb|csb|msg)\b/i;
+
+async function loadLibrary(
+  client: ProPresenterClient,
+  libraryId: string,
+  libraryName: string
+): Promise<LibraryPresentation[]> {
+  try {
+    const presentations = await client.getLibraryPresentations(libraryId);
+    return presentations.map(pres => ({ uuid: pres.uuid, name: pres.name, library: libraryName, libraryId }));
+  } catch {
+    // Unreadable libraries are skipped
+    return [];
+  }
+}
+
+/**
+ * Fetch the presentations of the worship, kids and service content libraries
+ */
+export async function loadServiceLibraries(client: ProPresenterClient, ids: ServiceLibraryIds): Promise<ServiceLibraries> {
+  const libraries = await client.getLibraries();
+  const nameOf = (id: string, fallback: string) => libraries.find(l => l.uuid === id)?.name || fallback;
+
+  const worship: LibraryPresentation[] = [];
+  for (const libraryId of ids.libraryIds) {
+    if (!libraryId || libraryId === ids.kidsLibraryId || libraryId === ids.serviceContentLibraryId) continue;
+    worship.push(...await loadLibrary(client, libraryId, nameOf(libraryId, 'Unknown')));
+  }
+  const serviceContent = ids.serviceContentLibraryId
+    ? await loadLibrary(client, ids.serviceContentLibraryId, nameOf(ids.serviceContentLibraryId, 'Service Content'))
+    : [];
+  const kids = ids.kidsLibraryId
+    ? await loadLibrary(client, ids.kidsLibraryId, nameOf(ids.kidsLibraryId, 'Kids'))
+    : [];
+
+  return { worship, kids, serviceContent };
+}
+
+function toMatchedPresentation(candidate: { presentation: LibraryPresentation; confidence: number }): MatchedPresentation {
+  return {
+    uuid: candidate.presentation.uuid,
+    name: candidate.presentation.name,
+    library: candidate.presentation.library,
+    confidence: Math.round(candidate.confidence * 100),
+  };
+}
+
+/**
+ * Fuzzy match songs and videos against the libraries. Kids items are matched
+ * in the kids library, falling back to every library; other videos in the
+ * service content library; songs in the worship libraries. Saved aliases win.
+ */
+export async function matchServiceSongs(
+  songItems: SongItemToMatch[],
+  libraries: ServiceLibraries,
+  threshold = DEFAULT_SONG_THRESHOLD
+): Promise<SongMatchResult[]> {
+  const matcher = new SongMatcher(threshold, aliasesToCustomMappings(loadAliases()));
+  const results: SongMatchResult[] = [];
+
+  for (let i = 0; i < songItems.length; i++) {
+    const item = songItems[i];
+    const isKids = Boolean(item.isKidsVideo || item.praiseSlot === 'kids');
+    const isVideo = item.praiseSlot === 'kids_video' || Boolean(item.text && item.text.includes('(Video)'));
+    const isNonKidsVideo = isVideo && !isKids;
+
+    const songText = typeof item.text === 'string' ? item.text : String(item.text || '');
+    if (!songText) continue;
+
+    const presentationsToMatch = isKids ? libraries.kids
+      : isNonKidsVideo ? libraries.serviceContent
+      : libraries.worship;
+
+    const songSection = { type: 'song' as const, title: songText, position: i };
+    let [match] = await matcher.matchSongs([songSection], presentationsToMatch);
+
+    // Cross-library fallback for kids
+    if (isKids && (!match.bestMatch || match.bestMatch.confidence < threshold)) {
+      const allPresentations = [...libraries.worship, ...libraries.serviceContent, ...libraries.kids];
+      const [fallbackMatch] = await matcher.matchSongs([songSection], allPresentations);
+      if (fallbackMatch.bestMatch && fallbackMatch.bestMatch.confidence > (match.bestMatch?.confidence || 0)) {
+        match = fallbackMatch;
+      }
+    }
+
+    results.push({
+      songName: match.pdfTitle,
+      praiseSlot: item.praiseSlot,
+      isKidsVideo: isKids,
+      matches: match.matches.map(toMatchedPresentation),
+      bestMatch: match.bestMatch ? toMatchedPresentation(match.bestMatch) : undefined,
+      requiresReview: match.requiresReview,
+      selectedMatch: match.bestMatch && !match.requiresReview ? {
+        uuid: match.bestMatch.presentation.uuid,
+        name: match.bestMatch.presentation.name,
+      } : undefined,
+    });
+  }
+
+  return results;
+}
+
+/**
+ * Match Bible references (e.g. "Luke 12:35-59") to verse presentations by name,
+ * e.g. "Luke 12_35-59 (NIV)-1"
+ */
+export function matchVerseReferences(
+  references: string[],
+  presentations: Array<{ uuid: string; name: string }>,
+  threshold = DEFAULT_VERSE_THRESHOLD
+): VerseMatchResult[] {
+  const biblePresentations = presentations.filter(pres => BIBLE_TRANSLATION.test(pres.name));
+  const searchPresentations = biblePresentations.length > 0 ? biblePresentations : presentations;
+
+  return references.map(reference => {
+    const normalizedRef = reference.toLowerCase().trim();
+    const normalizedRefStripped = normalizedRef.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
+
+    const refParts = normalizedRef.match(/^(\d?\s*[a-z]+)\s+(\d+)/);
+    const refBook = refParts ? refParts[1].replace(/\s+/g, ' ').trim() : '';
+    const refChapter = refParts ? refParts[2] : '';
+
+    const matches = searchPresentations
+      .filter(pres => {
+        const presName = pres.name.toLowerCase();
+        const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
+        return presName.includes(normalizedRef) ||
+               presNameStripped.includes(normalizedRefStripped) ||
+               normalizedRef.includes(presName) ||
+               normalizedRefStripped.includes(presNameStripped) ||
+               (refBook && refChapter && presName.includes(refBook) && presName.includes(refChapter));
+      })
+      .map(pres => {
+        const presName = pres.name.toLowerCase();
+        const presNameStripped = presName.replace(/[:\-_()]/g, ' ').replace(/\s+/g, ' ').trim();
+        let confidence = 0;
+        if (presName === normalizedRef || presNameStripped === normalizedRefStripped) {
+          confidence = 100;
+        } else if (presName.includes(normalizedRef) || normalizedRef.includes(presName) ||
+                   presNameStripped.includes(normalizedRefStripped) || normalizedRefStripped.includes(presNameStripped)) {
+          confidence = 85;
+        } else {
+          confidence = 60;
+        }
+        return { uuid: pres.uuid, name: pres.name, confidence };
+      })
+      .sort((a, b) => b.confidence - a.confidence)
+      .slice(0, 5);
+
+    const bestMatch = matches[0];
+    const requiresReview = !bestMatch || bestMatch.confidence < threshold;
+
+    return {
+      reference,
+      matches,
+      bestMatch,
+      requiresReview,
+      selectedMatch: bestMatch && !requiresReview ? { uuid: bestMatch.uuid, name: bestMatch.name } : undefined,
+    };
+  });
+}
+
+/**
+ * Songs, videos and readings of a parsed service order in the shape the
+ * matchers take
+ */
+export function serviceOrderItems(parsed: ParsedService): { songs: SongItemToMatch[]; readings: string[] } {
+  return {
+    songs: parsed.sections
+      .filter(section => section.type === 'song' || section.type === 'video')
+      .map(section => ({
+        text: section.title,
+        isKidsVideo: section.isKidsVideo === true,
+        praiseSlot: section.praiseSlot,
+        specialServiceType: parsed.specialServiceType ?? null,
+      })),
+    readings: parsed.sections.filter(section => section.type === 'bible').map(section => section.title),
+  };
+}
+
+/**
+ * Presentations to insert for the matches that were selected: songs under
+ * their praise slot, readings under "Reading"
+ */
+export function selectedServiceItems(songs: SongMatchResult[], verses: VerseMatchResult[]): ServicePlaylistItem[] {
+  return [
+    ...songs.filter(song => song.selectedMatch).map(song => ({
+      type: 'presentation',
+      uuid: song.selectedMatch!.uuid,
+      name: song.selectedMatch!.name,
+      praiseSlot: song.praiseSlot,
+    })),
+    ...verses.filter(verse => verse.selectedMatch).map(verse => ({
+      type: 'presentation',
+      uuid: verse.selectedMatch!.uuid,
+      name: verse.selectedMatch!.name,
+      praiseSlot: 'reading',
+    })),
+  ];
+}
+
+/**
+ * Record the songs of a built service playlist for CCLI usage reports.
+ * Readings and kids items aren't sung by the congregation, so they are left
+ * out. Never throws.
+ */
+export async function recordServicePlaylistUsage(
+  client: ProPresenterClient | null,
+  playlist: { name: string; id?: string },
+  items: ServicePlaylistItem[]
+): Promise<void> {
+  const sung = items
+    .filter(item => item.praiseSlot !== 'reading' && item.praiseSlot !== 'kids')
+    .map(item => ({ uuid: item.uuid, name: item.name }));
+  await recordServiceUsage(client, playlist, sung);
+}
+
+/**
+ * The playlist items with the service's presentations under the headers of
+ * their slots. A slot's new items replace what the template had under its
+ * header; slots with nothing to insert keep the template's items. Returns
+ * items cleaned for ProPresenter's PUT /v1/playlist.
+ */
+export function insertServiceItems(currentItems: any[], items: ServicePlaylistItem[]): any[] {
+  const itemsBySlot: Record<string, ServicePlaylistItem[]> = {
+    praise1: [], praise2: [], praise3: [], kids: [], reading: [],
+  };
+  for (const item of items) {
+    const slot = item.praiseSlot || 'praise1';
+    if (itemsBySlot[slot]) {
+      itemsBySlot[slot].push(item);
+    }
+  }
+
+  const newItems: any[] = [];
+  let skipUntilNextHeader = false;
+
+  for (const item of currentItems) {
+    const isHeader = item.type === 'header';
+    const itemName = (item.id?.name || item.name || '').toLowerCase().trim();
+
+    if (isHeader) {
+      const matchedSlot = SERVICE_HEADER_SLOTS[itemName];
+      if (matchedSlot) {
+        newItems.push(item);
+        const slotItems = itemsBySlot[matchedSlot] || [];
+        for (const songItem of slotItems) {
+          newItems.push({
+            id: { name: songItem.name, uuid: songItem.uuid, index: newItems.length },
+            type: 'presentation',
+            is_hidden: false,
+            is_pco: false,
+            target_uuid: songItem.uuid,
+            presentation_info: {
+              presentation_uuid: songItem.uuid,
+              arrangement_name: '',
+              arrangement_uuid: '',
+            },
+            destination: 'presentation',
+          });
+        }
+        skipUntilNextHeader = slotItems.length > 0;
+      } else {
+        skipUntilNextHeader = false;
+        newItems.push(item);
+      }
+    } else if (!skipUntilNextHeader) {
+      newItems.push(item);
+    }
+  }
+
+  return newItems.map((item: any, index: number) => {
+    let itemUuid = item.id?.uuid || '';
+    if (item.type === 'presentation' && !itemUuid && item.presentation_info?.presentation_uuid) {
+      itemUuid = item.presentation_info.presentation_uuid;
+    }
+
+    const cleaned: any = {
+      id: { name: item.id?.name || item.name || 'Untitled', index, uuid: itemUuid },
+      type: item.type,
+      is_hidden: item.is_hidden || false,
+      is_pco: item.is_pco || false,
+    };
+
+    // Include target_uuid (required by ProPresenter for deserialization)
+    cleaned.target_uuid = item.target_uuid || '';
+
+    if (item.type === 'header' && item.header_color) {
+      cleaned.header_color = item.header_color;
+    }
+    if (item.type === 'presentation') {
+      if (item.presentation_info) {
+        cleaned.presentation_info = {
+          presentation_uuid: item.presentation_info.presentation_uuid,
+          arrangement_name: item.presentation_info.arrangement_name || '',
+          arrangement_uuid: item.presentation_info.arrangement_uuid || '',
+        };
+      }
+      if (item.duration) cleaned.duration = item.duration;
+    }
+    if (item.destination) cleaned.destination = item.destination;
+
+    return cleaned;
+  });
+}
+
+/**
+ * Fetch a playlist's raw items and name from ProPresenter
+ */
+export async function fetchPlaylistItems(host: string, port: number, playlistId: string): Promise<{ name: string; items: any[] }> {
+  const response = await fetch(`http://${host}:${port}/v1/playlist/${playlistId}`);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch playlist: ${response.status}`);
+  }
+  const playlistData = await response.json() as any;
+  return { name: playlistData.id?.name || playlistId, items: playlistData.items || [] };
+}
+
+/**
+ * Insert the service's presentations into a playlist in ProPresenter
+ */
+export async function buildServicePlaylist(
+  host: string,
+  port: number,
+  playlistId: string,
+  items: ServicePlaylistItem[]
+): Promise<{ name: string; itemCount: number }> {
+  const playlist = await fetchPlaylistItems(host, port, playlistId);
+  const cleanedItems = insertServiceItems(playlist.items, items);
+
+  const putResponse = await fetch(`http://${host}:${port}/v1/playlist/${playlistId}`, {
+    method: 'PUT',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(cleanedItems),
+  });
+  if (!putResponse.ok) {
+    const errorText = await putResponse.text();
+    throw new Error(`Failed to update playlist: ${putResponse.status} ${errorText}`);
+  }
+
+  return { name: playlist.name, itemCount: cleanedItems.length };
+}
+
+/**
+ * Songs and readings left out of the playlist because no match was confident
+ * enough, as a Markdown checklist listing the candidates of each
+ */
+export function formatServiceReview(
+  songs: SongMatchResult[],
+  verses: VerseMatchResult[],
+  context: { source: string; date?: string; playlist?: string; threshold: number }
+): string {
+  const candidates = (matches: MatchedPresentation[]) => matches.length === 0
+    ? ['  - No candidates found']
+    : matches.map(match => `  - ${match.name} (${match.confidence}%)${match.library ? ` - ${match.library}` : ''}`);
+
+  const lines = [
+    `# Service review: ${context.source}`,
+    '',
+    ...(context.date ? [`Service date: ${context.date}`] : []),
+    ...(context.playlist ? [`Playlist: ${context.playlist}`] : []),
+    `Confidence threshold: ${Math.round(context.threshold * 100)}%`,
+    '',
+  ];
+
+  const songsToReview = songs.filter(song => !song.selectedMatch);
+  const versesToReview = verses.filter(verse => !verse.selectedMatch);
+  if (songsToReview.length === 0 && versesToReview.length === 0) {
+    return [...lines, 'Everything was matched.', ''].join('\n');
+  }
+
+  if (songsToReview.length > 0) {
+    lines.push('## Songs and videos', '');
+    for (const song of songsToReview) {
+      const slot = song.isKidsVideo ? 'kids' : song.praiseSlot || 'praise1';
+      lines.push(`- [ ] **${song.songName}** (${slot})`, ...candidates(song.matches));
+    }
+    lines.push('');
+  }
+  if (versesToReview.length > 0) {
+    lines.push('## Bible readings', '');
+    for (const verse of versesToReview) {
+      lines.push(`- [ ] **${verse.reference}**`, ...candidates(verse.matches));
+    }
+    lines.push('');
+  }
+  lines.push('Add these to the playlist by hand. Songs can be saved as aliases (`alias add "Title"`) so later services match them.', '');
+  return lines.join('\n');
+}
@@ -0,0 +1,207 @@
+/**
+ * Service Session Store
+ * Persistent storage for Service Generator runs in progress, so a build can be
+ * resumed after the app or browser tab is closed part way through.
+ * Stores sessions in ~/.propresenter-words/service-sessions.json
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import { randomUUID } from 'crypto';
+import type { ParsedService } from '../types/service-order';
+import type { SongMatchResult, VerseMatchResult } from './service-pipeline';
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const SERVICE_SESSIONS_FILE = path.join(CONFIG_DIR, 'service-sessions.json');
+
+/** The Service Generator step a session was left on */
+export type ServiceSessionStep = 'upload' | 'parse' | 'match' | 'verse' | 'build';
+
+/** A service order item as shown on the Parse step */
+export interface ServiceSessionItem {
+  type: 'song' | 'kids_video' | 'verse' | 'heading';
+  text: string;
+  reference?: string;
+  isKidsVideo?: boolean;
+  praiseSlot?: string;
+}
+
+export interface ServiceSessionVerse {
+  reference: string;
+  text: string;
+  error?: string;
+}
+
+export interface ServiceSessionBuild {
+  status: 'pending' | 'built' | 'failed';
+  itemCount?: number;
+  builtAt?: string;       // ISO timestamp of the last successful build
+  error?: string;
+}
+
+export interface ServiceSession {
+  id: string;             // Unique identifier (UUID)
+  name: string;           // Working playlist name, e.g. "St Andrews - Feb 10th"
+  playlistId: string;     // Working playlist the songs are added to
+  sourceFile?: string;    // Service order file the session was started from
+  grammarId?: string;     // Service grammar used to parse it
+  step: ServiceSessionStep;
+  parsed?: ParsedService;
+  items: ServiceSessionItem[];
+  songMatches: SongMatchResult[];   // Including any manual selections
+  verseMatches: VerseMatchResult[];
+  verses: ServiceSessionVerse[];
+  versesSkipped?: boolean;
+  build: ServiceSessionBuild;
+  createdAt: string;      // ISO timestamp
+  updatedAt: string;      // ISO timestamp
+}
+
+export type ServiceSessionInput = Partial<Omit<ServiceSession, 'id' | 'createdAt' | 'updatedAt'>> &
+  Pick<ServiceSession, 'name' | 'playlistId'> & { id?: string };
+
+const SESSION_STEPS: ServiceSessionStep[] = ['upload', 'parse', 'match', 'verse', 'build'];
+const BUILD_STATUSES: ServiceSessionBuild['status'][] = ['pending', 'built', 'failed'];
+const SESSION_LISTS = ['items', 'songMatches', 'verseMatches', 'verses'] as const;
+// Fields a client may set; the id and timestamps belong to the store
+const SESSION_FIELDS: Array<keyof Omit<ServiceSession, 'id' | 'createdAt' | 'updatedAt'>> = [
+  'name', 'playlistId', 'sourceFile', 'grammarId', 'step', 'parsed',
+  'items', 'songMatches', 'verseMatches', 'verses', 'versesSkipped', 'build',
+];
+
+/**
+ * Check a session sent by a client before it is saved.
+ * Returns a list of problems (empty if the session is valid).
+ */
+export function validateServiceSession(input: unknown): string[] {
+  if (!input || typeof input !== 'object' || Array.isArray(input)) {
+    return ['session must be an object'];
+  }
+  const session = input as Record<string, any>;
+  const errors: string[] = [];
+
+  if (session.id !== undefined && typeof session.id !== 'string') {
+    errors.push('id must be a string');
+  }
+  if (typeof session.name !== 'string' || !session.name.trim()) {
+    errors.push('name is required');
+  }
+  if (typeof session.playlistId !== 'string' || !session.playlistId) {
+    errors.push('playlistId is required');
+  }
+  if (session.step !== undefined && !SESSION_STEPS.includes(session.step)) {
+    errors.push(`step must be one of ${SESSION_STEPS.join(', ')}`);
+  }
+  for (const key of SESSION_LISTS) {
+    if (session[key] !== undefined && !Array.isArray(session[key])) {
+      errors.push(`${key} must be a list`);
+    }
+  }
+  if (session.build !== undefined) {
+    if (!session.build || typeof session.build !== 'object' || !BUILD_STATUSES.includes(session.build.status)) {
+      errors.push(`build.status must be one of ${BUILD_STATUSES.join(', ')}`);
+    }
+  }
+  return errors;
+}
+
+function ensureConfigDir(): void {
+  if (!fs.existsSync(CONFIG_DIR)) {
+    fs.mkdirSync(CONFIG_DIR, { recursive: true });
+  }
+}
+
+/**
+ * Load all service sessions from disk, most recently updated first
+ */
+export function loadServiceSessions(): ServiceSession[] {
+  try {
+    if (!fs.existsSync(SERVICE_SESSIONS_FILE)) {
+      return [];
+    }
+    const data = fs.readFileSync(SERVICE_SESSIONS_FILE, 'utf-8');
+    const sessions = JSON.parse(data) as ServiceSession[];
+    return sessions.sort((a, b) => b.updatedAt.localeCompare(a.updatedAt));
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Save all service sessions to disk
+ */
+function saveAllServiceSessions(sessions: ServiceSession[]): void {
+  ensureConfigDir();
+  fs.writeFileSync(SERVICE_SESSIONS_FILE, JSON.stringify(sessions, null, 2), 'utf-8');
+}
+
+/**
+ * Get a single service session by ID
+ */
+export function getServiceSession(id: string): ServiceSession | undefined {
+  const sessions = loadServiceSessions();
+  return sessions.find(s => s.id === id);
+}
+
+/**
+ * Save (create or update) a service session. Updates merge into the stored
+ * session, so a step only needs to send what it changed. An id that is not
+ * stored (e.g. the session was discarded) is rejected rather than recreated.
+ */
+export function saveServiceSession(session: ServiceSessionInput): ServiceSession {
+  const sessions = loadServiceSessions();
+  const now = new Date().toISOString();
+
+  if (session.id) {
+    // Update existing
+    const index = sessions.findIndex(s => s.id === session.id);
+    if (index >= 0) {
+      const updated: ServiceSession = { ...sessions[index], updatedAt: now };
+      for (const field of SESSION_FIELDS) {
+        if (session[field] !== undefined) {
+          Object.assign(updated, { [field]: session[field] });
+        }
+      }
+      sessions[index] = updated;
+      saveAllServiceSessions(sessions);
+      return updated;
+    }
+    throw Object.assign(new Error(`Session not found: ${session.id}`), { status: 404 });
+  }
+
+  // Create new
+  const newSession: ServiceSession = {
+    id: randomUUID(),
+    name: session.name,
+    playlistId: session.playlistId,
+    sourceFile: session.sourceFile,
+    grammarId: session.grammarId,
+    step: session.step || 'upload',
+    parsed: session.parsed,
+    items: session.items || [],
+    songMatches: session.songMatches || [],
+    verseMatches: session.verseMatches || [],
+    verses: session.verses || [],
+    versesSkipped: session.versesSkipped,
+    build: session.build || { status: 'pending' },
+    createdAt: now,
+    updatedAt: now,
+  };
+  sessions.push(newSession);
+  saveAllServiceSessions(sessions);
+  return newSession;
+}
+
+/**
+ * Delete (discard) a service session by ID
+ */
+export function deleteServiceSession(id: string): boolean {
+  const sessions = loadServiceSessions();
+  const filtered = sessions.filter(s => s.id !== id);
+  if (filtered.length < sessions.length) {
+    saveAllServiceSessions(filtered);
+    return true;
+  }
+  return false;
+}
@@ -0,0 +1,273 @@
+/**
+ * Snapshot Store
+ * Keeps a local history of every song's lyrics so accidental edits in
+ * ProPresenter can be spotted and undone.
+ *
+ * Lyrics are stored content-addressed in
+ * ~/.propresenter-words/snapshots/objects/<sha256>.json, so a song that
+ * hasn't changed since the last snapshot costs nothing. index.json lists
+ * each song's versions and every snapshot run.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import { createHash } from 'crypto';
+import type { ProPresenterClient } from '../propresenter-client';
+import { extractLyrics, ExtractedLyrics } from '../lyrics-extractor';
+import { crawlLibraries } from './library-crawler';
+
+export interface SnapshotVersion {
+  hash: string;
+  takenAt: string;
+  title: string;
+  lyricSlideCount: number;
+}
+
+export interface SnapshotSong {
+  uuid: string;
+  title: string;
+  library: string;
+  /** Oldest first */
+  versions: SnapshotVersion[];
+}
+
+export interface SnapshotRun {
+  takenAt: string;
+  libraries: string[];
+  songs: number;
+  added: number;
+  changed: number;
+  failed: number;
+}
+
+interface SnapshotIndex {
+  songs: Record<string, SnapshotSong>;
+  runs: SnapshotRun[];
+}
+
+export type SnapshotOutcome = 'added' | 'changed' | 'unchanged';
+
+export type SnapshotProgressEvent =
+  | { type: 'library'; name: string; presentations: number }
+  | { type: 'song'; title: string; outcome: SnapshotOutcome }
+  | { type: 'error'; title: string; message: string };
+
+export interface SnapshotOptions {
+  /** Library UUIDs or names to include; all libraries when empty */
+  libraries?: string[];
+  onProgress?: (event: SnapshotProgressEvent) => void;
+}
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const SNAPSHOT_DIR = path.join(CONFIG_DIR, 'snapshots');
+const OBJECTS_DIR = path.join(SNAPSHOT_DIR, 'objects');
+const INDEX_FILE = path.join(SNAPSHOT_DIR, 'index.json');
+
+function ensureSnapshotDirs(): void {
+  if (!fs.existsSync(OBJECTS_DIR)) {
+    fs.mkdirSync(OBJECTS_DIR, { recursive: true });
+  }
+}
+
+function loadIndex(): SnapshotIndex {
+  try {
+    if (!fs.existsSync(INDEX_FILE)) {
+      return { songs: {}, runs: [] };
+    }
+    const parsed = JSON.parse(fs.readFileSync(INDEX_FILE, 'utf-8'));
+    return { songs: parsed.songs || {}, runs: parsed.runs || [] };
+  } catch {
+    return { songs: {}, runs: [] };
+  }
+}
+
+function saveIndex(index: SnapshotIndex): void {
+  ensureSnapshotDirs();
+  fs.writeFileSync(INDEX_FILE, JSON.stringify(index, null, 2), 'utf-8');
+}
+
+// Shortest hash prefix accepted as a version, so it can't be mistaken for anything else
+const MIN_HASH_PREFIX = 7;
+
+/**
+ * Content hash of a song's lyrics: section names and the text of lyric
+ * slides, with line endings and trailing spaces normalised. Labels, notes,
+ * formatting and metadata don't count as a change.
+ */
+export function hashLyrics(lyrics: ExtractedLyrics): string {
+  const normalize = (text: string) => text.replace(/\r\n?/g, '\n').split('\n').map(line => line.trimEnd()).join('\n').trim();
+  const content = lyrics.sections.map(section => ({
+    name: section.name.trim(),
+    slides: section.slides.filter(slide => slide.isLyric && slide.text.trim()).map(slide => normalize(slide.text)),
+  }));
+  return createHash('sha256').update(JSON.stringify(content)).digest('hex');
+}
+
+function readObject(hash: string): ExtractedLyrics | null {
+  const filePath = path.join(OBJECTS_DIR, `${hash}.json`);
+  return fs.existsSync(filePath) ? JSON.parse(fs.readFileSync(filePath, 'utf-8')) as ExtractedLyrics : null;
+}
+
+function writeObject(hash: string, lyrics: ExtractedLyrics): void {
+  ensureSnapshotDirs();
+  const filePath = path.join(OBJECTS_DIR, `${hash}.json`);
+  if (!fs.existsSync(filePath)) {
+    fs.writeFileSync(filePath, JSON.stringify(lyrics, null, 2), 'utf-8');
+  }
+}
+
+function recordInIndex(
+  index: SnapshotIndex,
+  lyrics: ExtractedLyrics,
+  library: string,
+  takenAt: string
+): SnapshotOutcome {
+  const hash = hashLyrics(lyrics);
+  const existing = index.songs[lyrics.uuid];
+  const latest = existing?.versions[existing.versions.length - 1];
+  if (latest?.hash === hash) {
+    existing.title = lyrics.title;
+    existing.library = library;
+    return 'unchanged';
+  }
+
+  writeObject(hash, lyrics);
+  const version: SnapshotVersion = { hash, takenAt, title: lyrics.title, lyricSlideCount: lyrics.lyricSlideCount };
+  if (existing) {
+    existing.title = lyrics.title;
+    existing.library = library;
+    existing.versions.push(version);
+    return 'changed';
+  }
+  index.songs[lyrics.uuid] = { uuid: lyrics.uuid, title: lyrics.title, library, versions: [version] };
+  return 'added';
+}
+
+/**
+ * Store one song's lyrics. Returns 'unchanged' when they match the latest version.
+ */
+export function recordSnapshot(lyrics: ExtractedLyrics, library: string): SnapshotOutcome {
+  const index = loadIndex();
+  const outcome = recordInIndex(index, lyrics, library, new Date().toISOString());
+  if (outcome !== 'unchanged') {
+    saveIndex(index);
+  }
+  return outcome;
+}
+
+/**
+ * Walk the libraries and store every song whose lyrics changed since the last run
+ */
+export async function takeLibrarySnapshot(
+  client: ProPresenterClient,
+  options: SnapshotOptions = {}
+): Promise<SnapshotRun> {
+  const takenAt = new Date().toISOString();
+  const index = loadIndex();
+  const run: SnapshotRun = { takenAt, libraries: [], songs: 0, added: 0, changed: 0, failed: 0 };
+
+  const libraries = await crawlLibraries(client, {
+    libraries: options.libraries,
+    onLibrary: (library, presentations) => {
+      options.onProgress?.({ type: 'library', name: library.name, presentations });
+    },
+    onError: (title, error) => {
+      run.failed++;
+      options.onProgress?.({ type: 'error', title, message: error.message });
+    },
+  }, ({ library, name, presentation }) => {
+    const outcome = recordInIndex(index, extractLyrics(presentation), library.name, takenAt);
+    run.songs++;
+    if (outcome === 'added') run.added++;
+    if (outcome === 'changed') run.changed++;
+    options.onProgress?.({ type: 'song', title: name, outcome });
+  });
+
+  run.libraries = libraries.map(library => library.name);
+  index.runs.push(run);
+  saveIndex(index);
+  return run;
+}
+
+/**
+ * All songs in the store, by title
+ */
+export function listSnapshotSongs(): SnapshotSong[] {
+  return Object.values(loadIndex().songs).sort((a, b) => a.title.localeCompare(b.title));
+}
+
+export function listSnapshotRuns(): SnapshotRun[] {
+  return loadIndex().runs;
+}
+
+/**
+ * Find a song by presentation UUID or title (exact, then partial match)
+ */
+export function findSnapshotSong(query: string): SnapshotSong | null {
+  const songs = listSnapshotSongs();
+  const wanted = query.trim().toLowerCase();
+  const byUuid = songs.find(song => song.uuid.toLowerCase() === wanted);
+  if (byUuid) return byUuid;
+  const exact = songs.filter(song => song.title.toLowerCase() === wanted);
+  if (exact.length === 1) return exact[0];
+  const partial = songs.filter(song => song.title.toLowerCase().includes(wanted));
+  if (partial.length > 1 && exact.length === 0) {
+    const titles = partial.slice(0, 5).map(song => `"${song.title}"`).join(', ');
+    throw Object.assign(new Error(`"${query}" matches several songs: ${titles}. Use the presentation UUID.`), { status: 400 });
+  }
+  return exact[0] ?? partial[0] ?? null;
+}
+
+/**
+ * Lyrics of one stored version. The version is "v" and its 1-based number
+ * (v3), a negative offset from the latest (-1 is the one before), "latest",
+ * "previous" or at least the first 7 characters of its hash; the latest
+ * version when omitted. Errors carry an HTTP status: 404 for a song or
+ * version that isn't stored, 400 for a query that can't be used.
+ */
+export function loadSnapshotVersion(
+  query: string,
+  version?: string
+): { song: SnapshotSong; version: SnapshotVersion; number: number; lyrics: ExtractedLyrics } {
+  const song = findSnapshotSong(query);
+  if (!song) {
+    throw Object.assign(new Error(`No snapshots of "${query}". Run "snapshot" first.`), { status: 404 });
+  }
+
+  const count = song.versions.length;
+  const wanted = version?.trim().toLowerCase();
+  let position = count - 1;
+  if (wanted === 'previous') {
+    position = count - 2;
+  } else if (wanted && wanted !== 'latest') {
+    const numbered = wanted.match(/^v(\d+)$/);
+    if (numbered) {
+      position = parseInt(numbered[1], 10) - 1;
+    } else if (/^-\d+$/.test(wanted)) {
+      position = count - 1 + parseInt(wanted, 10);
+    } else if (/^[0-9a-f]+$/.test(wanted) && wanted.length >= MIN_HASH_PREFIX) {
+      position = song.versions.findIndex(entry => entry.hash.startsWith(wanted));
+    } else {
+      throw Object.assign(
+        new Error(`"${version}" is not a version. Use v<number> (e.g. v2), -1, latest, previous or a hash of at least ${MIN_HASH_PREFIX} characters.`),
+        { status: 400 }
+      );
+    }
+  }
+  const entry = song.versions[position];
+  if (!entry) {
+    throw Object.assign(new Error(`"${song.title}" has no version ${version} (versions v1-v${count})`), { status: 404 });
+  }
+
+  const lyrics = readObject(entry.hash);
+  if (!lyrics) {
+    throw new Error(`Snapshot data missing: ${path.join(OBJECTS_DIR, `${entry.hash}.json`)}`);
+  }
+  // Songs with the same lyrics share an object, so the song's own identity wins
+  return { song, version: entry, number: position + 1, lyrics: { ...lyrics, uuid: song.uuid, title: entry.title } };
+}
+
+export function getSnapshotDir(): string {
+  return SNAPSHOT_DIR;
+}
@@ -7,6 +7,35 @@ import Fuse from 'fuse.js';
 import { SongMatch, MatchCandidate, LibraryPresentation, MatchStatistics } from '../types/song-match';
 import { ServiceSection } from '../types/service-order';
 
+/**
+ * Fuse.js settings for fuzzy title matching against presentations' names
+ */
+export const TITLE_FUSE_OPTIONS = {
+  keys: ['name'],
+  threshold: 0.6,  // More lenient: 0 = exact, 1 = match anything
+  includeScore: true,
+  ignoreLocation: true,
+  distance: 150,
+  minMatchCharLength: 2,
+  useExtendedSearch: true
+};
+
+/**
+ * Normalize song title for comparison
+ */
+export function normalizeSongTitle(title: string): string {
+  // Defensive check - handle non-string values
+  if (typeof title !== 'string') {
+    console.error('[SongMatcher] normalizeSongTitle received non-string:', typeof title, title);
+    return String(title || '').toLowerCase().replace(/[^\w\s]/g, '').replace(/\s+/g, ' ').trim();
+  }
+  return title
+    .toLowerCase()
+    .replace(/[^\w\s]/g, '')  // Remove punctuation
+    .replace(/\s+/g, ' ')      // Normalize whitespace
+    .trim();
+}
+
 export class SongMatcher {
   private confidenceThreshold: number;
   private customMappings: Record<string, string>;
@@ -78,15 +107,7 @@ export class SongMatcher {
     const normalizedTitle = this.normalizeSongTitle(songTitle);
 
     // Configure Fuse.js with more lenient settings
-    const fuse = new Fuse(presentations, {
-      keys: ['name'],
-      threshold: 0.6,  // More lenient: 0 = exact, 1 = match anything
-      includeScore: true,
-      ignoreLocation: true,
-      distance: 150,
-      minMatchCharLength: 2,
-      useExtendedSearch: true
-    });
+    const fuse = new Fuse(presentations, TITLE_FUSE_OPTIONS);
 
     // Search
     const results = fuse.search(normalizedTitle);
@@ -195,16 +216,7 @@ export class SongMatcher {
    * Normalize song title for comparison
    */
   private normalizeSongTitle(title: string): string {
-    // Defensive check - handle non-string values
-    if (typeof title !== 'string') {
-      console.error('[SongMatcher] normalizeSongTitle received non-string:', typeof title, title);
-      return String(title || '').toLowerCase().replace(/[^\w\s]/g, '').replace(/\s+/g, ' ').trim();
-    }
-    return title
-      .toLowerCase()
-      .replace(/[^\w\s]/g, '')  // Remove punctuation
-      .replace(/\s+/g, ' ')      // Normalize whitespace
-      .trim();
+    return normalizeSongTitle(title);
   }
 
   /**
@@ -0,0 +1,167 @@
+/**
+ * Text Fit - Estimate whether lyrics fit a slide text box
+ *
+ * There are no font metrics at export time, so widths come from average
+ * character proportions of typical sans-serif fonts. That is close enough to
+ * decide when a slide needs a smaller font or has to be split in two.
+ */
+
+export interface TextBox {
+  /** Inches */
+  w: number;
+  h: number;
+}
+
+export interface TextFitOptions {
+  fontSize: number;
+  minFontSize: number;
+  maxFontSize: number;
+  bold?: boolean;
+}
+
+export type TextFitAction = 'fit' | 'shrink' | 'split';
+
+export interface TextFitChunk {
+  text: string;
+  fontSize: number;
+  /** False when even the minimum size overflows (a single very long line) */
+  fits: boolean;
+}
+
+export interface TextFitResult {
+  action: TextFitAction;
+  chunks: TextFitChunk[];
+}
+
+const POINTS_PER_INCH = 72;
+// PowerPoint's default text box inset, per side
+const INSET_PT = 0.1 * POINTS_PER_INCH;
+const LINE_HEIGHT = 1.2;
+
+const NARROW = new Set([...'il.,;:\'!|Ijtf()[] ']);
+const WIDE = new Set([...'MWmw@']);
+
+function charWidth(char: string): number {
+  if (NARROW.has(char)) return 0.28;
+  if (WIDE.has(char)) return 0.85;
+  if (char !== char.toLowerCase()) return 0.65;
+  return 0.52;
+}
+
+/**
+ * Width of a string in points at the given size
+ */
+export function measureWidth(text: string, fontSize: number, bold = false): number {
+  let em = 0;
+  for (const char of text) {
+    em += charWidth(char);
+  }
+  return em * fontSize * (bold ? 1.06 : 1);
+}
+
+/**
+ * Number of rendered lines once each lyric line is word-wrapped to the box
+ */
+export function countWrappedLines(text: string, widthPt: number, fontSize: number, bold = false): number {
+  const spaceWidth = measureWidth(' ', fontSize, bold);
+  let total = 0;
+
+  for (const line of text.split('\n')) {
+    let rows = 1;
+    let current = 0;
+    for (const word of line.split(/\s+/).filter(Boolean)) {
+      const width = measureWidth(word, fontSize, bold);
+      if (current === 0) {
+        current = width;
+      } else if (current + spaceWidth + width <= widthPt) {
+        current += spaceWidth + width;
+      } else {
+        rows++;
+        current = width;
+      }
+      // Words wider than the box break across rows
+      while (current > widthPt) {
+        rows++;
+        current -= widthPt;
+      }
+    }
+    total += rows;
+  }
+
+  return total;
+}
+
+export function textFits(text: string, box: TextBox, fontSize: number, bold = false): boolean {
+  const widthPt = box.w * POINTS_PER_INCH - INSET_PT * 2;
+  const heightPt = box.h * POINTS_PER_INCH - INSET_PT * 2;
+  const lines = countWrappedLines(text, widthPt, fontSize, bold);
+  return lines * fontSize * LINE_HEIGHT <= heightPt;
+}
+
+/**
+ * Largest size between the bounds that fits, or null if none does
+ */
+export function largestFittingSize(text: string, box: TextBox, options: TextFitOptions): number | null {
+  const start = Math.min(Math.max(options.fontSize, options.minFontSize), options.maxFontSize);
+  for (let size = start; size >= options.minFontSize; size--) {
+    if (textFits(text, box, size, options.bold)) {
+      return size;
+    }
+  }
+  return null;
+}
+
+/**
+ * Split lines into two halves, keeping blank-line stanza breaks on a boundary when possible
+ */
+function splitLines(lines: string[]): [string[], string[]] {
+  const middle = Math.ceil(lines.length / 2);
+  let cut = middle;
+  for (let offset = 0; offset < middle; offset++) {
+    if (lines[middle - offset]?.trim() === '') { cut = middle - offset; break; }
+    if (lines[middle + offset]?.trim() === '') { cut = middle + offset; break; }
+  }
+  const first = lines.slice(0, cut);
+  const second = lines.slice(cut);
+  // Drop the blank separator line from the start of the second half
+  while (second.length > 0 && second[0].trim() === '') second.shift();
+  return [first, second];
+}
+
+function fitChunks(lines: string[], box: TextBox, options: TextFitOptions): TextFitChunk[] {
+  const text = lines.join('\n');
+  const size = largestFittingSize(text, box, options);
+  if (size !== null) {
+    return [{ text, fontSize: size, fits: true }];
+  }
+  if (lines.length < 2) {
+    return [{ text, fontSize: options.minFontSize, fits: false }];
+  }
+  const [first, second] = splitLines(lines);
+  if (first.length === 0 || second.length === 0) {
+    return [{ text, fontSize: options.minFontSize, fits: false }];
+  }
+  return [...fitChunks(first, box, options), ...fitChunks(second, box, options)];
+}
+
+/**
+ * Decide how a slide's text is laid out: keep the size, shrink it within the
+ * bounds, or split it across slides at line boundaries.
+ */
+export function fitText(text: string, box: TextBox, options: TextFitOptions): TextFitResult {
+  const chunks = fitChunks(text.split('\n'), box, options);
+  if (chunks.length > 1) {
+    return { action: 'split', chunks };
+  }
+  return { action: chunks[0].fontSize === options.fontSize ? 'fit' : 'shrink', chunks };
+}
+
+/**
+ * Divide text into a given number of chunks of roughly equal line counts,
+ * e.g. to keep a translation alongside a split primary text
+ */
+export function divideLines(text: string, parts: number): string[] {
+  const lines = text.split('\n');
+  const size = Math.ceil(lines.length / parts);
+  return Array.from({ length: parts }, (_, i) => lines.slice(i * size, (i + 1) * size).join('\n'));
+}
@@ -0,0 +1,169 @@
+/**
+ * PPTX Theme Store
+ * Named slide designs for PowerPoint export: background, text box geometry,
+ * logo placement, title slide layout and fonts.
+ *
+ * Built-in presets ship with the tool. User themes are JSON files in
+ * ~/.propresenter-words/themes/<id>.json and may override a preset by id.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import { DEFAULT_PPTX_THEME, PptxTheme } from '../pptx-exporter';
+
+export interface ThemeSummary extends PptxTheme {
+  builtIn: boolean;
+  /** Set for themes loaded from the themes folder */
+  filePath?: string;
+}
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const THEMES_DIR = path.join(CONFIG_DIR, 'themes');
+
+const THEME_ID_PATTERN = /^[a-z0-9][a-z0-9-]*$/;
+
+const BUILT_IN_THEMES: PptxTheme[] = [
+  DEFAULT_PPTX_THEME,
+  {
+    id: 'dark-stage',
+    name: 'Dark Stage',
+    description: 'Black slides with large white lyrics, no logo on lyric slides',
+    background: { color: '000000' },
+    text: { x: 0.5, y: 1.5, w: 12.333, h: 4.5, align: 'center', valign: 'middle' },
+    logo: { show: false, x: 6.0, y: 6.2, w: 1.2, h: 1.0 },
+    title: { x: 0.5, y: 2.75, w: 12.333, h: 2.0, showLogo: false },
+    footer: { x: 0.5, y: 6.95, w: 12.333, h: 0.45, align: 'center' },
+    fonts: { textColor: 'FFFFFF', fontSize: 48, titleFontSize: 60, bold: true, italic: false },
+  },
+  {
+    id: 'lower-third',
+    name: 'Lower Third',
+    description: 'Lyrics along the bottom of a dark slide, for use over video',
+    background: { color: '1A1A1A' },
+    text: { x: 0.5, y: 5.0, w: 12.333, h: 2.0, align: 'center', valign: 'bottom' },
+    logo: { show: true, x: 12.0, y: 0.3, w: 1.0, h: 0.8 },
+    title: { x: 0.5, y: 5.0, w: 12.333, h: 1.5, showLogo: true },
+    footer: { x: 0.3, y: 0.3, w: 8.0, h: 0.45, align: 'left' },
+    fonts: { textColor: 'FFFFFF', fontSize: 32, titleFontSize: 40, bold: true, italic: false },
+  },
+];
+
+function ensureThemesDir(): void {
+  if (!fs.existsSync(THEMES_DIR)) {
+    fs.mkdirSync(THEMES_DIR, { recursive: true });
+  }
+}
+
+/**
+ * Fill any missing parts of a (possibly hand-edited) theme from the default
+ */
+export function normalizeTheme(raw: Partial<PptxTheme> & { id: string }): PptxTheme {
+  const base = DEFAULT_PPTX_THEME;
+  return {
+    id: raw.id,
+    name: raw.name || raw.id,
+    description: raw.description,
+    background: { ...base.background, ...raw.background },
+    text: { ...base.text, ...raw.text },
+    logo: { ...base.logo, ...raw.logo },
+    title: { ...base.title, ...raw.title },
+    footer: { ...base.footer, ...raw.footer },
+    fonts: { ...raw.fonts },
+  };
+}
+
+function loadUserThemes(): ThemeSummary[] {
+  try {
+    if (!fs.existsSync(THEMES_DIR)) {
+      return [];
+    }
+    const themes: ThemeSummary[] = [];
+    for (const file of fs.readdirSync(THEMES_DIR)) {
+      if (!file.toLowerCase().endsWith('.json')) continue;
+      const filePath = path.join(THEMES_DIR, file);
+      try {
+        const raw = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+        const id = path.basename(file, path.extname(file)).toLowerCase();
+        themes.push({ ...normalizeTheme({ ...raw, id }), builtIn: false, filePath });
+      } catch {
+        // Skip files that aren't valid JSON
+      }
+    }
+    return themes;
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * All themes, presets first. A user theme with a preset's id replaces it.
+ */
+export function listThemes(): ThemeSummary[] {
+  const userThemes = loadUserThemes();
+  const userIds = new Set(userThemes.map(theme => theme.id));
+  const presets = BUILT_IN_THEMES
+    .filter(theme => !userIds.has(theme.id))
+    .map(theme => ({ ...theme, builtIn: true }));
+  return [...presets, ...userThemes.sort((a, b) => a.name.localeCompare(b.name))];
+}
+
+export function getTheme(id: string): ThemeSummary | null {
+  const wanted = id.trim().toLowerCase();
+  return listThemes().find(theme => theme.id === wanted) ?? null;
+}
+
+/**
+ * Look up a theme, throwing with the available ids when it doesn't exist
+ */
+export function requireTheme(id: string): PptxTheme {
+  const theme = getTheme(id);
+  if (!theme) {
+    const available = listThemes().map(t => t.id).join(', ');
+    throw new Error(`Unknown theme "${id}". Available: ${available}`);
+  }
+  return theme;
+}
+
+/**
+ * Create or replace a user theme
+ */
+export function saveTheme(theme: Partial<PptxTheme> & { id: string }): ThemeSummary {
+  const id = theme.id.trim().toLowerCase();
+  if (!THEME_ID_PATTERN.test(id)) {
+    throw new Error('Theme id may only contain lowercase letters, numbers and dashes');
+  }
+
+  const normalized = normalizeTheme({ ...theme, id });
+  ensureThemesDir();
+  const filePath = path.join(THEMES_DIR, `${id}.json`);
+  fs.writeFileSync(filePath, JSON.stringify(normalized, null, 2), 'utf-8');
+  return { ...normalized, builtIn: false, filePath };
+}
+
+/**
+ * Delete a user theme. Returns false if there was no such file.
+ */
+export function deleteTheme(id: string): boolean {
+  const theme = getTheme(id);
+  if (!theme || !theme.filePath) {
+    if (BUILT_IN_THEMES.some(preset => preset.id === id)) {
+      throw new Error(`"${id}" is a built-in theme and cannot be deleted`);
+    }
+    return false;
+  }
+  fs.unlinkSync(theme.filePath);
+  return true;
+}
+
+/**
+ * Copy an existing theme to a new user theme file for editing
+ */
+export function copyTheme(fromId: string, newId: string, name?: string): ThemeSummary {
+  const source = requireTheme(fromId);
+  return saveTheme({ ...source, id: newId, name: name || newId });
+}
+
+export function getThemesDir(): string {
+  return THEMES_DIR;
+}
@@ -0,0 +1,213 @@
+/**
+ * Usage Store
+ * Records which songs were exported or placed in a generated service, for
+ * CCLI copyright reporting. Stored in ~/.propresenter-words/usage-log.json
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import { randomUUID } from 'crypto';
+import type { ProPresenterClient } from '../propresenter-client';
+import { extractLyrics, ExtractedLyrics } from '../lyrics-extractor';
+import { UsageEntry, UsageReport, UsageReportRow, UsageSong, UsageSource } from '../types/usage';
+
+const CONFIG_DIR = path.join(os.homedir(), '.propresenter-words');
+const USAGE_FILE = path.join(CONFIG_DIR, 'usage-log.json');
+
+function ensureConfigDir(): void {
+  if (!fs.existsSync(CONFIG_DIR)) {
+    fs.mkdirSync(CONFIG_DIR, { recursive: true });
+  }
+}
+
+function todayISO(): string {
+  const now = new Date();
+  const month = String(now.getMonth() + 1).padStart(2, '0');
+  const day = String(now.getDate()).padStart(2, '0');
+  return `${now.getFullYear()}-${month}-${day}`;
+}
+
+/**
+ * Load the full usage log from disk
+ */
+export function loadUsageLog(): UsageEntry[] {
+  try {
+    if (!fs.existsSync(USAGE_FILE)) {
+      return [];
+    }
+    const data = fs.readFileSync(USAGE_FILE, 'utf-8');
+    return JSON.parse(data) as UsageEntry[];
+  } catch {
+    return [];
+  }
+}
+
+function saveUsageLog(entries: UsageEntry[]): void {
+  ensureConfigDir();
+  fs.writeFileSync(USAGE_FILE, JSON.stringify(entries, null, 2), 'utf-8');
+}
+
+/**
+ * Record a playlist's songs. Re-exporting the same playlist on the same date
+ * replaces the earlier entry so songs aren't counted twice.
+ */
+export function recordUsage(input: {
+  source: UsageSource;
+  playlistName: string;
+  playlistId?: string;
+  date?: string;
+  songs: UsageSong[];
+}): UsageEntry | null {
+  if (input.songs.length === 0) {
+    return null;
+  }
+
+  const entry: UsageEntry = {
+    id: randomUUID(),
+    date: input.date || todayISO(),
+    recordedAt: new Date().toISOString(),
+    source: input.source,
+    playlistId: input.playlistId,
+    playlistName: input.playlistName,
+    songs: input.songs,
+  };
+
+  const entries = loadUsageLog().filter(existing => !(
+    existing.date === entry.date &&
+    (entry.playlistId ? existing.playlistId === entry.playlistId : existing.playlistName === entry.playlistName)
+  ));
+  entries.push(entry);
+  saveUsageLog(entries);
+  return entry;
+}
+
+/**
+ * Record an export. Never throws — usage tracking must not break exports.
+ */
+export function recordExportUsage(
+  songs: ExtractedLyrics[],
+  playlist: { name: string; id?: string }
+): void {
+  try {
+    recordUsage({
+      source: 'export',
+      playlistName: playlist.name,
+      playlistId: playlist.id,
+      songs: songs.map(song => ({
+        title: song.title,
+        uuid: song.uuid,
+        ccliNumber: song.metadata?.ccliNumber,
+      })),
+    });
+  } catch (error) {
+    console.warn('[usage] Failed to record export usage:', error);
+  }
+}
+
+/**
+ * Record a generated service playlist. CCLI numbers are looked up from each
+ * presentation when a client is available. Never throws.
+ */
+export async function recordServiceUsage(
+  client: ProPresenterClient | null,
+  playlist: { name: string; id?: string },
+  items: Array<{ type?: string; uuid: string; name: string }>
+): Promise<void> {
+  try {
+    const songs: UsageSong[] = [];
+    for (const item of items) {
+      if (item.type && item.type !== 'song') continue;
+
+      let ccliNumber: string | undefined;
+      if (client) {
+        try {
+          const presentation = await client.getPresentationByUuid(item.uuid);
+          // extractLyrics also picks up numbers printed on copyright slides
+          ccliNumber = presentation ? extractLyrics(presentation).metadata?.ccliNumber : undefined;
+        } catch {
+          // Missing CCLI numbers are reported as blank
+        }
+      }
+      songs.push({ title: item.name, uuid: item.uuid, ccliNumber });
+    }
+
+    recordUsage({
+      source: 'service-generator',
+      playlistName: playlist.name,
+      playlistId: playlist.id,
+      songs,
+    });
+  } catch (error) {
+    console.warn('[usage] Failed to record service usage:', error);
+  }
+}
+
+/**
+ * Entries within an inclusive YYYY-MM-DD date range
+ */
+export function getUsageEntries(from?: string, to?: string): UsageEntry[] {
+  return loadUsageLog()
+    .filter(entry => (!from || entry.date >= from) && (!to || entry.date <= to))
+    .sort((a, b) => a.date.localeCompare(b.date));
+}
+
+/**
+ * Aggregate usage per song for a date range
+ */
+export function buildUsageReport(from?: string, to?: string): UsageReport {
+  const entries = getUsageEntries(from, to);
+  const rows = new Map<string, UsageReportRow>();
+
+  for (const entry of entries) {
+    for (const song of entry.songs) {
+      const key = song.ccliNumber || song.uuid || song.title.toLowerCase();
+      const row = rows.get(key) ?? {
+        title: song.title,
+        ccliNumber: song.ccliNumber,
+        uuid: song.uuid,
+        timesUsed: 0,
+        dates: [],
+      };
+      row.timesUsed++;
+      if (!row.dates.includes(entry.date)) {
+        row.dates.push(entry.date);
+      }
+      rows.set(key, row);
+    }
+  }
+
+  return {
+    from,
+    to,
+    generatedAt: new Date().toISOString(),
+    totalPlaylists: entries.length,
+    songs: Array.from(rows.values()).sort((a, b) => a.title.localeCompare(b.title)),
+  };
+}
+
+function csvCell(value: string | number | undefined): string {
+  const text = value === undefined ? '' : String(value);
+  return /[",\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text;
+}
+
+/**
+ * Format a usage report as CSV, one row per song
+ */
+export function formatUsageReportCsv(report: UsageReport): string {
+  const lines = ['Song Title,CCLI Number,Times Used,Dates,Presentation UUID'];
+  for (const row of report.songs) {
+    lines.push([
+      csvCell(row.title),
+      csvCell(row.ccliNumber),
+      csvCell(row.timesUsed),
+      csvCell(row.dates.join(' ')),
+      csvCell(row.uuid),
+    ].join(','));
+  }
+  return lines.join('\n') + '\n';
+}
+
+export function getUsageFilePath(): string {
+  return USAGE_FILE;
+}
@@ -0,0 +1,216 @@
+/**
+ * Simulator Fixtures - Seed data for the local ProPresenter API simulator
+ *
+ * Fixtures describe libraries, presentations and playlists in a compact,
+ * hand-editable JSON form. The simulator turns them into ProPresenter 7
+ * Network API responses (see presentationJson in server.ts for the fields
+ * that have not been checked against real ones). A set of built-in fixtures
+ * ships with the tool; .pro, .proplaylist and library folders can be used
+ * as seed data too.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { randomUUID } from 'crypto';
+import type { PresentationInfo, SongMetadata } from '../propresenter-client';
+import { loadPresentationsFromPath, isOfflineSource } from '../services/pro-file-reader';
+import defaultFixtures from './fixtures/default.json';
+
+export interface FixtureVersion {
+  name?: string;
+  platform?: string;
+  osVersion?: string;
+  major?: number;
+  minor?: number;
+  patch?: number;
+}
+
+export interface FixtureSlide {
+  text: string;
+  /** Separate text elements, e.g. lyrics and their translation */
+  elements?: string[];
+  notes?: string;
+  label?: string;
+  enabled?: boolean;
+}
+
+export interface FixtureGroup {
+  uuid?: string;
+  name: string;
+  /** Hex color, e.g. "#2D6A7A" */
+  color?: string;
+  /** Plain strings are shorthand for { text } */
+  slides: Array<string | FixtureSlide>;
+}
+
+export interface FixtureArrangement {
+  uuid: string;
+  name: string;
+  /** Group names or UUIDs in play order; repeats allowed */
+  groups: string[];
+}
+
+export interface FixturePresentation {
+  uuid: string;
+  name: string;
+  ccliNumber?: string;
+  /** Song credits; ccliNumber above takes precedence for the song number */
+  metadata?: SongMetadata;
+  groups: FixtureGroup[];
+  arrangements?: FixtureArrangement[];
+  /** UUID of the arrangement selected in the presentation */
+  selectedArrangement?: string;
+}
+
+export interface FixtureLibrary {
+  uuid: string;
+  name: string;
+  /** Presentation UUIDs in library order */
+  presentations: string[];
+}
+
+export interface FixtureHeaderItem {
+  type: 'header';
+  name: string;
+  color?: string;
+}
+
+export interface FixturePresentationItem {
+  type: 'presentation';
+  presentationUuid: string;
+  /** Defaults to the presentation's name */
+  name?: string;
+  /** Arrangement name to play this item with */
+  arrangement?: string;
+}
+
+export type FixturePlaylistItem = FixtureHeaderItem | FixturePresentationItem;
+
+export interface FixturePlaylist {
+  uuid: string;
+  name: string;
+  /** Groups hold child playlists, playlists hold items */
+  type?: 'playlist' | 'group';
+  children?: FixturePlaylist[];
+  items?: FixturePlaylistItem[];
+}
+
+export interface SimulatorFixtures {
+  version?: FixtureVersion;
+  libraries: FixtureLibrary[];
+  presentations: FixturePresentation[];
+  playlists: FixturePlaylist[];
+}
+
+/**
+ * The fixtures bundled with the tool
+ */
+export function getDefaultFixtures(): SimulatorFixtures {
+  // Deep copy so the simulator can mutate playlists freely
+  return JSON.parse(JSON.stringify(defaultFixtures)) as SimulatorFixtures;
+}
+
+/**
+ * Build fixtures from presentations read offline. Everything goes into one
+ * library and one playlist named after the source.
+ */
+export function fixturesFromPresentations(name: string, presentations: PresentationInfo[]): SimulatorFixtures {
+  const converted: FixturePresentation[] = presentations.map(presentation => ({
+    uuid: presentation.uuid || randomUUID().toUpperCase(),
+    name: presentation.name,
+    ccliNumber: presentation.metadata?.ccliNumber,
+    metadata: presentation.metadata,
+    groups: presentation.groups.map(group => ({
+      uuid: group.uuid,
+      name: group.name,
+      color: group.color || undefined,
+      slides: group.slides.map(slide => ({
+        text: slide.text,
+        elements: slide.elements,
+        notes: slide.notes,
+        label: slide.label,
+        enabled: slide.enabled,
+      })),
+    })),
+    arrangements: presentation.arrangements,
+    selectedArrangement: presentation.selectedArrangementUuid,
+  }));
+
+  return {
+    libraries: [{
+      uuid: randomUUID().toUpperCase(),
+      name,
+      presentations: converted.map(p => p.uuid),
+    }],
+    presentations: converted,
+    playlists: [{
+      uuid: randomUUID().toUpperCase(),
+      name,
+      type: 'playlist',
+      items: converted.map(p => ({ type: 'presentation' as const, presentationUuid: p.uuid })),
+    }],
+  };
+}
+
+function validateFixtures(fixtures: SimulatorFixtures, source: string): SimulatorFixtures {
+  for (const key of ['libraries', 'presentations', 'playlists'] as const) {
+    if (!Array.isArray(fixtures[key])) {
+      throw new Error(`Invalid fixtures in ${source}: "${key}" must be an array`);
+    }
+  }
+
+  const known = new Set(fixtures.presentations.map(p => p.uuid));
+
+  for (const library of fixtures.libraries) {
+    for (const uuid of library.presentations || []) {
+      if (!known.has(uuid)) {
+        throw new Error(`Library "${library.name}" references unknown presentation ${uuid}`);
+      }
+    }
+  }
+
+  const checkPlaylist = (playlist: FixturePlaylist): void => {
+    for (const item of playlist.items || []) {
+      if (item.type !== 'presentation') continue;
+      const presentation = fixtures.presentations.find(p => p.uuid === item.presentationUuid);
+      if (!presentation) {
+        throw new Error(`Playlist "${playlist.name}" references unknown presentation ${item.presentationUuid}`);
+      }
+      if (item.arrangement && !(presentation.arrangements || []).some(a => a.name === item.arrangement)) {
+        throw new Error(`Playlist "${playlist.name}" uses unknown arrangement "${item.arrangement}" of "${presentation.name}"`);
+      }
+    }
+    (playlist.children || []).forEach(checkPlaylist);
+  };
+  fixtures.playlists.forEach(checkPlaylist);
+
+  return fixtures;
+}
+
+/**
+ * Load fixtures from a JSON file, a .pro/.proplaylist file or a folder of
+ * .pro files. With no source the built-in fixtures are used.
+ */
+export function loadFixtures(source?: string): SimulatorFixtures {
+  if (!source) {
+    return getDefaultFixtures();
+  }
+
+  if (isOfflineSource(source)) {
+    const playlist = loadPresentationsFromPath(source);
+    return fixturesFromPresentations(playlist.name, playlist.presentations);
+  }
+
+  if (!fs.existsSync(source)) {
+    throw new Error(`Fixtures file not found: ${source}`);
+  }
+
+  let parsed: SimulatorFixtures;
+  try {
+    parsed = JSON.parse(fs.readFileSync(source, 'utf-8'));
+  } catch (error: any) {
+    throw new Error(`Could not parse fixtures ${path.basename(source)}: ${error.message}`);
+  }
+
+  return validateFixtures(parsed, source);
+}
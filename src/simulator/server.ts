@@ -0,0 +1,567 @@
+/**
+ * ProPresenter API Simulator - Local stand-in for the ProPresenter 7 Network API
+ *
+ * Serves the endpoints this project uses (version, libraries, playlists,
+ * presentations, slide status and thumbnails) from in-memory state seeded by
+ * fixtures, so the client, viewer, exporters and service generator can be
+ * developed and demoed without a Mac running ProPresenter.
+ */
+
+import express, { Request, Response } from 'express';
+import http from 'http';
+import { randomUUID } from 'crypto';
+import type {
+  FixtureGroup,
+  FixturePlaylist,
+  FixturePresentation,
+  FixtureSlide,
+  SimulatorFixtures,
+} from './fixtures';
+
+export interface SimulatorOptions {
+  host: string;
+  port: number;
+  fixtures: SimulatorFixtures;
+  /** Advance the active presentation one slide every N ms (0 disables) */
+  advanceIntervalMs?: number;
+  /** Called with a one-line summary of every request */
+  log?: (line: string) => void;
+}
+
+export interface RunningSimulator {
+  server: http.Server;
+  url: string;
+  close(): Promise<void>;
+}
+
+interface PlaylistNode {
+  uuid: string;
+  name: string;
+  type: 'playlist' | 'group';
+  children: PlaylistNode[];
+  items: any[];
+}
+
+interface FlatSlide {
+  group: FixtureGroup;
+  slide: FixtureSlide;
+}
+
+const VERSION_DEFAULTS = {
+  name: 'ProPresenter Simulator',
+  platform: 'mac',
+  osVersion: '14.0',
+  major: 7,
+  minor: 16,
+  patch: 2,
+};
+
+function hexToColor(hex?: string): { red: number; green: number; blue: number; alpha: number } | undefined {
+  const match = /^#?([0-9a-f]{6})$/i.exec(hex || '');
+  if (!match) return undefined;
+  const value = parseInt(match[1], 16);
+  const channel = (shift: number) => Math.round(((value >> shift) & 0xff) / 255 * 1000) / 1000;
+  return { red: channel(16), green: channel(8), blue: channel(0), alpha: 1 };
+}
+
+function normalizeSlide(slide: string | FixtureSlide): FixtureSlide {
+  return typeof slide === 'string' ? { text: slide } : slide;
+}
+
+/**
+ * CCLI details. The field names mirror the .pro CCLI record and are what
+ * ProPresenterClient reads; they have not been checked against a real /v1
+ * response.
+ */
+function ccliJson(presentation: FixturePresentation): Record<string, unknown> | undefined {
+  const metadata = presentation.metadata || {};
+  const songNumber = presentation.ccliNumber || metadata.ccliNumber;
+  if (!songNumber && !metadata.author && !metadata.publisher && !metadata.copyrightYear) {
+    return undefined;
+  }
+  return {
+    song_number: songNumber ? Number(songNumber) : undefined,
+    author: metadata.author,
+    artist_credits: metadata.artist,
+    publisher: metadata.publisher,
+    copyright_year: metadata.copyrightYear,
+    album: metadata.album,
+  };
+}
+
+function escapeXml(text: string): string {
+  return text
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+/**
+ * Render a slide as a 16:9 SVG thumbnail
+ */
+function renderThumbnail(text: string, label: string): string {
+  const lines = text.split('\n').slice(0, 6);
+  const lineHeight = 22;
+  const top = 90 - ((lines.length - 1) * lineHeight) / 2;
+  const rows = lines
+    .map((line, i) => `<text x="160" y="${top + i * lineHeight}" text-anchor="middle" dominant-baseline="middle">${escapeXml(line)}</text>`)
+    .join('');
+
+  return [
+    '<svg xmlns="http://www.w3.org/2000/svg" width="320" height="180" viewBox="0 0 320 180">',
+    '<rect width="320" height="180" fill="#000"/>',
+    `<g fill="#fff" font-family="Helvetica, Arial, sans-serif" font-size="14">${rows}</g>`,
+    label ? `<text x="8" y="170" fill="#888" font-family="Helvetica, Arial, sans-serif" font-size="10">${escapeXml(label)}</text>` : '',
+    '</svg>',
+  ].join('');
+}
+
+/**
+ * In-memory ProPresenter state built from fixtures
+ */
+class SimulatorState {
+  readonly version: typeof VERSION_DEFAULTS;
+  readonly presentations = new Map<string, FixturePresentation>();
+  readonly libraries: SimulatorFixtures['libraries'];
+  readonly playlists: PlaylistNode[];
+
+  activePlaylistUuid: string | null = null;
+  activeItemIndex = -1;
+  focusedPlaylistUuid: string | null = null;
+  activePresentationUuid: string | null = null;
+  focusedPresentationUuid: string | null = null;
+  slideIndex = 0;
+
+  constructor(fixtures: SimulatorFixtures) {
+    this.version = { ...VERSION_DEFAULTS, ...(fixtures.version || {}) };
+    this.libraries = fixtures.libraries;
+    for (const presentation of fixtures.presentations) {
+      this.presentations.set(presentation.uuid, presentation);
+    }
+    this.playlists = fixtures.playlists.map(p => this.buildPlaylist(p));
+
+    // Start with the first playlist that has a presentation in it, as if an
+    // operator had just clicked it
+    const first = this.allPlaylists().find(p => p.items.some(item => item.type === 'presentation'));
+    if (first) {
+      const index = first.items.findIndex(item => item.type === 'presentation');
+      this.trigger(first, index);
+      this.focusedPlaylistUuid = first.uuid;
+    }
+  }
+
+  private buildPlaylist(fixture: FixturePlaylist): PlaylistNode {
+    const type = fixture.type ?? (fixture.children ? 'group' : 'playlist');
+    const node: PlaylistNode = {
+      uuid: fixture.uuid,
+      name: fixture.name,
+      type,
+      children: (fixture.children || []).map(child => this.buildPlaylist(child)),
+      items: [],
+    };
+
+    node.items = (fixture.items || []).map((item, index) => {
+      if (item.type === 'header') {
+        return {
+          id: { uuid: randomUUID().toUpperCase(), name: item.name, index },
+          type: 'header',
+          is_hidden: false,
+          is_pco: false,
+          header_color: hexToColor(item.color),
+          target_uuid: '',
+        };
+      }
+      const presentation = this.presentations.get(item.presentationUuid);
+      const arrangement = presentation?.arrangements?.find(a => a.name === item.arrangement);
+      return {
+        id: { uuid: randomUUID().toUpperCase(), name: item.name || presentation?.name || 'Untitled', index },
+        type: 'presentation',
+        is_hidden: false,
+        is_pco: false,
+        target_uuid: item.presentationUuid,
+        presentation_info: {
+          presentation_uuid: item.presentationUuid,
+          arrangement_name: arrangement?.name || '',
+          arrangement_uuid: arrangement?.uuid || '',
+        },
+        destination: 'presentation',
+      };
+    });
+
+    return node;
+  }
+
+  allPlaylists(nodes: PlaylistNode[] = this.playlists): PlaylistNode[] {
+    return nodes.flatMap(node => [node, ...this.allPlaylists(node.children)]);
+  }
+
+  findPlaylist(uuid: string): PlaylistNode | undefined {
+    return this.allPlaylists().find(p => p.uuid === uuid);
+  }
+
+  createPlaylist(name: string, type: 'playlist' | 'group' = 'playlist'): PlaylistNode {
+    const node: PlaylistNode = { uuid: randomUUID().toUpperCase(), name, type, children: [], items: [] };
+    this.playlists.push(node);
+    return node;
+  }
+
+  /**
+   * Trigger a playlist item. Presentations become active at their first slide.
+   */
+  trigger(playlist: PlaylistNode, index: number): boolean {
+    const item = playlist.items[index];
+    if (!item) return false;
+
+    this.activePlaylistUuid = playlist.uuid;
+    this.activeItemIndex = index;
+
+    const presentationUuid = item.presentation_info?.presentation_uuid;
+    if (item.type === 'presentation' && presentationUuid && this.presentations.has(presentationUuid)) {
+      this.activePresentationUuid = presentationUuid;
+      this.focusedPresentationUuid = presentationUuid;
+      this.slideIndex = 0;
+    }
+    return true;
+  }
+
+  slides(uuid: string | null): FlatSlide[] {
+    const presentation = uuid ? this.presentations.get(uuid) : undefined;
+    if (!presentation) return [];
+    return presentation.groups.flatMap(group =>
+      group.slides.map(slide => ({ group, slide: normalizeSlide(slide) }))
+    );
+  }
+
+  /**
+   * Move through the active presentation, wrapping at either end
+   */
+  step(delta: number): void {
+    const count = this.slides(this.activePresentationUuid).length;
+    if (count === 0) return;
+    this.slideIndex = (this.slideIndex + delta + count) % count;
+  }
+
+  /**
+   * A presentation as /v1/presentation/:uuid returns it. The id, groups and
+   * slide text are the fields the client already read from ProPresenter;
+   * elements, ccli, arrangements and current_arrangement are unverified
+   * guesses that ProPresenterClient reads, so passing against the simulator
+   * doesn't prove them.
+   */
+  presentationJson(uuid: string): any {
+    const presentation = this.presentations.get(uuid)!;
+    return {
+      presentation: {
+        id: { uuid: presentation.uuid, name: presentation.name, index: 0 },
+        groups: presentation.groups.map(group => ({
+          uuid: group.uuid,
+          name: group.name,
+          color: hexToColor(group.color),
+          slides: group.slides.map(normalizeSlide).map(slide => ({
+            enabled: slide.enabled ?? true,
+            notes: slide.notes || '',
+            text: slide.text || (slide.elements || []).join('\n'),
+            elements: slide.elements,
+            label: slide.label || '',
+            size: { width: 1920, height: 1080 },
+          })),
+        })),
+        has_timeline: false,
+        presentation_path: `${presentation.name}.pro`,
+        destination: 'presentation',
+        ccli: ccliJson(presentation),
+        arrangements: (presentation.arrangements || []).map((arrangement, index) => ({
+          id: { uuid: arrangement.uuid, name: arrangement.name, index },
+          groups: arrangement.groups,
+        })),
+        current_arrangement: presentation.selectedArrangement,
+      },
+    };
+  }
+
+  playlistTreeJson(nodes: PlaylistNode[] = this.playlists): any[] {
+    return nodes.map((node, index) => ({
+      id: { uuid: node.uuid, name: node.name, index },
+      field_type: node.type,
+      ...(node.type === 'group' ? { children: this.playlistTreeJson(node.children) } : {}),
+    }));
+  }
+
+  playlistRef(uuid: string | null): any {
+    const playlist = uuid ? this.findPlaylist(uuid) : undefined;
+    if (!playlist) return null;
+    const index = this.allPlaylists().indexOf(playlist);
+    return { uuid: playlist.uuid, name: playlist.name, index };
+  }
+}
+
+/**
+ * Build the simulator's Express app
+ */
+export function createSimulatorApp(fixtures: SimulatorFixtures, log?: (line: string) => void) {
+  const state = new SimulatorState(fixtures);
+  const app = express();
+
+  app.use(express.json({ limit: '10mb' }));
+
+  if (log) {
+    app.use((req, res, next) => {
+      res.on('finish', () => log(`${req.method} ${req.path} → ${res.statusCode}`));
+      next();
+    });
+  }
+
+  const notFound = (res: Response, what: string) => {
+    res.status(404).json({ error: `${what} not found` });
+  };
+
+  // --- Version ---
+
+  const version = (_req: Request, res: Response) => {
+    res.json({
+      name: state.version.name,
+      platform: state.version.platform,
+      os_version: state.version.osVersion,
+      host_description: `ProPresenter ${state.version.major}.${state.version.minor}.${state.version.patch}`,
+      api_version: 'v1',
+      major: state.version.major,
+      minor: state.version.minor,
+      patch: state.version.patch,
+    });
+  };
+  app.get('/version', version);
+  app.get('/v1/version', version);
+
+  // --- Libraries ---
+
+  app.get('/v1/libraries', (_req, res) => {
+    res.json(state.libraries.map((library, index) => ({
+      id: { uuid: library.uuid, name: library.name, index },
+    })));
+  });
+
+  app.get('/v1/library/:id', (req, res) => {
+    const library = state.libraries.find(l => l.uuid === req.params.id || l.name === req.params.id);
+    if (!library) return notFound(res, 'Library');
+
+    res.json({
+      update_type: 'all',
+      items: library.presentations.map((uuid, index) => ({
+        id: { uuid, name: state.presentations.get(uuid)?.name || 'Untitled', index },
+      })),
+    });
+  });
+
+  // --- Playlists ---
+
+  app.get('/v1/playlists', (_req, res) => {
+    res.json(state.playlistTreeJson());
+  });
+
+  app.post('/v1/playlists', (req, res) => {
+    const name = typeof req.body?.name === 'string' ? req.body.name.trim() : '';
+    if (!name) {
+      res.status(400).json({ error: 'name is required' });
+      return;
+    }
+    const node = state.createPlaylist(name, req.body.type === 'group' ? 'group' : 'playlist');
+    res.json({
+      id: { uuid: node.uuid, name: node.name, index: state.playlists.length - 1 },
+      field_type: node.type,
+    });
+  });
+
+  const playlistStatus = (uuid: string | null) => {
+    const playlist = state.playlistRef(uuid);
+    const active = uuid && uuid === state.activePlaylistUuid ? state.findPlaylist(uuid) : undefined;
+    const item = active?.items[state.activeItemIndex]?.id ?? null;
+    return { presentation: { playlist, item }, announcements: { playlist: null, item: null } };
+  };
+
+  app.get('/v1/playlist/active', (_req, res) => {
+    res.json(playlistStatus(state.activePlaylistUuid));
+  });
+
+  app.get('/v1/playlist/focused', (_req, res) => {
+    res.json(playlistStatus(state.focusedPlaylistUuid));
+  });
+
+  app.get('/v1/playlist/:id', (req, res) => {
+    const playlist = state.findPlaylist(req.params.id);
+    if (!playlist || playlist.type !== 'playlist') return notFound(res, 'Playlist');
+
+    res.json({
+      id: state.playlistRef(playlist.uuid),
+      items: playlist.items,
+    });
+  });
+
+  app.put('/v1/playlist/:id', (req, res) => {
+    const playlist = state.findPlaylist(req.params.id);
+    if (!playlist || playlist.type !== 'playlist') return notFound(res, 'Playlist');
+
+    if (!Array.isArray(req.body)) {
+      res.status(400).json({ error: 'Expected an array of playlist items' });
+      return;
+    }
+
+    for (const item of req.body) {
+      if (item?.type !== 'header' && item?.type !== 'presentation') {
+        res.status(400).json({ error: `Unsupported playlist item type: ${item?.type}` });
+        return;
+      }
+      const presentationUuid = item.presentation_info?.presentation_uuid;
+      if (item.type === 'presentation' && !state.presentations.has(presentationUuid)) {
+        res.status(400).json({ error: `Unknown presentation: ${presentationUuid}` });
+        return;
+      }
+    }
+
+    playlist.items = req.body.map((item: any, index: number) => ({
+      ...item,
+      id: {
+        uuid: item.id?.uuid || randomUUID().toUpperCase(),
+        name: item.id?.name || 'Untitled',
+        index,
+      },
+    }));
+    res.status(204).end();
+  });
+
+  app.get('/v1/playlist/:id/focus', (req, res) => {
+    const playlist = state.findPlaylist(req.params.id);
+    if (!playlist) return notFound(res, 'Playlist');
+    state.focusedPlaylistUuid = playlist.uuid;
+    res.status(204).end();
+  });
+
+  app.get('/v1/playlist/:id/trigger', (req, res) => {
+    const playlist = state.findPlaylist(req.params.id);
+    if (!playlist || !state.trigger(playlist, 0)) return notFound(res, 'Playlist item');
+    res.status(204).end();
+  });
+
+  app.get('/v1/playlist/:id/:index/trigger', (req, res) => {
+    const playlist = state.findPlaylist(req.params.id);
+    if (!playlist || !state.trigger(playlist, parseInt(req.params.index, 10))) {
+      return notFound(res, 'Playlist item');
+    }
+    res.status(204).end();
+  });
+
+  // --- Presentations ---
+
+  app.get('/v1/presentation/active', (_req, res) => {
+    if (!state.activePresentationUuid) return notFound(res, 'Active presentation');
+    res.json(state.presentationJson(state.activePresentationUuid));
+  });
+
+  app.get('/v1/presentation/focused', (_req, res) => {
+    if (!state.focusedPresentationUuid) return notFound(res, 'Focused presentation');
+    res.json(state.presentationJson(state.focusedPresentationUuid));
+  });
+
+  app.get('/v1/presentation/slide_index', (_req, res) => {
+    const uuid = state.activePresentationUuid;
+    if (!uuid) {
+      res.json({ presentation_index: null });
+      return;
+    }
+    res.json({
+      presentation_index: {
+        index: state.slideIndex,
+        presentation_id: { uuid, name: state.presentations.get(uuid)!.name, index: 0 },
+      },
+    });
+  });
+
+  app.get('/v1/presentation/active/next/trigger', (_req, res) => {
+    state.step(1);
+    res.status(204).end();
+  });
+
+  app.get('/v1/presentation/active/previous/trigger', (_req, res) => {
+    state.step(-1);
+    res.status(204).end();
+  });
+
+  app.get('/v1/presentation/:uuid', (req, res) => {
+    if (!state.presentations.has(req.params.uuid)) return notFound(res, 'Presentation');
+    res.json(state.presentationJson(req.params.uuid));
+  });
+
+  app.get('/v1/presentation/:uuid/:index/trigger', (req, res) => {
+    const slides = state.slides(req.params.uuid);
+    const index = parseInt(req.params.index, 10);
+    if (!slides[index]) return notFound(res, 'Slide');
+    state.activePresentationUuid = req.params.uuid;
+    state.focusedPresentationUuid = req.params.uuid;
+    state.slideIndex = index;
+    res.status(204).end();
+  });
+
+  app.get('/v1/presentation/:uuid/thumbnail/:index', (req, res) => {
+    const slide = state.slides(req.params.uuid)[parseInt(req.params.index, 10)];
+    if (!slide) return notFound(res, 'Slide');
+    res.setHeader('Content-Type', 'image/svg+xml');
+    res.send(renderThumbnail(slide.slide.text || '', slide.slide.label || slide.group.name));
+  });
+
+  app.get('/v1/trigger/next', (_req, res) => {
+    state.step(1);
+    res.status(204).end();
+  });
+
+  app.get('/v1/trigger/previous', (_req, res) => {
+    state.step(-1);
+    res.status(204).end();
+  });
+
+  // --- Status ---
+
+  app.get('/v1/status/slide', (_req, res) => {
+    const slides = state.slides(state.activePresentationUuid);
+    const toJson = (entry?: FlatSlide) => entry
+      ? { text: entry.slide.text || '', notes: entry.slide.notes || '', uuid: '' }
+      : null;
+    res.json({
+      current: toJson(slides[state.slideIndex]),
+      next: toJson(slides[state.slideIndex + 1]),
+    });
+  });
+
+  app.use((req, res) => {
+    res.status(404).json({ error: `Not implemented by simulator: ${req.method} ${req.path}` });
+  });
+
+  return { app, state };
+}
+
+/**
+ * Start the simulator and resolve once it is listening
+ */
+export function startSimulator(options: SimulatorOptions): Promise<RunningSimulator> {
+  const { app, state } = createSimulatorApp(options.fixtures, options.log);
+
+  return new Promise((resolve, reject) => {
+    const server = app.listen(options.port, options.host);
+    let timer: NodeJS.Timeout | null = null;
+
+    server.once('error', reject);
+    server.once('listening', () => {
+      if (options.advanceIntervalMs && options.advanceIntervalMs > 0) {
+        timer = setInterval(() => state.step(1), options.advanceIntervalMs);
+      }
+
+      resolve({
+        server,
+        url: `http://${options.host}:${options.port}`,
+        close: () => new Promise<void>(done => {
+          if (timer) clearInterval(timer);
+          server.close(() => done());
+        }),
+      });
+    });
+  });
+}
@@ -0,0 +1,111 @@
+/**
+ * Classification Rules Test Script
+ * Checks rule order, the skip list, pattern parsing and rule set validation.
+ * Run with: npx ts-node src/test-classification-rules.ts
+ */
+
+import type { SlideInfo } from './propresenter-client';
+import {
+  ClassificationRuleSet,
+  DEFAULT_CLASSIFICATION_RULES,
+  classifySlide,
+  parsePattern,
+  validateClassificationRules,
+} from './services/classification-rules';
+import { check, finishChecks } from './test-helpers';
+
+function slide(text: string, extra: Partial<SlideInfo> = {}): SlideInfo {
+  return { index: 0, text, notes: '', label: '', enabled: true, ...extra };
+}
+
+function testDefaultRules(): void {
+  const decide = (text: string, group = 'Verse 1') => {
+    const result = classifySlide(slide(text), group, DEFAULT_CLASSIFICATION_RULES);
+    return [result.isLyric, result.ruleId];
+  };
+  check('1-2 character slides are excluded', decide('  A '), [false, 'too-short']);
+  check('scripture references are excluded', decide('John 3:16 For God so loved the world', 'Reading'), [false, 'scripture-reference']);
+  check('announcements are excluded', decide('Welcome to St Andrews', 'Intro'), [false, 'announcements']);
+  check('song sections are included', decide('Amazing grace how sweet the sound'), [true, 'lyric-sections']);
+  check('slides no rule matches get the default action', decide('Amazing grace how sweet the sound', 'Group 7'), [true, null]);
+}
+
+function testSkips(): void {
+  const disabled = slide('Amazing grace', { enabled: false });
+  check('disabled slides are skipped', classifySlide(disabled, 'Verse 1', DEFAULT_CLASSIFICATION_RULES).ruleId, 'skip-disabled');
+  check('skipDisabled false leaves disabled slides to the rules',
+    classifySlide(disabled, 'Verse 1', { ...DEFAULT_CLASSIFICATION_RULES, skipDisabled: false }).ruleId, 'lyric-sections');
+  check('skip labels match whole labels, ignoring case',
+    classifySlide(slide('Amazing grace', { label: ' blank ' }), 'Verse 1', DEFAULT_CLASSIFICATION_RULES).ruleId, 'skip-label');
+  check('labels that only contain a skip label are not skipped',
+    classifySlide(slide('Amazing grace', { label: 'Blank verse' }), 'Verse 1', DEFAULT_CLASSIFICATION_RULES).ruleId, 'lyric-sections');
+}
+
+function testRuleOrder(): void {
+  const ruleSet: ClassificationRuleSet = {
+    defaultAction: 'exclude',
+    rules: [
+      { id: 'low', action: 'include', priority: 1, match: { text: 'grace' } },
+      { id: 'first', action: 'exclude', priority: 5, match: { text: 'grace' } },
+      { id: 'second', action: 'include', priority: 5, match: { text: 'grace' } },
+      { id: 'off', action: 'include', priority: 10, disabled: true, match: { text: 'grace' } },
+    ],
+  };
+  const result = classifySlide(slide('Amazing grace'), 'Verse 1', ruleSet);
+  check('highest priority wins, file order breaks ties, disabled rules are skipped', [result.isLyric, result.ruleId], [false, 'first']);
+  check('the default action applies when nothing matches',
+    classifySlide(slide('Something else'), 'Verse 1', ruleSet), { isLyric: false, ruleId: null, reason: 'No rule matched (default: exclude)' });
+
+  const lengths: ClassificationRuleSet = {
+    defaultAction: 'include',
+    rules: [{ id: 'mid', action: 'exclude', priority: 1, match: { minLength: 3, maxLength: 5, slideEnabled: true } }],
+  };
+  check('length bounds are inclusive',
+    ['ab', 'abc', 'abcde', 'abcdef'].map(text => classifySlide(slide(text), 'Verse 1', lengths).ruleId), [null, 'mid', 'mid', null]);
+}
+
+function testPatterns(): void {
+  check('plain patterns ignore case', parsePattern('chorus').test('CHORUS 2'), true);
+  check('/pattern/ keeps case', parsePattern('/Chorus/').test('chorus'), false);
+  check('/pattern/flags keeps other flags', parsePattern('/chorus/i').flags, 'i');
+  check('the g and y flags are dropped', parsePattern('/chorus/giy').flags, 'i');
+
+  // A g flag would make test() start from the last match on the next slide
+  const ruleSet: ClassificationRuleSet = {
+    defaultAction: 'include',
+    rules: [{ id: 'g', action: 'exclude', priority: 1, match: { text: '/grace/g' } }],
+  };
+  check('a /g pattern matches the same slide every time',
+    [1, 2, 3].map(() => classifySlide(slide('grace'), 'Verse 1', ruleSet).ruleId), ['g', 'g', 'g']);
+}
+
+function testValidation(): void {
+  check('the built-in rules are valid', validateClassificationRules(DEFAULT_CLASSIFICATION_RULES), []);
+  check('non-objects are rejected', validateClassificationRules(null), ['Rules must be a JSON object']);
+  // The regex engine's own message varies between Node versions
+  const problems = validateClassificationRules({
+    defaultAction: 'maybe',
+    skipLabels: 'Blank',
+    rules: [
+      { id: 'a', action: 'include', priority: 1, match: { text: '(' } },
+      { id: 'a', action: 'drop', priority: '1', match: {} },
+    ],
+  }).map(problem => problem.replace(/ \(.*\)$/, ''));
+  check('every problem is reported', problems, [
+    'defaultAction must be "include" or "exclude"',
+    'skipLabels must be a list of labels',
+    'Rule "a": invalid text pattern',
+    'Rule "a" has a duplicate id',
+    'Rule "a": action must be "include" or "exclude"',
+    'Rule "a": priority must be a number',
+    'Rule "a": match needs at least one condition',
+  ]);
+}
+
+testDefaultRules();
+testSkips();
+testRuleOrder();
+testPatterns();
+testValidation();
+
+finishChecks();
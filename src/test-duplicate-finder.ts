@@ -0,0 +1,83 @@
+/**
+ * Duplicate Finder Test Script
+ * Checks how songs are paired and grouped into clusters of copies.
+ * Run with: npx ts-node src/test-duplicate-finder.ts
+ */
+
+import { findDuplicates, DuplicateInput, DuplicateOptions } from './services/duplicate-finder';
+import { check, finishChecks, lyricsFixture } from './test-helpers';
+
+const HOW_GREAT = [
+  'O Lord my God when I in awesome wonder',
+  'Consider all the worlds Thy hands have made',
+  'I see the stars I hear the rolling thunder',
+  'Thy power throughout the universe displayed',
+  'Then sings my soul my Saviour God to Thee',
+];
+
+function input(library: string, title: string, uuid: string, slides: string[]): DuplicateInput {
+  return { library, lyrics: lyricsFixture(title, uuid, [['Verse 1', slides]]) };
+}
+
+/** Clusters as lists of titles, reference first */
+function titles(inputs: DuplicateInput[], options: DuplicateOptions = {}): string[][] {
+  return findDuplicates(inputs, options).clusters.map(cluster => cluster.songs.map(song => song.title));
+}
+
+function testPairs(): void {
+  console.log('\nPairs');
+  const original = input('Worship', 'Amazing Grace', 'AG-1', ['Amazing grace how sweet the sound', 'That saved a wretch like me']);
+  const copy = input('Hymns', 'Amazing Grace', 'AG-2', ['Amazing Grace, how sweet the sound!\nThat saved a wretch like me']);
+  const report = findDuplicates([copy, original]);
+  check('every song is scanned', report.songsScanned, 2);
+  check('the copy with the most lyric slides is the reference', report.clusters[0]?.songs.map(song => song.uuid), ['AG-1', 'AG-2']);
+  check('the same lines under the same title are identical lyrics with a similar title',
+    report.clusters[0]?.comparisons[0].reasons, ['identical-lyrics', 'similar-title']);
+
+  const reworded = input('Hymns', 'O Lord My God', 'HG-2', [...HOW_GREAT.slice(0, 4), 'Then sings my soul my Saviour God to thee!!', 'How great Thou art']);
+  const similar = findDuplicates([input('Worship', 'How Great Thou Art', 'HG-1', HOW_GREAT), reworded]).clusters[0]?.comparisons[0];
+  check('mostly the same lines are similar lyrics whatever the titles',
+    [similar?.uuid, similar?.reasons, similar?.lyricSimilarity, similar?.linesOnlyInReference, similar?.linesOnlyInThis],
+    ['HG-1', ['similar-lyrics'], 0.91, 1, 0]);
+
+  check('songs sharing a title but not their lyrics stay apart', titles([
+    input('Worship', 'Holy', 'H-1', ['Holy holy holy Lord God Almighty', 'Early in the morning our song shall rise to Thee']),
+    input('Kids', 'Holy', 'H-2', ['Holy is the Lord', 'The whole earth is filled with His glory']),
+  ]), []);
+}
+
+function testCommonLines(): void {
+  console.log('\nCommon lines');
+  // Titles are left out of these checks so only the lyrics pair songs
+  const lyricsOnly = { titleThreshold: 2 };
+  const responses = ['Hallelujah', 'Amen', 'Glory to God', 'Praise the Lord'];
+  const songs = Array.from({ length: 11 }, (_, index) =>
+    input('Worship', `Song ${index + 1}`, `S-${index}`, [...responses, `Verse line of song ${index + 1}`]));
+  check('lines shared by many songs don\'t pair them', titles(songs, lyricsOnly), []);
+  check('the same lines in a few songs do', titles(songs.slice(0, 3), lyricsOnly), [['Song 1', 'Song 2', 'Song 3']]);
+}
+
+function testClusters(): void {
+  console.log('\nClusters');
+  const lines = ['One', 'Two', 'Three', 'Four', 'Five', 'Six', 'Seven'].map(word => `Line ${word.toLowerCase()} of the song`);
+  const a = input('Worship', 'Song A', 'A', [lines.slice(0, 2).join('\n'), lines[2], lines.slice(3, 5).join('\n')]);
+  const b = input('Hymns', 'Song B', 'B', [lines.slice(0, 4).join('\n'), lines[5]]);
+  const c = input('Kids', 'Song C', 'C', [lines.slice(1, 4).join('\n'), lines.slice(5, 7).join('\n')]);
+  const [cluster] = findDuplicates([c, b, a], { titleThreshold: 2 }).clusters;
+  check('copies of copies join one cluster', cluster?.songs.map(song => song.uuid), ['A', 'B', 'C']);
+  check('a copy joined through another has no direct reasons',
+    cluster?.comparisons.map(comparison => [comparison.uuid, comparison.reasons, comparison.lyricSimilarity]),
+    [['B', ['similar-lyrics'], 0.8], ['C', [], 0.6]]);
+  check('clusters are sorted by reference title', titles([
+    input('Worship', 'Zion', 'Z-1', ['Zion hear the song']),
+    input('Worship', 'Zion', 'Z-2', ['Zion hear the song']),
+    input('Worship', 'Abba', 'A-1', ['Abba Father']),
+    input('Worship', 'Abba', 'A-2', ['Abba Father']),
+  ]), [['Abba', 'Abba'], ['Zion', 'Zion']]);
+}
+
+testPairs();
+testCommonLines();
+testClusters();
+
+finishChecks();
@@ -0,0 +1,70 @@
+/**
+ * Test Helpers
+ * Shared by the self-checking test scripts (src/test-*.ts): call check() for
+ * each expectation and finishChecks() at the end, which exits non-zero if any
+ * check failed.
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import type { ExtractedLyrics } from './lyrics-extractor';
+
+let failures = 0;
+let scratchHome: string | null = null;
+
+/**
+ * Compare actual with expected (as JSON) and print the result
+ */
+export function check(name: string, actual: unknown, expected: unknown): void {
+  const ok = JSON.stringify(actual) === JSON.stringify(expected);
+  if (!ok) failures++;
+  console.log(`${ok ? '✅' : '❌'} ${name}`);
+  if (!ok) {
+    console.log(`   expected: ${JSON.stringify(expected)}`);
+    console.log(`   actual:   ${JSON.stringify(actual)}`);
+  }
+}
+
+/**
+ * Point the home directory at an empty scratch directory, so the stores in
+ * ~/.propresenter-words start empty and the real ones are left alone. Stores
+ * read the home directory when they load: import them with await import()
+ * after calling this. finishChecks() removes the directory.
+ */
+export function useScratchHome(): string {
+  scratchHome = fs.mkdtempSync(path.join(os.tmpdir(), 'propresenter-words-test-'));
+  process.env.HOME = scratchHome;
+  process.env.USERPROFILE = scratchHome;
+  return scratchHome;
+}
+
+/**
+ * A song from section names and their slides' text, every slide a lyric slide
+ */
+export function lyricsFixture(title: string, uuid: string, sections: Array<[string, string[]]>): ExtractedLyrics {
+  let index = 0;
+  const lyricSections = sections.map(([name, slides]) => ({
+    name,
+    slides: slides.map(text => ({ index: index++, text, section: name, isLyric: true })),
+  }));
+  return {
+    title,
+    uuid,
+    sections: lyricSections,
+    fullText: sections.flatMap(([, slides]) => slides).join('\n\n'),
+    slideCount: index,
+    lyricSlideCount: index,
+  };
+}
+
+/**
+ * Print the summary and exit with status 1 if any check failed
+ */
+export function finishChecks(): never {
+  if (scratchHome) {
+    fs.rmSync(scratchHome, { recursive: true, force: true });
+  }
+  console.log(failures === 0 ? '\nAll checks passed' : `\n${failures} check(s) failed`);
+  process.exit(failures === 0 ? 0 : 1);
+}
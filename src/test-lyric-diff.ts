@@ -0,0 +1,91 @@
+/**
+ * Lyric Diff Test Script
+ * Checks section and slide changes between two versions of a built-in song.
+ * Run with: npx ts-node src/test-lyric-diff.ts
+ */
+
+import { ExtractedLyrics } from './lyrics-extractor';
+import { diffLyrics, formatLyricDiff } from './services/lyric-diff';
+import { check, finishChecks } from './test-helpers';
+
+/**
+ * A song from section names and their slides' text
+ */
+function song(sections: Array<[string, string[]]>): ExtractedLyrics {
+  let index = 0;
+  const lyricSections = sections.map(([name, slides]) => ({
+    name,
+    slides: slides.map(text => ({ index: index++, text, section: name, isLyric: true })),
+  }));
+  return {
+    title: 'Amazing Grace',
+    uuid: 'A-UUID',
+    sections: lyricSections,
+    fullText: sections.flatMap(([, slides]) => slides).join('\n\n'),
+    slideCount: index,
+    lyricSlideCount: index,
+  };
+}
+
+const VERSE_1 = ['Amazing grace how sweet the sound\nThat saved a wretch like me', 'I once was lost but now am found\nWas blind but now I see'];
+const VERSE_2 = ['Twas grace that taught my heart to fear\nAnd grace my fears relieved', 'How precious did that grace appear\nThe hour I first believed'];
+const CHORUS = ['My chains are gone\nI\'ve been set free', 'My God my Saviour\nHas ransomed me'];
+
+const ORIGINAL = song([['Verse 1', VERSE_1], ['Chorus', CHORUS], ['Verse 2', VERSE_2]]);
+
+function testUnchanged(): void {
+  console.log('\nUnchanged');
+  const respaced = song([
+    ['verse 1', VERSE_1.map(slide => slide.toUpperCase())],
+    ['Chorus', CHORUS.map(slide => slide.replace(/ /g, '  '))],
+    ['Verse 2', VERSE_2],
+  ]);
+  check('case and spacing are ignored', diffLyrics(ORIGINAL, respaced).identical, true);
+}
+
+function testSections(): void {
+  console.log('\nSections');
+  const bridge = ['And like a flood His mercy reigns\nUnending love amazing grace'];
+  const edited = song([['Verse 1', VERSE_1], ['Refrain', CHORUS], ['Bridge', bridge]]);
+  const diff = diffLyrics(ORIGINAL, edited);
+
+  check('section statuses', diff.sections.map(section => [section.name, section.status]), [
+    ['Verse 1', 'unchanged'],
+    ['Refrain', 'renamed'],
+    ['Bridge', 'added'],
+    ['Verse 2', 'removed'],
+  ]);
+  check('renames', diff.renames, [{ from: 'Chorus', to: 'Refrain' }]);
+  check('summary', diff.summary, {
+    slidesAdded: 1, slidesRemoved: 2, slidesChanged: 0, sectionsAdded: 1, sectionsRemoved: 1, sectionsRenamed: 1,
+  });
+  check('a section with different lines is not a rename',
+    diffLyrics(ORIGINAL, song([['Verse 1', VERSE_1], ['Refrain', bridge], ['Verse 2', VERSE_2]])).renames, []);
+}
+
+function testSlides(): void {
+  console.log('\nSlides');
+  const fixed = [VERSE_2[0], 'How precious did that grace appear\nThe hour I first believed!', 'Through many dangers toils and snares'];
+  const diff = diffLyrics(ORIGINAL, song([['Verse 1', VERSE_1], ['Chorus', CHORUS], ['Verse 2', fixed]]));
+  const verse2 = diff.sections.find(section => section.name === 'Verse 2');
+
+  check('a changed and an added slide', verse2?.changes.map(change => [change.type, change.beforeIndex, change.afterIndex]), [
+    ['changed', 2, 2],
+    ['added', undefined, 3],
+  ]);
+  check('text diff hunks',
+    formatLyricDiff(diff).split('\n').filter(line => /^(@@|[-+ ]\S)/.test(line) && !/^(---|\+\+\+) /.test(line)), [
+      '@@ Verse 2, slide 2 changed @@',
+      ' How precious did that grace appear',
+      '-The hour I first believed',
+      '+The hour I first believed!',
+      '@@ Verse 2, slide 3 added @@',
+      '+Through many dangers toils and snares',
+    ]);
+}
+
+testUnchanged();
+testSections();
+testSlides();
+
+finishChecks();
@@ -0,0 +1,100 @@
+/**
+ * Lyric Search Test Script
+ * Indexes two small libraries from a stand-in ProPresenter client, in a
+ * scratch home directory, and checks searching and re-indexing.
+ * Run with: npx ts-node src/test-lyric-search.ts
+ */
+
+import type { LibraryInfo, PresentationInfo, ProPresenterClient } from './propresenter-client';
+import { check, finishChecks, useScratchHome } from './test-helpers';
+
+useScratchHome();
+
+const WORSHIP: LibraryInfo = { uuid: 'LIB-WORSHIP', name: 'Worship' };
+const HYMNS: LibraryInfo = { uuid: 'LIB-HYMNS', name: 'Hymns' };
+
+function presentation(uuid: string, name: string, groups: Array<[string, string[]]>): PresentationInfo {
+  let index = 0;
+  return {
+    uuid,
+    name,
+    hasTimeline: false,
+    destination: 'presentation',
+    groups: groups.map(([groupName, slides]) => ({
+      name: groupName,
+      color: '',
+      slides: slides.map(text => ({ index: index++, text, notes: '', label: '', enabled: true })),
+    })),
+  };
+}
+
+const SONGS: Record<string, PresentationInfo[]> = {
+  [WORSHIP.uuid]: [
+    presentation('AG', 'Amazing Grace', [
+      ['Verse 1', ['Amazing grace how sweet the sound\nThat saved a wretch like me']],
+      ['Chorus', ['My chains are gone\nI\'ve been set free']],
+    ]),
+    presentation('TTR', '10,000 Reasons', [
+      ['Chorus', ['Bless the Lord, O my soul\nO my soul']],
+      ['Verse 1', ['The sun comes up it\'s a new day dawning']],
+    ]),
+    presentation('BROKEN', 'Unreadable', []),
+  ],
+  [HYMNS.uuid]: [
+    presentation('HGTA', 'How Great Thou Art', [
+      ['Verse 1', ['O Lord my God when I in awesome wonder\nConsider all the worlds Thy hands have made']],
+      ['Chorus', ['Then sings my soul my Saviour God to Thee\nHow great Thou art']],
+    ]),
+  ],
+};
+
+// Stands in for ProPresenter: only what the library crawl uses
+const client = {
+  getLibraries: async () => [WORSHIP, HYMNS],
+  getLibraryPresentations: async (libraryId: string) => SONGS[libraryId].map(({ uuid, name }) => ({ uuid, name })),
+  getPresentationByUuid: async (uuid: string) => {
+    if (uuid === 'BROKEN') throw new Error('Presentation could not be read');
+    return Object.values(SONGS).flat().find(song => song.uuid === uuid) ?? null;
+  },
+} as unknown as ProPresenterClient;
+
+async function main(): Promise<void> {
+  const search = await import('./services/lyric-search');
+
+  console.log('\nIndexing');
+  const errors: string[] = [];
+  const indexed = await search.buildLyricIndex(client, {
+    onProgress: event => { if (event.type === 'error') errors.push(event.title); },
+  });
+  check('libraries are indexed with their song counts', indexed.map(library => [library.name, library.songs]), [['Worship', 2], ['Hymns', 1]]);
+  check('unreadable presentations are reported and skipped', errors, ['Unreadable']);
+  check('indexed libraries are listed', search.listIndexedLibraries().map(library => library.id), [WORSHIP.uuid, HYMNS.uuid]);
+  check('libraries never indexed are found', search.unindexedLibraries([HYMNS.uuid, 'LIB-KIDS', '']), ['LIB-KIDS']);
+
+  console.log('\nSearching');
+  const top = (query: string, options: { libraryIds?: string[] } = {}) => search.searchLyrics(query, options)
+    .map(result => [result.title, result.section, result.snippet, result.score]);
+  check('a remembered line finds its song, with an exact phrase bonus',
+    top('sweet the sound'), [['Amazing Grace', 'Verse 1', 'Amazing grace how sweet the sound', 1.5]]);
+  check('case, punctuation and apostrophes are ignored',
+    top('BLESS THE LORD O MY SOUL!')[0], ['10,000 Reasons', 'Chorus', 'Bless the Lord, O my soul', 1.5]);
+  check('the best line is the snippet', top('its a new day')[0]?.[2], 'The sun comes up it\'s a new day dawning');
+  check('songs need every word of the query', top('grace soul'), []);
+  check('equal scores are sorted by title',
+    top('my soul').map(result => [result[0], result[3]]), [['10,000 Reasons', 1.5], ['How Great Thou Art', 1.5]]);
+  check('a title-only match has no snippet', top('reasons'), [['10,000 Reasons', '', '', 0]]);
+  check('results can be limited to libraries', top('soul', { libraryIds: [HYMNS.uuid] }).map(result => result[0]), ['How Great Thou Art']);
+  check('an empty query finds nothing', top(' ?! '), []);
+  check('words in two songs make the vocabulary', [...search.indexedVocabulary(2)].sort(), ['a', 'how', 'lord', 'my', 'o', 'soul', 'the']);
+
+  console.log('\nRe-indexing');
+  SONGS[WORSHIP.uuid] = SONGS[WORSHIP.uuid].filter(song => song.uuid !== 'TTR');
+  await search.buildLyricIndex(client, { libraries: ['worship'] });
+  check('songs removed from a re-indexed library leave the index', top('bless the lord'), []);
+  check('libraries not crawled keep their songs', top('how great').map(result => result[0]), ['How Great Thou Art']);
+}
+
+main().then(finishChecks, error => {
+  console.error('Error testing lyric search:', error);
+  process.exit(1);
+});
@@ -0,0 +1,85 @@
+/**
+ * Offline Import Test Script
+ * Checks the RTF decoder and .proplaylist reader against small built-in fixtures.
+ * Run with: npx ts-node src/test-offline-import.ts
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { rtfToPlainText } from './utils/rtf';
+import { createZip } from './utils/zip-writer';
+import { readProPlaylist } from './services/pro-file-reader';
+import { check, finishChecks } from './test-helpers';
+
+// Minimal protobuf writer for length-delimited fields
+function varint(value: number): Buffer {
+  const bytes: number[] = [];
+  do {
+    let byte = value & 0x7f;
+    value >>>= 7;
+    if (value > 0) byte |= 0x80;
+    bytes.push(byte);
+  } while (value > 0);
+  return Buffer.from(bytes);
+}
+
+function field(number: number, ...parts: Array<Buffer | string>): Buffer {
+  const bytes = Buffer.concat(parts.map(part => Buffer.isBuffer(part) ? part : Buffer.from(part, 'utf-8')));
+  return Buffer.concat([varint((number << 3) | 2), varint(bytes.length), bytes]);
+}
+
+// rv.data.Presentation with just a UUID and a name
+function presentation(uuid: string, name: string): Buffer {
+  return Buffer.concat([field(2, field(1, uuid)), field(3, name)]);
+}
+
+// PlaylistItem.presentation.document_path.absolute_string
+function presentationItem(url: string): Buffer {
+  return field(1, field(5, field(1, field(1, url))));
+}
+
+function testCp1252(): void {
+  console.log('\nRTF decoding');
+  const rtf = '{\\rtf1\\ansi\\ansicpg1252\\cocoartf2639 {\\fonttbl\\f0\\fswiss Helvetica;}'
+    + '\\f0\\fs96 \\\'93Amazing grace\\\'94 \\\'96 how sweet\\par It\\\'92s caf\\\'e9\\\'85}';
+  check('curly quotes, dashes and ellipsis use cp1252', rtfToPlainText(rtf), '“Amazing grace” – how sweet\nIt’s café…');
+  check('\\u escapes skip their fallback byte', rtfToPlainText('{\\rtf1\\uc1 \\u8217\\\'92s}'), '’s');
+}
+
+function testPlaylistOrder(): void {
+  console.log('\n.proplaylist order');
+  // "Grace.pro" also appears inside the path of "Amazing Grace.pro"
+  const library = 'file:///Users/worship/Documents/ProPresenter/Libraries/Default/';
+  const document = field(3, field(12, field(1,
+    field(2, 'Sunday Morning'),
+    field(13,
+      presentationItem(`${library}Amazing%20Grace.pro`),
+      presentationItem(`${library}Holy.pro`),
+      presentationItem(`${library}Grace.pro`)
+    )
+  )));
+  const bundle = createZip([
+    { name: 'Grace.pro', data: presentation('G-UUID', 'Grace') },
+    { name: 'Unused.pro', data: presentation('U-UUID', 'Unused') },
+    { name: 'Holy.pro', data: presentation('H-UUID', 'Holy') },
+    { name: 'Amazing Grace.pro', data: presentation('A-UUID', 'Amazing Grace') },
+    { name: 'data', data: document },
+  ]);
+
+  const filePath = path.join(os.tmpdir(), `test-offline-import-${process.pid}.proplaylist`);
+  fs.writeFileSync(filePath, bundle);
+  try {
+    const playlist = readProPlaylist(filePath);
+    check('playlist name comes from the document', playlist.name, 'Sunday Morning');
+    check('items follow the playlist, unlisted files last',
+      playlist.presentations.map(p => p.name), ['Amazing Grace', 'Holy', 'Grace', 'Unused']);
+  } finally {
+    fs.unlinkSync(filePath);
+  }
+}
+
+testCp1252();
+testPlaylistOrder();
+
+finishChecks();
@@ -0,0 +1,58 @@
+/**
+ * Service Grammar Test Script
+ * Parses a built-in service order with the default grammar and checks every item.
+ * Run with: npx ts-node src/test-service-grammar.ts
+ */
+
+import { PDFParser } from './services/pdf-parser';
+import { DEFAULT_SERVICE_GRAMMAR, normalizeServiceGrammar } from './services/service-grammar';
+import { check, finishChecks } from './test-helpers';
+
+const SERVICE_ORDER = `SUNDAY MORNING, 1st February 2026 - 11am
+10:30am Prayer meeting in Room 1
+WELCOME AND NOTICES
+CALL TO WORSHIP (Mark)
+PRAISE: MP 59 'Holy, holy, holy, Lord God Almighty' (Praise Team)
+PRAISE: Great are You Lord / 10,000 Reasons (Praise Team)
+VIDEO: Kids Video - God's Big Story
+[Children leave for Kids Church]
+BIBLE READING: Luke 2:21-40 (Peter J)
+PRAYERS FOR OTHERS
+PRAISE: 'He will keep you (Psalm 121)' (Praise Team)
+SERMON: The Light of the World
+PRAYERFUL REFLECTION AND RESPONSE
+PRAISE 'In Christ Alone' (Video)
+BLESSING`;
+
+function testDefaultGrammar(): void {
+  console.log('\nDefault grammar');
+  const parsed = new PDFParser().parseText(SERVICE_ORDER);
+
+  check('date comes from the first line', parsed.date, '1st February 2026');
+  check('an ordinary Sunday is no special service', parsed.specialServiceType, null);
+  check('items, slots and leaders', parsed.sections.map(section => [
+    section.type, section.title, section.praiseSlot ?? '', section.leader ?? '',
+  ]), [
+    ['song', 'Holy, holy, holy, Lord God Almighty', 'praise1', 'Praise Team'],
+    ['song', 'Great are You Lord', 'praise1', 'Praise Team'],
+    ['song', '10,000 Reasons', 'praise1', 'Praise Team'],
+    ['video', "Kids Video - God's Big Story", 'kids', ''],
+    ['bible', 'Luke 2:21-40', '', 'Peter J'],
+    ['song', 'He will keep you (Psalm 121)', 'praise2', 'Praise Team'],
+    ['video', 'In Christ Alone', 'praise3', ''],
+  ]);
+  check('bracketed lines are ignored, not item notes', parsed.sections.some(section => section.notes), false);
+  check('the default grammar has no notes line', DEFAULT_SERVICE_GRAMMAR.metadata.notesLine, undefined);
+}
+
+function testNotesLine(): void {
+  console.log('\nNotes lines');
+  const grammar = normalizeServiceGrammar({ id: 'with-notes', ignore: [], metadata: { notesLine: '^\\[(.*)\\]$' } });
+  const video = new PDFParser(grammar).parseText(SERVICE_ORDER).sections.find(section => section.isKidsVideo);
+  check('a grammar with notesLine attaches the line to the item before', video?.notes, 'Children leave for Kids Church');
+}
+
+testDefaultGrammar();
+testNotesLine();
+
+finishChecks();
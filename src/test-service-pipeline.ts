@@ -0,0 +1,130 @@
+/**
+ * Service Pipeline Test Script
+ * Checks how chosen presentations are put under the headers of a template
+ * playlist, and how Bible readings are matched to verse presentations.
+ * Run with: npx ts-node src/test-service-pipeline.ts
+ */
+
+import {
+  insertServiceItems,
+  matchVerseReferences,
+  selectedServiceItems,
+  ServicePlaylistItem,
+} from './services/service-pipeline';
+import { check, finishChecks } from './test-helpers';
+
+// Template playlist items as GET /v1/playlist/{id} returns them
+function header(name: string): any {
+  return { id: { name, uuid: `H-${name}`, index: 0 }, type: 'header', header_color: { red: 1, green: 0, blue: 0, alpha: 1 }, extra: 'dropped' };
+}
+
+function placeholder(name: string): any {
+  return {
+    id: { name, uuid: '', index: 0 },
+    type: 'presentation',
+    target_uuid: `T-${name}`,
+    presentation_info: { presentation_uuid: `P-${name}`, arrangement_name: 'Default', arrangement_uuid: 'ARR' },
+    duration: 30,
+  };
+}
+
+const TEMPLATE = [
+  header('Welcome'),
+  placeholder('Notices'),
+  header('Praise 1'),
+  placeholder('Song placeholder'),
+  header('Reading'),
+  placeholder('Reading placeholder'),
+  header('Praise 2'),
+  placeholder('Kept song'),
+  header('Kids Talk'),
+  header('Benediction'),
+];
+
+function song(name: string, praiseSlot?: string): ServicePlaylistItem {
+  return { type: 'presentation', uuid: `U-${name}`, name, praiseSlot };
+}
+
+function testInsert(): void {
+  console.log('\nInserting into the template');
+  const result = insertServiceItems(TEMPLATE, [
+    song('Amazing Grace', 'praise1'),
+    song('Cornerstone'),
+    song('Luke 12_35-59 (NIV)', 'reading'),
+    song('Jesus Loves Me', 'kids'),
+    song('Offering', 'offering'),
+  ]);
+  check('items replace a slot\'s placeholders; slots with nothing new keep theirs; unknown slots are dropped',
+    result.map(item => item.id.name), [
+      'Welcome', 'Notices',
+      'Praise 1', 'Amazing Grace', 'Cornerstone',
+      'Reading', 'Luke 12_35-59 (NIV)',
+      'Praise 2', 'Kept song',
+      'Kids Talk', 'Jesus Loves Me',
+      'Benediction',
+    ]);
+  check('items are renumbered', result.map(item => item.id.index), result.map((_, index) => index));
+  check('inserted presentations point at the presentation', result[3], {
+    id: { name: 'Amazing Grace', index: 3, uuid: 'U-Amazing Grace' },
+    type: 'presentation',
+    is_hidden: false,
+    is_pco: false,
+    target_uuid: 'U-Amazing Grace',
+    presentation_info: { presentation_uuid: 'U-Amazing Grace', arrangement_name: '', arrangement_uuid: '' },
+    destination: 'presentation',
+  });
+  check('kept presentations fall back to the presentation uuid and keep their arrangement and duration', result[8], {
+    id: { name: 'Kept song', index: 8, uuid: 'P-Kept song' },
+    type: 'presentation',
+    is_hidden: false,
+    is_pco: false,
+    target_uuid: 'T-Kept song',
+    presentation_info: { presentation_uuid: 'P-Kept song', arrangement_name: 'Default', arrangement_uuid: 'ARR' },
+    duration: 30,
+  });
+  check('headers keep their colour and lose fields ProPresenter rejects', result[0], {
+    id: { name: 'Welcome', index: 0, uuid: 'H-Welcome' },
+    type: 'header',
+    is_hidden: false,
+    is_pco: false,
+    target_uuid: '',
+    header_color: { red: 1, green: 0, blue: 0, alpha: 1 },
+  });
+  check('header names match ignoring case and spaces',
+    insertServiceItems([header('  PRAISE 3 '), placeholder('Old')], [song('New', 'praise3')]).map(item => item.id.name), ['  PRAISE 3 ', 'New']);
+}
+
+function testSelected(): void {
+  console.log('\nSelected matches');
+  const items = selectedServiceItems([
+    { songName: 'Amazing Grace', praiseSlot: 'praise1', isKidsVideo: false, matches: [], requiresReview: false, selectedMatch: { uuid: 'AG', name: 'Amazing Grace' } },
+    { songName: 'Unknown Song', praiseSlot: 'praise2', isKidsVideo: false, matches: [], requiresReview: true },
+  ], [
+    { reference: 'John 3:16', matches: [], requiresReview: false, selectedMatch: { uuid: 'J', name: 'John 3_16 (NIV)' } },
+  ]);
+  check('only selected songs and readings are inserted, readings under Reading',
+    items.map(item => [item.name, item.praiseSlot]), [['Amazing Grace', 'praise1'], ['John 3_16 (NIV)', 'reading']]);
+}
+
+function testVerses(): void {
+  console.log('\nBible readings');
+  const presentations = [
+    { uuid: '1', name: 'Luke 12_35-59 (NIV)-1' },
+    { uuid: '2', name: 'Luke 12 Sermon Notes' },
+    { uuid: '3', name: 'Luke 14_1-6 (NIV)' },
+    { uuid: '4', name: 'Psalm 23 (ESV)' },
+  ];
+  const [luke, psalm, missing] = matchVerseReferences(['Luke 12:35-59', 'Psalm 23', 'Acts 2:1-4'], presentations);
+  check('a reference matches its verse presentation, punctuation aside',
+    [luke.bestMatch?.uuid, luke.bestMatch?.confidence, luke.requiresReview], ['1', 85, false]);
+  check('presentations without a translation are left out when there are translated ones',
+    luke.matches.map(match => match.uuid), ['1']);
+  check('a name containing the reference is 85%', psalm.matches.map(match => [match.uuid, match.confidence]), [['4', 85]]);
+  check('references with no presentation need review', [missing.matches, missing.requiresReview, missing.selectedMatch], [[], true, undefined]);
+}
+
+testInsert();
+testSelected();
+testVerses();
+
+finishChecks();
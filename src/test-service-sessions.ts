@@ -0,0 +1,76 @@
+/**
+ * Service Session Store Test Script
+ * Checks saving, merging, listing and discarding Service Generator sessions,
+ * in a scratch home directory.
+ * Run with: npx ts-node src/test-service-sessions.ts
+ */
+
+import { check, finishChecks, useScratchHome } from './test-helpers';
+
+useScratchHome();
+
+const later = () => new Promise(resolve => setTimeout(resolve, 5));
+
+async function main(): Promise<void> {
+  const store = await import('./services/service-session-store');
+
+  console.log('\nValidation');
+  check('a session needs a name and playlist', store.validateServiceSession({ name: ' ', playlistId: '' }),
+    ['name is required', 'playlistId is required']);
+  check('steps, lists and build status are checked', store.validateServiceSession({
+    id: 7, name: 'Sunday', playlistId: 'PL', step: 'done', items: {}, verses: 'none', build: { status: 'maybe' },
+  }), [
+    'id must be a string',
+    'step must be one of upload, parse, match, verse, build',
+    'items must be a list',
+    'verses must be a list',
+    'build.status must be one of pending, built, failed',
+  ]);
+  check('non-objects are rejected', store.validateServiceSession([]), ['session must be an object']);
+  check('a minimal session is valid', store.validateServiceSession({ name: 'Sunday', playlistId: 'PL' }), []);
+
+  console.log('\nSaving');
+  const created = store.saveServiceSession({ name: 'St Andrews - Feb 10th', playlistId: 'PL-1', sourceFile: 'feb10.pdf' });
+  check('new sessions start on upload with empty lists and a pending build',
+    [created.step, created.items, created.songMatches, created.build, created.createdAt === created.updatedAt],
+    ['upload', [], [], { status: 'pending' }, true]);
+
+  await later();
+  const updated = store.saveServiceSession({
+    id: created.id,
+    name: created.name,
+    playlistId: created.playlistId,
+    step: 'match',
+    items: [{ type: 'song', text: 'Amazing Grace', praiseSlot: 'praise1' }],
+  });
+  check('updates merge into the stored session', [updated.step, updated.items.length, updated.sourceFile], ['match', 1, 'feb10.pdf']);
+  check('updates move updatedAt only', [updated.createdAt, updated.updatedAt > created.updatedAt], [created.createdAt, true]);
+
+  const tampered = store.saveServiceSession({
+    ...updated,
+    createdAt: '2000-01-01T00:00:00.000Z',
+    admin: true,
+  } as any);
+  check('clients can\'t set the timestamps or other fields', [tampered.createdAt, 'admin' in tampered], [created.createdAt, false]);
+
+  console.log('\nListing');
+  await later();
+  const second = store.saveServiceSession({ name: 'Evening', playlistId: 'PL-2' });
+  check('sessions are listed most recently updated first', store.loadServiceSessions().map(session => session.id), [second.id, created.id]);
+  check('a session can be read back', store.getServiceSession(created.id)?.step, 'match');
+
+  console.log('\nDiscarding');
+  check('discarding removes the session once', [store.deleteServiceSession(second.id), store.deleteServiceSession(second.id)], [true, false]);
+  let status: number | null = null;
+  try {
+    store.saveServiceSession({ id: second.id, name: 'Evening', playlistId: 'PL-2', step: 'parse' });
+  } catch (error: any) {
+    status = error.status;
+  }
+  check('a discarded session is not recreated by a late save', [status, store.loadServiceSessions().length], [404, 1]);
+}
+
+main().then(finishChecks, error => {
+  console.error('Error testing service sessions:', error);
+  process.exit(1);
+});
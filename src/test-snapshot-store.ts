@@ -0,0 +1,76 @@
+/**
+ * Snapshot Store Test Script
+ * Checks lyric hashing, recording versions and loading them back for restore,
+ * in a scratch home directory.
+ * Run with: npx ts-node src/test-snapshot-store.ts
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { check, finishChecks, lyricsFixture, useScratchHome } from './test-helpers';
+
+useScratchHome();
+
+const VERSE = ['Amazing grace how sweet the sound\nThat saved a wretch like me'];
+const CHORUS = ['My chains are gone\nI\'ve been set free'];
+
+/** HTTP status of the error fn throws (its message if it has none), or null if it doesn't throw */
+function errorStatus(fn: () => unknown): number | string | null {
+  try {
+    fn();
+    return null;
+  } catch (error: any) {
+    return error.status ?? error.message;
+  }
+}
+
+async function main(): Promise<void> {
+  const store = await import('./services/snapshot-store');
+  const original = lyricsFixture('Amazing Grace', 'AG-UUID', [['Verse 1', VERSE], ['Chorus', CHORUS]]);
+
+  console.log('\nHashing');
+  const hash = store.hashLyrics(original);
+  const reformatted = lyricsFixture('Amazing Grace (2024)', 'OTHER-UUID', [
+    [' Verse 1 ', VERSE.map(slide => slide.replace(/\n/g, '  \r\n'))],
+    ['Chorus', CHORUS],
+  ]);
+  reformatted.sections[1].slides.push({ index: 9, text: 'CCLI 12345', section: 'Chorus', isLyric: false, label: 'Copyright' });
+  check('line endings, trailing spaces, titles and non-lyric slides don\'t change the hash', store.hashLyrics(reformatted), hash);
+  check('renaming a section changes the hash',
+    store.hashLyrics(lyricsFixture('Amazing Grace', 'AG-UUID', [['Verse', VERSE], ['Chorus', CHORUS]])) === hash, false);
+
+  console.log('\nRecording');
+  const edited = lyricsFixture('Amazing Grace', 'AG-UUID', [['Verse 1', ['Amazing grace how sweet the sound\nThat saved a soul like me']], ['Chorus', CHORUS]]);
+  check('outcomes of recording a song, again, then edited',
+    [store.recordSnapshot(original, 'Worship'), store.recordSnapshot(original, 'Worship'), store.recordSnapshot(edited, 'Hymns')],
+    ['added', 'unchanged', 'changed']);
+  const [song] = store.listSnapshotSongs();
+  check('the song keeps both versions, oldest first',
+    song.versions.map(version => version.hash), [hash, store.hashLyrics(edited)]);
+  check('the library follows the latest recording', song.library, 'Hymns');
+  check('each version is stored once, by hash',
+    fs.readdirSync(path.join(store.getSnapshotDir(), 'objects')).sort(), [`${hash}.json`, `${store.hashLyrics(edited)}.json`].sort());
+
+  console.log('\nLoading versions');
+  const text = (query: string, version?: string) => store.loadSnapshotVersion(query, version).lyrics.sections[0].slides[0].text;
+  check('the latest version by default', text('Amazing Grace'), edited.sections[0].slides[0].text);
+  check('v1, -1, previous and a hash prefix name the first version',
+    ['v1', '-1', 'previous', hash.slice(0, 7)].map(version => text('AG-UUID', version)), Array(4).fill(VERSE[0]));
+  check('the version number is reported', store.loadSnapshotVersion('amazing', 'latest').number, 2);
+
+  store.recordSnapshot({ ...original, uuid: 'COPY-UUID', title: 'Amazing Grace (Copy)' }, 'Worship');
+  const copy = store.loadSnapshotVersion('COPY-UUID');
+  check('songs sharing lyrics keep their own uuid and title', [copy.lyrics.uuid, copy.lyrics.title], ['COPY-UUID', 'Amazing Grace (Copy)']);
+
+  console.log('\nErrors');
+  check('an unknown song is 404', errorStatus(() => store.loadSnapshotVersion('How Great Thou Art')), 404);
+  check('a title matching several songs is 400', errorStatus(() => store.loadSnapshotVersion('grace')), 400);
+  check('a version that isn\'t stored is 404', errorStatus(() => store.loadSnapshotVersion('AG-UUID', 'v9')), 404);
+  check('something that isn\'t a version is 400', errorStatus(() => store.loadSnapshotVersion('AG-UUID', 'yesterday')), 400);
+  check('a hash prefix shorter than 7 characters is 400', errorStatus(() => store.loadSnapshotVersion('AG-UUID', hash.slice(0, 4))), 400);
+}
+
+main().then(finishChecks, error => {
+  console.error('Error testing snapshot store:', error);
+  process.exit(1);
+});
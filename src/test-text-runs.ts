@@ -0,0 +1,99 @@
+/**
+ * Text Runs Test Script
+ * Checks that slide formatting survives RTF parsing, slide splitting and reflow.
+ * Run with: npx ts-node src/test-text-runs.ts
+ */
+
+import { ExtractedLyrics } from './lyrics-extractor';
+import { reflowLyrics } from './services/lyric-reflow';
+import { rtfBlobsToRuns, rtfToPlainText, rtfToRuns } from './utils/rtf';
+import { restyler, runsForText, runsToText } from './utils/text-runs';
+import { check, finishChecks } from './test-helpers';
+
+const HEADER = '{\\rtf1\\ansi\\ansicpg1252\\cocoartf2639 {\\fonttbl\\f0\\fswiss Helvetica;}'
+  + '{\\colortbl;\\red255\\green255\\blue255;\\red255\\green0\\blue0;}\\f0\\fs96 \\cf1 ';
+
+// A leader line in bold and a congregation response in italics
+const CALL_AND_RESPONSE = `${HEADER}\\b Leader:\\b0  The Lord be with you\\par \\i And also with you\\i0\\par Lift up your hearts}`;
+
+function testRtfRuns(): void {
+  console.log('\nRTF runs');
+  const runs = rtfToRuns(CALL_AND_RESPONSE);
+  check('runs join to the plain text', runsToText(runs), rtfToPlainText(CALL_AND_RESPONSE));
+  check('only the exceptions to the base style are marked', runs, [
+    { text: 'Leader:', bold: true },
+    { text: ' The Lord be with you\n' },
+    { text: 'And also with you', italic: true },
+    { text: '\nLift up your hearts' },
+  ]);
+  check('a coloured word keeps its colour',
+    rtfToRuns(`${HEADER}Sing \\cf2 Hallelujah\\cf1  to the King}`).find(run => run.color),
+    { text: 'Hallelujah', color: 'FF0000' });
+  check('plain slides have no runs',
+    rtfBlobsToRuns([`${HEADER}Amazing grace}`, `${HEADER}How sweet the sound}`]), undefined);
+  check('text elements are joined line by line',
+    runsToText(rtfBlobsToRuns([`${HEADER}Amazing \\i grace\\i0}`, `${HEADER}How sweet the sound}`]) ?? []),
+    'Amazing grace\nHow sweet the sound');
+}
+
+function testRunsForText(): void {
+  console.log('\nRuns for part of a slide');
+  const runs = rtfToRuns(CALL_AND_RESPONSE);
+  check('the second chunk of a split slide keeps its formatting',
+    runsForText(runs, 'And also with you\nLift up your hearts'),
+    [[{ text: 'And also with you', italic: true }], [{ text: 'Lift up your hearts' }]]);
+  check('edited text no longer lines up', runsForText(runs, 'And also with thee'), null);
+}
+
+function testRestyler(): void {
+  console.log('\nRestyling reflowed text');
+  const runs = rtfToRuns(CALL_AND_RESPONSE);
+  const restyle = restyler(runs);
+  // Spaces take the style of the word before them
+  check('styles follow the words across new line breaks',
+    restyle('Leader: The Lord be with you And'),
+    [{ text: 'Leader: ', bold: true }, { text: 'The Lord be with you ' }, { text: 'And', italic: true }]);
+  check('the next chunk carries on where the last stopped',
+    restyle('also with you Lift up your hearts'),
+    [{ text: 'also with you ', italic: true }, { text: 'Lift up your hearts' }]);
+  check('different words stop the restyling', restyler(runs)('The Lord'), undefined);
+}
+
+function testBilingualReflow(): void {
+  console.log('\nReflowing a bilingual slide');
+  const elements = ['Amazing grace how sweet the sound\nThat saved a wretch like me', 'Gracia admirable\nQue a mi pecador salvo'];
+  const runs = [
+    { text: 'Amazing ' },
+    { text: 'grace', bold: true },
+    { text: ' how sweet the sound\nThat saved a wretch like me\n' },
+    { text: 'Gracia admirable\nQue a mi pecador salvo', italic: true },
+  ];
+  const song: ExtractedLyrics = {
+    title: 'Amazing Grace',
+    uuid: 'A-UUID',
+    sections: [{ name: 'Verse 1', slides: [{ index: 0, text: elements.join('\n'), section: 'Verse 1', isLyric: true, elements, runs }] }],
+    fullText: elements.join('\n'),
+    slideCount: 1,
+    lyricSlideCount: 1,
+  };
+  const [first, second] = reflowLyrics([song], { mode: 'split', width: 40, maxLines: 1 })[0].sections[0].slides;
+  check('each language keeps its own formatting when split', first.runs, [
+    { text: 'Amazing ' },
+    { text: 'grace ', bold: true },
+    { text: 'how sweet the sound' },
+    { text: '\n' },
+    { text: 'Gracia admirable', italic: true },
+  ]);
+  check('the next slide carries on in both languages', second.runs, [
+    { text: 'That saved a wretch like me' },
+    { text: '\n' },
+    { text: 'Que a mi pecador salvo', italic: true },
+  ]);
+}
+
+testRtfRuns();
+testRunsForText();
+testRestyler();
+testBilingualReflow();
+
+finishChecks();
@@ -20,6 +20,18 @@ export interface ServiceSection {
   praiseSlot?: PraiseSlot;  // Which praise section: praise1, praise2, praise3, or kids
 }
 
+/**
+ * A line of a service order, with the typographic cues a layout-aware PDF read
+ * keeps (text files and the plain PDF text have none)
+ */
+export interface ServiceOrderLine {
+  text: string;
+  /** Most of the line is set in a bold font */
+  bold?: boolean;
+  /** Set noticeably larger than the body text */
+  heading?: boolean;
+}
+
 export interface ParsedService {
   date: string;         // "1st February 2026"
   rawDate?: string;     // Full date header from PDF
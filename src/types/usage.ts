@@ -0,0 +1,44 @@
+/**
+ * Type definitions for song usage tracking (CCLI copyright reporting)
+ */
+
+export type UsageSource = 'export' | 'service-generator';
+
+export interface UsageSong {
+  title: string;
+  uuid: string;
+  ccliNumber?: string;
+}
+
+/**
+ * One exported or generated playlist
+ */
+export interface UsageEntry {
+  id: string;
+  /** Date the songs were used, YYYY-MM-DD */
+  date: string;
+  recordedAt: string;
+  source: UsageSource;
+  playlistId?: string;
+  playlistName: string;
+  songs: UsageSong[];
+}
+
+/**
+ * Usage of a single song across a reporting period
+ */
+export interface UsageReportRow {
+  title: string;
+  ccliNumber?: string;
+  uuid: string;
+  timesUsed: number;
+  dates: string[];
+}
+
+export interface UsageReport {
+  from?: string;
+  to?: string;
+  generatedAt: string;
+  totalPlaylists: number;
+  songs: UsageReportRow[];
+}
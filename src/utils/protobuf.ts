@@ -0,0 +1,150 @@
+/**
+ * Minimal Protocol Buffers wire-format decoder
+ *
+ * ProPresenter 7 stores presentations and playlists as protobuf messages.
+ * We don't ship the .proto schemas, so this decodes the raw wire format into
+ * a list of numbered fields that callers can walk by field number.
+ */
+
+export interface ProtoField {
+  number: number;
+  wireType: number;
+  /** Varints are numbers, fixed32/fixed64 and length-delimited values are raw bytes */
+  value: number | Buffer;
+}
+
+const WIRE_VARINT = 0;
+const WIRE_FIXED64 = 1;
+const WIRE_LENGTH_DELIMITED = 2;
+const WIRE_FIXED32 = 5;
+
+function readVarint(buf: Buffer, offset: number): { value: number; next: number } {
+  let value = 0;
+  let multiplier = 1;
+  let pos = offset;
+
+  while (pos < buf.length) {
+    const byte = buf[pos++];
+    value += (byte & 0x7f) * multiplier;
+    if ((byte & 0x80) === 0) {
+      return { value, next: pos };
+    }
+    multiplier *= 128;
+    if (pos - offset > 10) break;
+  }
+
+  throw new Error('Malformed varint');
+}
+
+/**
+ * Decode a buffer into its top-level fields. Throws if the bytes are not a
+ * well-formed message.
+ */
+export function decodeMessage(buf: Buffer): ProtoField[] {
+  const fields: ProtoField[] = [];
+  let pos = 0;
+
+  while (pos < buf.length) {
+    const key = readVarint(buf, pos);
+    pos = key.next;
+    const number = Math.floor(key.value / 8);
+    const wireType = key.value & 0x07;
+
+    if (number === 0) {
+      throw new Error('Invalid field number 0');
+    }
+
+    switch (wireType) {
+      case WIRE_VARINT: {
+        const v = readVarint(buf, pos);
+        fields.push({ number, wireType, value: v.value });
+        pos = v.next;
+        break;
+      }
+      case WIRE_FIXED64:
+        if (pos + 8 > buf.length) throw new Error('Truncated fixed64');
+        fields.push({ number, wireType, value: buf.subarray(pos, pos + 8) });
+        pos += 8;
+        break;
+      case WIRE_LENGTH_DELIMITED: {
+        const len = readVarint(buf, pos);
+        pos = len.next;
+        if (pos + len.value > buf.length) throw new Error('Truncated length-delimited field');
+        fields.push({ number, wireType, value: buf.subarray(pos, pos + len.value) });
+        pos += len.value;
+        break;
+      }
+      case WIRE_FIXED32:
+        if (pos + 4 > buf.length) throw new Error('Truncated fixed32');
+        fields.push({ number, wireType, value: buf.subarray(pos, pos + 4) });
+        pos += 4;
+        break;
+      default:
+        throw new Error(`Unsupported wire type ${wireType}`);
+    }
+  }
+
+  return fields;
+}
+
+/**
+ * Decode a buffer as a message, returning null instead of throwing
+ */
+export function tryDecodeMessage(buf: Buffer): ProtoField[] | null {
+  try {
+    return decodeMessage(buf);
+  } catch {
+    return null;
+  }
+}
+
+export function getFields(fields: ProtoField[], number: number): ProtoField[] {
+  return fields.filter(f => f.number === number);
+}
+
+export function getBytes(fields: ProtoField[], number: number): Buffer | undefined {
+  const field = fields.find(f => f.number === number && f.wireType === WIRE_LENGTH_DELIMITED);
+  return field ? (field.value as Buffer) : undefined;
+}
+
+export function getString(fields: ProtoField[], number: number): string | undefined {
+  const bytes = getBytes(fields, number);
+  return bytes ? bytes.toString('utf-8') : undefined;
+}
+
+export function getVarint(fields: ProtoField[], number: number): number | undefined {
+  const field = fields.find(f => f.number === number && f.wireType === WIRE_VARINT);
+  return field ? (field.value as number) : undefined;
+}
+
+export function getFloat(fields: ProtoField[], number: number): number | undefined {
+  const field = fields.find(f => f.number === number);
+  if (!field || !Buffer.isBuffer(field.value)) return undefined;
+  if (field.wireType === WIRE_FIXED32) return field.value.readFloatLE(0);
+  if (field.wireType === WIRE_FIXED64) return field.value.readDoubleLE(0);
+  return undefined;
+}
+
+export function getMessage(fields: ProtoField[], number: number): ProtoField[] | undefined {
+  const bytes = getBytes(fields, number);
+  return bytes ? tryDecodeMessage(bytes) ?? undefined : undefined;
+}
+
+export function getMessages(fields: ProtoField[], number: number): ProtoField[][] {
+  return getFields(fields, number)
+    .filter(f => f.wireType === WIRE_LENGTH_DELIMITED)
+    .map(f => tryDecodeMessage(f.value as Buffer))
+    .filter((m): m is ProtoField[] => m !== null);
+}
+
+/**
+ * Follow a path of nested message field numbers, e.g. [23, 2, 1]
+ */
+export function getPath(fields: ProtoField[], path: number[]): ProtoField[] | undefined {
+  let current: ProtoField[] | undefined = fields;
+  for (const number of path) {
+    if (!current) return undefined;
+    current = getMessage(current, number);
+  }
+  return current;
+}
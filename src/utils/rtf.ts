@@ -0,0 +1,344 @@
+/**
+ * RTF helpers - Convert the RTF blobs stored in ProPresenter text elements
+ * into plain text, or into formatted text runs.
+ */
+
+import type { TextRun } from '../propresenter-client';
+
+// Destinations whose contents are never visible text
+const SKIPPED_DESTINATIONS = new Set([
+  'fonttbl', 'colortbl', 'expandedcolortbl', 'stylesheet', 'info', 'pict',
+  'header', 'footer', 'listtable', 'listoverridetable', 'generator', '*',
+]);
+
+interface CharStyle {
+  bold: boolean;
+  italic: boolean;
+  underline: boolean;
+  /** Hex color without '#', null for the automatic color */
+  color: string | null;
+}
+
+interface GroupState {
+  skip: boolean;
+  style: CharStyle;
+}
+
+const PLAIN_STYLE: CharStyle = { bold: false, italic: false, underline: false, color: null };
+
+// Windows-1252 characters for bytes 0x80-0x9F, where it differs from Latin-1.
+// ProPresenter writes \ansicpg1252, so curly quotes and dashes arrive as \'93 etc.
+const CP1252_HIGH = [
+  '\u20AC', '\u0081', '\u201A', '\u0192', '\u201E', '\u2026', '\u2020', '\u2021',
+  '\u02C6', '\u2030', '\u0160', '\u2039', '\u0152', '\u008D', '\u017D', '\u008F',
+  '\u0090', '\u2018', '\u2019', '\u201C', '\u201D', '\u2022', '\u2013', '\u2014',
+  '\u02DC', '\u2122', '\u0161', '\u203A', '\u0153', '\u009D', '\u017E', '\u0178',
+];
+
+/**
+ * Decode a \'xx byte as Windows-1252
+ */
+export function decodeCp1252(byte: number): string {
+  return byte >= 0x80 && byte <= 0x9f ? CP1252_HIGH[byte - 0x80] : String.fromCharCode(byte);
+}
+
+/**
+ * Read the color table. Index 0 is the automatic color.
+ */
+function parseColorTable(rtf: string): Array<string | null> {
+  const match = /\{\\colortbl([^}]*)\}/.exec(rtf);
+  if (!match) return [null];
+  return match[1].split(';').map(entry => {
+    const red = /\\red(\d+)/.exec(entry);
+    const green = /\\green(\d+)/.exec(entry);
+    const blue = /\\blue(\d+)/.exec(entry);
+    if (!red || !green || !blue) return null;
+    return [red, green, blue]
+      .map(component => parseInt(component[1], 10).toString(16).padStart(2, '0'))
+      .join('')
+      .toUpperCase();
+  });
+}
+
+/**
+ * Walk an RTF document, calling emit for each piece of visible text with the
+ * character formatting in effect
+ */
+function walkRtf(rtf: string, emit: (text: string, style: CharStyle) => void): void {
+  const colors = parseColorTable(rtf);
+  let i = 0;
+  // Skip flag and character formatting per group depth
+  const stack: GroupState[] = [{ skip: false, style: { ...PLAIN_STYLE } }];
+  let pendingUnicodeSkip = 0;
+  let ucSkip = 1;
+
+  const current = () => stack[stack.length - 1];
+  const skipping = () => current().skip;
+  const out = (text: string) => {
+    if (!skipping()) emit(text, current().style);
+  };
+
+  while (i < rtf.length) {
+    const ch = rtf[i];
+
+    if (ch === '{') {
+      stack.push({ skip: skipping(), style: { ...current().style } });
+      i++;
+      continue;
+    }
+    if (ch === '}') {
+      if (stack.length > 1) stack.pop();
+      i++;
+      continue;
+    }
+    if (ch === '\\') {
+      const next = rtf[i + 1];
+      if (next === undefined) break;
+
+      // Escaped literals
+      if (next === '\\' || next === '{' || next === '}') {
+        out(next);
+        i += 2;
+        continue;
+      }
+      // Hex-encoded character (\'xx)
+      if (next === '\'') {
+        const hex = rtf.slice(i + 2, i + 4);
+        if (pendingUnicodeSkip > 0) {
+          pendingUnicodeSkip--;
+        } else {
+          out(decodeCp1252(parseInt(hex, 16)));
+        }
+        i += 4;
+        continue;
+      }
+      // Line breaks written as backslash + newline
+      if (next === '\n' || next === '\r') {
+        out('\n');
+        i += 2;
+        continue;
+      }
+      // Ignorable destination marker
+      if (next === '*') {
+        current().skip = true;
+        i += 2;
+        continue;
+      }
+      if (!/[a-zA-Z]/.test(next)) {
+        // Other control symbols (\~ non-breaking space, \- optional hyphen, ...)
+        if (next === '~') out(' ');
+        i += 2;
+        continue;
+      }
+
+      const match = /^\\([a-zA-Z]+)(-?\d+)? ?/.exec(rtf.slice(i));
+      if (!match) {
+        i++;
+        continue;
+      }
+      const word = match[1];
+      const param = match[2] !== undefined ? parseInt(match[2], 10) : undefined;
+      i += match[0].length;
+
+      if (SKIPPED_DESTINATIONS.has(word)) {
+        current().skip = true;
+        continue;
+      }
+      if (skipping()) continue;
+
+      const style = current().style;
+      switch (word) {
+        case 'par':
+        case 'line':
+          out('\n');
+          break;
+        case 'tab':
+          out('\t');
+          break;
+        case 'uc':
+          ucSkip = param ?? 1;
+          break;
+        case 'u':
+          if (param !== undefined) {
+            out(String.fromCharCode(param < 0 ? param + 65536 : param));
+            pendingUnicodeSkip = ucSkip;
+          }
+          break;
+        case 'emdash':
+          out('—');
+          break;
+        case 'endash':
+          out('–');
+          break;
+        case 'lquote':
+          out('‘');
+          break;
+        case 'rquote':
+          out('’');
+          break;
+        case 'ldblquote':
+          out('“');
+          break;
+        case 'rdblquote':
+          out('”');
+          break;
+        case 'b':
+          style.bold = param !== 0;
+          break;
+        case 'i':
+          style.italic = param !== 0;
+          break;
+        case 'ul':
+          style.underline = param !== 0;
+          break;
+        case 'ulnone':
+          style.underline = false;
+          break;
+        case 'cf':
+          style.color = colors[param ?? 0] ?? null;
+          break;
+        case 'plain':
+          Object.assign(style, PLAIN_STYLE);
+          break;
+        default:
+          break;
+      }
+      continue;
+    }
+
+    if (ch === '\r' || ch === '\n') {
+      i++;
+      continue;
+    }
+
+    if (pendingUnicodeSkip > 0) {
+      pendingUnicodeSkip--;
+    } else {
+      out(ch);
+    }
+    i++;
+  }
+}
+
+/**
+ * Strip RTF control words and groups, keeping the text content.
+ * Paragraph and line breaks become newlines.
+ */
+export function rtfToPlainText(rtf: string): string {
+  if (!rtf || !rtf.trimStart().startsWith('{\\rtf')) {
+    return rtf || '';
+  }
+
+  let out = '';
+  walkRtf(rtf, text => { out += text; });
+
+  return out
+    .split('\n')
+    .map(line => line.replace(/\s+$/, ''))
+    .join('\n')
+    .trim();
+}
+
+/**
+ * Most common value of a style attribute, weighted by visible characters
+ */
+function dominant<T>(chars: Array<{ ch: string; style: CharStyle }>, pick: (style: CharStyle) => T): T {
+  const counts = new Map<T, number>();
+  for (const { ch, style } of chars) {
+    if (/\s/.test(ch)) continue;
+    const value = pick(style);
+    counts.set(value, (counts.get(value) ?? 0) + 1);
+  }
+  let best = pick(PLAIN_STYLE);
+  let bestCount = -1;
+  for (const [value, count] of counts) {
+    if (count > bestCount) {
+      best = value;
+      bestCount = count;
+    }
+  }
+  return best;
+}
+
+/**
+ * Convert RTF into text runs. Formatting that covers most of the text is the
+ * element's base style and is left out, so runs only mark the exceptions:
+ * the italic response line, the bold leader part, a coloured word. The runs'
+ * text joins to exactly what rtfToPlainText returns.
+ */
+export function rtfToRuns(rtf: string): TextRun[] {
+  if (!rtf || !rtf.trimStart().startsWith('{\\rtf')) {
+    return rtf ? [{ text: rtf }] : [];
+  }
+
+  const chars: Array<{ ch: string; style: CharStyle }> = [];
+  walkRtf(rtf, (text, style) => {
+    for (const ch of text) chars.push({ ch, style: { ...style } });
+  });
+
+  // Same trimming as rtfToPlainText: trailing spaces per line, then both ends
+  const kept = chars.filter((entry, index) => {
+    if (entry.ch === '\n' || !/\s/.test(entry.ch)) return true;
+    for (let j = index + 1; j < chars.length; j++) {
+      if (chars[j].ch === '\n') return false;
+      if (!/\s/.test(chars[j].ch)) return true;
+    }
+    return false;
+  });
+  let start = 0;
+  let end = kept.length;
+  while (start < end && /\s/.test(kept[start].ch)) start++;
+  while (end > start && /\s/.test(kept[end - 1].ch)) end--;
+  const trimmed = kept.slice(start, end);
+
+  const base = {
+    bold: dominant(trimmed, style => style.bold),
+    italic: dominant(trimmed, style => style.italic),
+    underline: dominant(trimmed, style => style.underline),
+    color: dominant(trimmed, style => style.color),
+  };
+
+  const runs: TextRun[] = [];
+  for (const { ch, style } of trimmed) {
+    const run: TextRun = { text: ch };
+    if (style.bold !== base.bold) run.bold = style.bold;
+    if (style.italic !== base.italic) run.italic = style.italic;
+    if (style.underline !== base.underline) run.underline = style.underline;
+    if (style.color !== base.color && style.color) run.color = style.color;
+
+    const last = runs[runs.length - 1];
+    if (last
+      && last.bold === run.bold
+      && last.italic === run.italic
+      && last.underline === run.underline
+      && last.color === run.color) {
+      last.text += ch;
+    } else {
+      runs.push(run);
+    }
+  }
+
+  return runs;
+}
+
+/**
+ * Runs for a slide made of several RTF text elements, one element per line
+ * block. Returns undefined when nothing in the slide is formatted differently
+ * from its base style, so plain slides stay plain.
+ */
+export function rtfBlobsToRuns(blobs: string[]): TextRun[] | undefined {
+  const runs: TextRun[] = [];
+  for (const blob of blobs) {
+    if (!rtfToPlainText(blob).trim()) continue;
+    if (runs.length > 0) runs.push({ text: '\n' });
+    runs.push(...rtfToRuns(blob));
+  }
+  return runs.some(hasFormatting) ? runs : undefined;
+}
+
+function hasFormatting(run: TextRun): boolean {
+  return run.bold !== undefined
+    || run.italic !== undefined
+    || run.underline !== undefined
+    || run.color !== undefined;
+}
@@ -0,0 +1,120 @@
+/**
+ * Text run helpers - Line handling for formatted slide text
+ */
+
+import type { TextRun } from '../propresenter-client';
+
+export function runsToText(runs: TextRun[]): string {
+  return runs.map(run => run.text).join('');
+}
+
+/**
+ * Break runs at newlines into one run list per line
+ */
+export function splitRunsIntoLines(runs: TextRun[]): TextRun[][] {
+  const lines: TextRun[][] = [[]];
+  for (const run of runs) {
+    run.text.split('\n').forEach((part, index) => {
+      if (index > 0) lines.push([]);
+      if (part) lines[lines.length - 1].push({ ...run, text: part });
+    });
+  }
+  return lines;
+}
+
+/**
+ * Formatted lines for a piece of the slide text, such as one chunk of a split
+ * slide. Lines are matched in order by content; returns null when the text
+ * no longer lines up with the runs (e.g. after it was reflowed or edited).
+ */
+export function runsForText(runs: TextRun[], text: string): TextRun[][] | null {
+  const runLines = splitRunsIntoLines(runs);
+  const plain = runLines.map(line => runsToText(line).trim());
+  const result: TextRun[][] = [];
+  let cursor = 0;
+
+  for (const line of text.split('\n')) {
+    const wanted = line.trim();
+    let found = -1;
+    for (let i = cursor; i < plain.length; i++) {
+      if (plain[i] === wanted) {
+        found = i;
+        break;
+      }
+    }
+    if (found === -1) return null;
+    result.push(runLines[found]);
+    cursor = found + 1;
+  }
+
+  return result;
+}
+
+/**
+ * Cut runs into consecutive parts holding the given numbers of non-space
+ * characters, e.g. one part per text element of a bilingual slide
+ */
+export function partitionRuns(runs: TextRun[], sizes: number[]): TextRun[][] {
+  const parts: TextRun[][] = sizes.map(() => []);
+  let part = 0;
+  let used = 0;
+  for (const run of runs) {
+    let piece: TextRun | null = null;
+    for (const ch of run.text) {
+      const visible = !/\s/.test(ch);
+      if (visible && used >= sizes[part] && part < sizes.length - 1) {
+        part++;
+        used = 0;
+        piece = null;
+      }
+      if (visible) used++;
+      if (!piece) {
+        piece = { ...run, text: '' };
+        parts[part].push(piece);
+      }
+      piece.text += ch;
+    }
+  }
+  return parts;
+}
+
+function sameStyle(a: TextRun, b: TextRun): boolean {
+  return a.bold === b.bold && a.italic === b.italic && a.underline === b.underline && a.color === b.color;
+}
+
+/**
+ * Carry run styles over to the same words laid out with other line breaks.
+ * Each call styles the next piece of the text, so a slide split into chunks
+ * is styled chunk by chunk. Returns undefined once the words stop matching.
+ */
+export function restyler(runs: TextRun[]): (text: string) => TextRun[] | undefined {
+  const chars = runs.flatMap(run => [...run.text].filter(ch => !/\s/.test(ch)).map(ch => ({ ch, run })));
+  let position = 0;
+  let failed = false;
+
+  return text => {
+    if (failed) return undefined;
+    const result: TextRun[] = [];
+    for (const ch of text) {
+      let style: TextRun;
+      if (/\s/.test(ch)) {
+        // Spaces take the style of the word before them
+        style = chars[Math.max(0, position - 1)]?.run ?? runs[0];
+      } else {
+        const source = chars[position++];
+        if (!source || source.ch !== ch) {
+          failed = true;
+          return undefined;
+        }
+        style = source.run;
+      }
+      const last = result[result.length - 1];
+      if (last && sameStyle(last, style)) {
+        last.text += ch;
+      } else {
+        result.push({ ...style, text: ch });
+      }
+    }
+    return result;
+  };
+}
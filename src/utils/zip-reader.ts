@@ -0,0 +1,95 @@
+/**
+ * Minimal ZIP archive reader
+ *
+ * ProPresenter .proplaylist bundles are plain ZIP archives. This reads the
+ * central directory and inflates entries with Node's zlib so we don't need
+ * an extra dependency just to open them.
+ */
+
+import * as zlib from 'zlib';
+
+export interface ZipEntry {
+  name: string;
+  compressedSize: number;
+  uncompressedSize: number;
+  method: number;
+  localHeaderOffset: number;
+}
+
+const EOCD_SIGNATURE = 0x06054b50;
+const CENTRAL_SIGNATURE = 0x02014b50;
+const LOCAL_SIGNATURE = 0x04034b50;
+
+export class ZipReader {
+  private buf: Buffer;
+  readonly entries: ZipEntry[];
+
+  constructor(buf: Buffer) {
+    this.buf = buf;
+    this.entries = this.readCentralDirectory();
+  }
+
+  private readCentralDirectory(): ZipEntry[] {
+    // End of central directory record sits within the last 64KB + 22 bytes
+    const searchStart = Math.max(0, this.buf.length - 0xffff - 22);
+    let eocd = -1;
+    for (let i = this.buf.length - 22; i >= searchStart; i--) {
+      if (this.buf.readUInt32LE(i) === EOCD_SIGNATURE) {
+        eocd = i;
+        break;
+      }
+    }
+    if (eocd < 0) {
+      throw new Error('Not a ZIP archive (no end of central directory)');
+    }
+
+    const entryCount = this.buf.readUInt16LE(eocd + 10);
+    let pos = this.buf.readUInt32LE(eocd + 16);
+    const entries: ZipEntry[] = [];
+
+    for (let i = 0; i < entryCount; i++) {
+      if (this.buf.readUInt32LE(pos) !== CENTRAL_SIGNATURE) {
+        throw new Error('Corrupt ZIP central directory');
+      }
+      const method = this.buf.readUInt16LE(pos + 10);
+      const compressedSize = this.buf.readUInt32LE(pos + 20);
+      const uncompressedSize = this.buf.readUInt32LE(pos + 24);
+      const nameLength = this.buf.readUInt16LE(pos + 28);
+      const extraLength = this.buf.readUInt16LE(pos + 30);
+      const commentLength = this.buf.readUInt16LE(pos + 32);
+      const localHeaderOffset = this.buf.readUInt32LE(pos + 42);
+      const name = this.buf.toString('utf-8', pos + 46, pos + 46 + nameLength);
+
+      entries.push({ name, compressedSize, uncompressedSize, method, localHeaderOffset });
+      pos += 46 + nameLength + extraLength + commentLength;
+    }
+
+    return entries;
+  }
+
+  /**
+   * Read and decompress a single entry
+   */
+  read(entry: ZipEntry): Buffer {
+    const pos = entry.localHeaderOffset;
+    if (this.buf.readUInt32LE(pos) !== LOCAL_SIGNATURE) {
+      throw new Error(`Corrupt ZIP entry: ${entry.name}`);
+    }
+    const nameLength = this.buf.readUInt16LE(pos + 26);
+    const extraLength = this.buf.readUInt16LE(pos + 28);
+    const start = pos + 30 + nameLength + extraLength;
+    const data = this.buf.subarray(start, start + entry.compressedSize);
+
+    if (entry.method === 0) {
+      return Buffer.from(data);
+    }
+    if (entry.method === 8) {
+      return zlib.inflateRawSync(data);
+    }
+    throw new Error(`Unsupported ZIP compression method ${entry.method} for ${entry.name}`);
+  }
+
+  find(predicate: (entry: ZipEntry) => boolean): ZipEntry | undefined {
+    return this.entries.find(predicate);
+  }
+}